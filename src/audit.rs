@@ -0,0 +1,431 @@
+//! Audit log of tool calls.
+//!
+//! When [`Config::audit_log`] is set, every `tools/call` handled by
+//! [`crate::MathService`] is appended to that file as one JSON line: when it
+//! happened, the session, the tool, a hash of the arguments (and, unless
+//! only hashes are kept, the arguments themselves), a summary of the result
+//! or the error code, and how long it took. Arrays longer than
+//! [`Config::audit_max_array_len`] are replaced by their length and hash so
+//! lines stay small. The file is rotated once it reaches
+//! [`Config::audit_max_bytes`].
+
+use crate::config::Config;
+use crate::error::McpError;
+use crate::tools::cache::canonical_json;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::error;
+
+/// Open audit logs by path, so every session appends through the same writer
+static AUDIT_LOGS: Lazy<Mutex<HashMap<PathBuf, Arc<AuditLog>>>> = Lazy::new(Default::default);
+
+/// How the arguments of a call are recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuditArguments {
+    /// The arguments, with long arrays replaced by their length and hash
+    #[default]
+    Redacted,
+    /// Only the hash of the arguments
+    Hash,
+}
+
+impl FromStr for AuditArguments {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "redacted" => Ok(Self::Redacted),
+            "hash" => Ok(Self::Hash),
+            other => Err(format!(
+                "unknown argument mode '{}' (expected 'redacted' or 'hash')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AuditArguments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Redacted => write!(f, "redacted"),
+            Self::Hash => write!(f, "hash"),
+        }
+    }
+}
+
+/// An append-only JSONL file of tool calls with size-based rotation.
+///
+/// Once writing a line would take the file past `max_bytes` it is renamed to
+/// `<path>.1`, earlier rotations move up by one and the oldest beyond
+/// `max_files` is deleted.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::audit::{AuditArguments, AuditLog};
+/// use serde_json::json;
+///
+/// let path = std::env::temp_dir().join(format!("audit-doc-{}.jsonl", std::process::id()));
+/// let log = AuditLog::open(&path, 1024 * 1024, 2, AuditArguments::Redacted, 16).unwrap();
+/// let call = log.begin(1, "add", &json!({ "numbers": [1, 2] }));
+/// log.finish(call, Ok(&json!({ "result": 3.0 })));
+///
+/// let line = std::fs::read_to_string(&path).unwrap();
+/// let entry: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+/// assert_eq!(entry["tool"], "add");
+/// assert_eq!(entry["result"], 3.0);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    arguments: AuditArguments,
+    max_array_len: usize,
+    writer: Mutex<Writer>,
+}
+
+#[derive(Debug)]
+struct Writer {
+    file: File,
+    /// Bytes in the current file
+    size: u64,
+}
+
+/// A call in progress, written out by [`AuditLog::finish`]
+#[derive(Debug)]
+pub struct AuditCall {
+    timestamp: SystemTime,
+    started: Instant,
+    session: u64,
+    tool: String,
+    arguments_hash: String,
+    arguments: Option<Value>,
+}
+
+/// One line of the audit log
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    timestamp: String,
+    session: u64,
+    tool: &'a str,
+    arguments_hash: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arguments: Option<&'a Value>,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+    duration_ms: f64,
+}
+
+impl AuditLog {
+    /// Open (or create) the log at `path`, appending to what is already there
+    pub fn open(
+        path: impl Into<PathBuf>,
+        max_bytes: u64,
+        max_files: usize,
+        arguments: AuditArguments,
+        max_array_len: usize,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        let writer = Writer::open(&path)?;
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            arguments,
+            max_array_len,
+            writer: Mutex::new(writer),
+        })
+    }
+
+    /// The log `config` asks for, shared with every other session writing to
+    /// the same path; `None` when auditing is off.
+    ///
+    /// The first caller's rotation and redaction settings apply to a path.
+    pub fn shared(config: &Config) -> io::Result<Option<Arc<Self>>> {
+        let Some(path) = &config.audit_log else {
+            return Ok(None);
+        };
+        let mut logs = AUDIT_LOGS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(log) = logs.get(path) {
+            return Ok(Some(Arc::clone(log)));
+        }
+        let log = Arc::new(Self::open(
+            path,
+            config.audit_max_bytes,
+            config.audit_max_files,
+            config.audit_arguments,
+            config.audit_max_array_len,
+        )?);
+        logs.insert(path.clone(), Arc::clone(&log));
+        Ok(Some(log))
+    }
+
+    /// Path of the current log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Start recording a call; the clock runs until [`finish`](Self::finish)
+    pub fn begin(&self, session: u64, tool: &str, arguments: &Value) -> AuditCall {
+        AuditCall {
+            timestamp: SystemTime::now(),
+            started: Instant::now(),
+            session,
+            tool: tool.to_string(),
+            arguments_hash: hash(arguments),
+            arguments: match self.arguments {
+                AuditArguments::Redacted => Some(redact(arguments, self.max_array_len)),
+                AuditArguments::Hash => None,
+            },
+        }
+    }
+
+    /// Write out a call with its outcome.
+    ///
+    /// Failing to write is logged rather than returned: auditing never fails
+    /// the call itself.
+    pub fn finish(&self, call: AuditCall, outcome: Result<&Value, &McpError>) {
+        let duration_ms = call.started.elapsed().as_secs_f64() * 1000.0;
+        let (result, error) = match outcome {
+            Ok(result) => (Some(self.summarize(result)), None),
+            Err(e) => (None, Some(json!({ "code": e.code, "message": e.message }))),
+        };
+        let entry = AuditEntry {
+            timestamp: format_timestamp(call.timestamp),
+            session: call.session,
+            tool: &call.tool,
+            arguments_hash: &call.arguments_hash,
+            arguments: call.arguments.as_ref(),
+            success: outcome.is_ok(),
+            result,
+            error,
+            duration_ms,
+        };
+        if let Err(e) = self.write(&entry) {
+            error!(path = %self.path.display(), error = %e, "Failed to write audit log");
+        }
+    }
+
+    /// The part of a tool result worth keeping: its `result` (or a batch's
+    /// `summary`) when it has one, redacted
+    fn summarize(&self, result: &Value) -> Value {
+        let summary = result
+            .get("result")
+            .or_else(|| result.get("summary"))
+            .unwrap_or(result);
+        redact(summary, self.max_array_len)
+    }
+
+    fn write(&self, entry: &AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let mut writer = self.lock();
+        if writer.size > 0 && writer.size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+            *writer = Writer::open(&self.path)?;
+        }
+        writer.file.write_all(&line)?;
+        writer.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1`, dropping the oldest, then move the
+    /// current file to `<path>.1`
+    fn rotate(&self) -> io::Result<()> {
+        if self.max_files == 0 {
+            return fs::remove_file(&self.path);
+        }
+        match fs::remove_file(rotated_path(&self.path, self.max_files)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        for index in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Writer> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Writer {
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { file, size })
+    }
+}
+
+/// `<path>.<index>`
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Hex FNV-1a hash of `value` in canonical form, so the same arguments always
+/// hash the same however they were written
+fn hash(value: &Value) -> String {
+    let hash = canonical_json(value)
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// `value` with every array longer than `max_len` replaced by its length and hash
+fn redact(value: &Value, max_len: usize) -> Value {
+    match value {
+        Value::Array(items) if items.len() > max_len => json!({
+            "redacted": "array",
+            "length": items.len(),
+            "hash": hash(value),
+        }),
+        Value::Array(items) => items.iter().map(|item| redact(item, max_len)).collect(),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, field)| (name.clone(), redact(field, max_len)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// RFC 3339 UTC timestamp with milliseconds, e.g. `2024-05-01T12:30:00.250Z`
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rust-math-mcp-audit-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        for index in 0..5 {
+            let _ = fs::remove_file(rotated_path(&path, index));
+        }
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_timestamps() {
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let time = UNIX_EPOCH + Duration::from_millis(1_709_210_096_789);
+        assert_eq!(format_timestamp(time), "2024-02-29T12:34:56.789Z");
+    }
+
+    #[test]
+    fn test_redacts_long_arrays() {
+        let value = json!({ "numbers": [1, 2, 3], "nested": { "matrix": [[1, 2], [3, 4]] } });
+        assert_eq!(redact(&value, 3), value);
+
+        let redacted = redact(&value, 2);
+        assert_eq!(redacted["numbers"]["redacted"], "array");
+        assert_eq!(redacted["numbers"]["length"], 3);
+        assert_eq!(redacted["numbers"]["hash"], hash(&json!([1, 2, 3])));
+        assert_eq!(redacted["nested"]["matrix"], json!([[1, 2], [3, 4]]));
+    }
+
+    #[test]
+    fn test_hash_is_canonical() {
+        assert_eq!(
+            hash(&json!({ "a": 1, "b": 2 })),
+            hash(&json!({ "b": 2.0, "a": 1.0 }))
+        );
+        assert_ne!(hash(&json!({ "a": 1 })), hash(&json!({ "a": 2 })));
+        assert_eq!(hash(&json!(null)).len(), 16);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let path = temp_path("rotation.jsonl");
+        let log = AuditLog::open(&path, 400, 2, AuditArguments::Hash, 16).unwrap();
+        for _ in 0..10 {
+            let call = log.begin(7, "add", &json!({ "numbers": [1, 2] }));
+            log.finish(call, Ok(&json!({ "result": 3.0 })));
+        }
+
+        for file in [&path, &rotated_path(&path, 1), &rotated_path(&path, 2)] {
+            let text = fs::read_to_string(file).unwrap();
+            assert!(text.len() <= 400);
+            for line in text.lines() {
+                let entry: Value = serde_json::from_str(line).unwrap();
+                assert_eq!(entry["session"], 7);
+                assert!(entry.get("arguments").is_none());
+            }
+        }
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn test_error_entries() {
+        let path = temp_path("errors.jsonl");
+        let log = AuditLog::open(&path, 1 << 20, 1, AuditArguments::Redacted, 16).unwrap();
+        let call = log.begin(1, "divide", &json!({ "a": 1, "b": 0 }));
+        log.finish(call, Err(&McpError::validation_error("Division by zero")));
+
+        let entry: Value = serde_json::from_str(fs::read_to_string(&path).unwrap().trim()).unwrap();
+        assert_eq!(entry["success"], false);
+        assert_eq!(entry["error"]["code"], -32001);
+        assert_eq!(entry["arguments"], json!({ "a": 1, "b": 0 }));
+        assert!(entry.get("result").is_none());
+        assert!(entry["duration_ms"].as_f64().unwrap() >= 0.0);
+    }
+}
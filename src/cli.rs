@@ -0,0 +1,423 @@
+//! Calling tools from the command line, without an MCP client.
+//!
+//! Backs the binary's `call`, `list` and `repl` subcommands. Calls go through
+//! [`DefaultToolRegistry`] under the server's configuration, so arguments
+//! are validated and errors reported exactly as they are over MCP.
+
+use crate::config::Config;
+use crate::error::{McpError, McpResult};
+use crate::tools::registry::{get_enabled_tools, tool_category};
+use crate::tools::{DefaultToolRegistry, ToolContext, ToolRegistry, CATEGORIES};
+use crate::utils::output::apply_output_settings;
+use serde_json::{json, Value};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Default REPL history file name, created in the home directory
+pub const HISTORY_FILE_NAME: &str = ".rust-math-mcp_history";
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// The tool's JSON result, as an MCP client would see it
+    Json,
+    /// Indented `name: value` lines
+    #[default]
+    Text,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            other => Err(format!(
+                "unknown format '{}' (expected 'json' or 'text')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// Call `tool` with `arguments`, a JSON object, under `config`, with the
+/// result rounded per the `[output]` settings like the server's.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::cli;
+/// use rust_math_mcp::config::Config;
+/// use std::sync::Arc;
+///
+/// let config = Arc::new(Config::new());
+/// let result = cli::call(&config, "mean", r#"{"numbers": [1, 2, 3]}"#).unwrap();
+/// assert_eq!(result["result"], 2.0);
+///
+/// let err = cli::call(&config, "mean", r#"{"numbers": "1, 2"}"#).unwrap_err();
+/// assert_eq!(err.code, -32602);
+/// ```
+pub fn call(config: &Arc<Config>, tool: &str, arguments: &str) -> McpResult<Value> {
+    let arguments: Value = serde_json::from_str(arguments)
+        .map_err(|e| McpError::invalid_params(format!("Arguments are not valid JSON: {}", e)))?;
+    let ctx = ToolContext::new()
+        .with_config(Arc::clone(config))
+        .with_timeout(config.tool_timeout(tool));
+    let mut result = DefaultToolRegistry.execute_tool_with_context(tool, &arguments, &ctx)?;
+    apply_output_settings(&mut result, config);
+    Ok(result)
+}
+
+/// The definitions of the tools `config` enables, each with its `category`,
+/// optionally only those in `category`
+pub fn list(config: &Config, category: Option<&str>) -> McpResult<Vec<Value>> {
+    if let Some(category) = category {
+        if !CATEGORIES.iter().any(|known| known.name == category) {
+            return Err(McpError::invalid_params(format!(
+                "Unknown category '{}' (expected one of: {})",
+                category,
+                CATEGORIES
+                    .iter()
+                    .map(|known| known.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+    }
+
+    Ok(get_enabled_tools(config)
+        .into_iter()
+        .filter_map(|mut tool| {
+            let name = tool["name"].as_str()?;
+            let tool_category = tool_category(name).map(|category| category.name);
+            if category.is_some() && tool_category != category {
+                return None;
+            }
+            if let (Some(fields), Some(tool_category)) = (tool.as_object_mut(), tool_category) {
+                fields.insert("category".to_string(), json!(tool_category));
+            }
+            Some(tool)
+        })
+        .collect())
+}
+
+/// Format a tool result for printing
+pub fn format_result(result: &Value, format: OutputFormat, pretty: bool) -> String {
+    match format {
+        OutputFormat::Json if pretty => {
+            serde_json::to_string_pretty(result).unwrap_or_else(|_| result.to_string())
+        }
+        OutputFormat::Json => result.to_string(),
+        OutputFormat::Text => {
+            let mut out = String::new();
+            write_text(result, 0, &mut out);
+            out.trim_end().to_string()
+        }
+    }
+}
+
+/// Format an error for printing: the `structuredContent` of the `isError`
+/// result a client would get as JSON, or its message and `data` as text
+pub fn format_error(error: &McpError, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => json!({
+            "error": { "code": error.code, "message": error.message, "data": error.data }
+        })
+        .to_string(),
+        OutputFormat::Text => {
+            let mut out = format!("Error: {}", error.message);
+            if let Some(data) = &error.data {
+                out.push('\n');
+                write_text(data, 1, &mut out);
+            }
+            out.trim_end().to_string()
+        }
+    }
+}
+
+/// Format tool definitions from [`list`]: the definitions as a JSON array, or
+/// one `name  description` line per tool under its category
+pub fn format_tools(tools: &[Value], format: OutputFormat, pretty: bool) -> String {
+    if format == OutputFormat::Json {
+        return format_result(&Value::from(tools.to_vec()), format, pretty);
+    }
+
+    let width = tools
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    let mut current = None;
+    for tool in tools {
+        let category = tool["category"].as_str().unwrap_or("composite");
+        if current != Some(category) {
+            if current.is_some() {
+                out.push('\n');
+            }
+            out.push_str(category);
+            out.push('\n');
+            current = Some(category);
+        }
+        out.push_str(&format!(
+            "  {:width$}  {}\n",
+            tool["name"].as_str().unwrap_or_default(),
+            tool["description"].as_str().unwrap_or_default(),
+            width = width
+        ));
+    }
+    out.trim_end().to_string()
+}
+
+/// Write `value` as indented `name: value` lines
+fn write_text(value: &Value, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                if is_inline(field) {
+                    out.push_str(&format!("{}{}: {}\n", indent, name, inline(field)));
+                } else {
+                    out.push_str(&format!("{}{}:\n", indent, name));
+                    write_text(field, depth + 1, out);
+                }
+            }
+        }
+        Value::Array(items) if !is_inline(value) => {
+            for item in items {
+                if is_inline(item) {
+                    out.push_str(&format!("{}- {}\n", indent, inline(item)));
+                } else {
+                    out.push_str(&format!("{}-\n", indent));
+                    write_text(item, depth + 1, out);
+                }
+            }
+        }
+        other => out.push_str(&format!("{}{}\n", indent, inline(other))),
+    }
+}
+
+/// Whether `value` fits on one line: a scalar or an array of scalars
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => false,
+        Value::Array(items) => items
+            .iter()
+            .all(|item| !item.is_object() && !item.is_array()),
+        _ => true,
+    }
+}
+
+/// `value` on one line, with strings unquoted
+fn inline(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(inline).collect();
+            format!("[{}]", items.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Lines entered in the REPL, kept in a file between sessions
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<String>,
+    file: Option<PathBuf>,
+}
+
+impl History {
+    /// History that is not saved
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// History loaded from `path`, with new lines appended to it
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(text) => text.lines().map(str::to_string).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            entries,
+            file: Some(path),
+        })
+    }
+
+    /// `~/.rust-math-mcp_history`, if the home directory is known
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE_NAME))
+    }
+
+    /// Lines entered so far, oldest first
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Add a line, saving it if the history has a file
+    pub fn push(&mut self, line: &str) -> io::Result<()> {
+        self.entries.push(line.to_string());
+        if let Some(path) = &self.file {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Expand `!!` (the last line) and `!N` (line N, counting from 1)
+    fn expand(&self, line: &str) -> Result<String, String> {
+        let Some(reference) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+        let entry = if reference == "!" {
+            self.entries.last()
+        } else {
+            reference
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| index.checked_sub(1))
+                .and_then(|index| self.entries.get(index))
+        };
+        entry
+            .cloned()
+            .ok_or_else(|| format!("No history entry '{}'", line))
+    }
+}
+
+const REPL_HELP: &str = "\
+Commands:
+  <tool> [arguments]   call a tool, e.g. mean {\"numbers\": [1, 2, 3]}
+  list [category]      list the available tools
+  format json|text     change how results are printed
+  history              show earlier lines; !! repeats the last, !N line N
+  help                 show this help
+  quit                 leave (or end input)";
+
+/// Read lines from `input` and run them until `quit` or the end of input,
+/// printing prompts, results and errors to `output`
+pub fn repl(
+    config: &Arc<Config>,
+    input: impl BufRead,
+    mut output: impl Write,
+    history: &mut History,
+    mut format: OutputFormat,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "rust-math-mcp {} - type 'help' for commands",
+        config.server_version()
+    )?;
+    let mut lines = input.lines();
+    loop {
+        write!(output, "math> ")?;
+        output.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            writeln!(output)?;
+            return Ok(());
+        };
+        let line = match history.expand(line.trim()) {
+            Ok(line) => line,
+            Err(message) => {
+                writeln!(output, "{}", message)?;
+                continue;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+        // Repeating the last line does not add it again
+        if history.entries().last() != Some(&line) {
+            history.push(&line)?;
+        }
+
+        let (command, rest) = line
+            .split_once(char::is_whitespace)
+            .map_or((line.as_str(), ""), |(command, rest)| {
+                (command, rest.trim())
+            });
+        let printed = match command {
+            "quit" | "exit" => return Ok(()),
+            "help" => REPL_HELP.to_string(),
+            "history" => history
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| format!("{:5}  {}", index + 1, entry))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "format" => match rest.parse() {
+                Ok(new_format) => {
+                    format = new_format;
+                    format!("Printing results as {}", format)
+                }
+                Err(message) => message,
+            },
+            "list" => {
+                let category = Some(rest).filter(|rest| !rest.is_empty());
+                match list(config, category) {
+                    Ok(tools) => format_tools(&tools, format, config.pretty_output),
+                    Err(e) => format_error(&e, format),
+                }
+            }
+            tool => {
+                let arguments = if rest.is_empty() { "{}" } else { rest };
+                match call(config, tool, arguments) {
+                    Ok(result) => format_result(&result, format, config.pretty_output),
+                    Err(e) => format_error(&e, format),
+                }
+            }
+        };
+        writeln!(output, "{}", printed)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_format() {
+        let value = json!({
+            "result": 2.5,
+            "roots": [1, "two"],
+            "summary": { "total": 2, "ids": ["a", "b"] },
+            "results": [{ "id": "a" }, 3],
+        });
+        assert_eq!(
+            format_result(&value, OutputFormat::Text, false),
+            "result: 2.5\nresults:\n  -\n    id: a\n  - 3\nroots: [1, two]\n\
+             summary:\n  ids: [a, b]\n  total: 2"
+        );
+    }
+
+    #[test]
+    fn test_history_expansion() {
+        let mut history = History::new();
+        assert!(history.expand("!!").is_err());
+        history.push("add {\"numbers\": [1]}").unwrap();
+        history.push("list").unwrap();
+        assert_eq!(history.expand("!!").unwrap(), "list");
+        assert_eq!(history.expand("!1").unwrap(), "add {\"numbers\": [1]}");
+        assert!(history.expand("!0").is_err());
+        assert!(history.expand("!9").is_err());
+        assert_eq!(history.expand("mean").unwrap(), "mean");
+    }
+}
@@ -1,9 +1,97 @@
+use crate::audit::AuditArguments;
+use crate::tools::composite::{CompositeSpec, CompositeTool};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Default address for the HTTP transport (localhost only)
+pub const DEFAULT_HTTP_BIND: &str = "127.0.0.1:8080";
+
+/// Default address for the raw TCP transport (localhost only)
+pub const DEFAULT_TCP_BIND: &str = "127.0.0.1:9090";
+
+/// Default Unix socket file name, created in the system temp directory
+pub const DEFAULT_SOCKET_NAME: &str = "rust-math-mcp.sock";
+
+/// Default time budget for a single tool call, in milliseconds
+pub const DEFAULT_TOOL_TIMEOUT_MS: u64 = 30_000;
+
+/// Default largest number of operations in one `batch_operations` call
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+
+/// Default largest number of results the result cache holds
+pub const DEFAULT_CACHE_MAX_ENTRIES: usize = 1024;
+
+/// Default largest total size of the result cache, in bytes
+pub const DEFAULT_CACHE_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Default size at which the audit log is rotated, in bytes
+pub const DEFAULT_AUDIT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Default number of rotated audit log files kept
+pub const DEFAULT_AUDIT_MAX_FILES: usize = 5;
+
+/// Default longest array written to the audit log in full
+pub const DEFAULT_AUDIT_MAX_ARRAY_LEN: usize = 16;
+
+/// Most decimal places an `f64` result can meaningfully be rounded to
+pub const MAX_OUTPUT_DECIMAL_PLACES: u32 = 15;
+
+/// Transport the server is exposed over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransportKind {
+    /// Newline-delimited JSON-RPC over stdin/stdout (one client per process)
+    #[default]
+    Stdio,
+    /// MCP streamable HTTP (POST + SSE), shared by any number of clients
+    Http,
+    /// Newline-delimited JSON-RPC over TCP, one session per connection
+    Tcp,
+    /// Newline-delimited JSON-RPC over a Unix domain socket, one session per connection
+    Unix,
+}
+
+impl FromStr for TransportKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "stdio" => Ok(Self::Stdio),
+            "http" => Ok(Self::Http),
+            "tcp" => Ok(Self::Tcp),
+            "unix" => Ok(Self::Unix),
+            other => Err(format!(
+                "unknown transport '{}' (expected 'stdio', 'http', 'tcp' or 'unix')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdio => write!(f, "stdio"),
+            Self::Http => write!(f, "http"),
+            Self::Tcp => write!(f, "tcp"),
+            Self::Unix => write!(f, "unix"),
+        }
+    }
+}
 
 /// Server configuration.
 ///
-/// Provides configurable settings for the MCP server, with defaults
-/// that can be overridden via environment variables.
+/// Settings are layered, each layer overriding the one before:
+///
+/// 1. built-in defaults ([`Config::default`])
+/// 2. a TOML configuration file ([`Config::load`] with a path)
+/// 3. `MCP_*` environment variables
+/// 4. command line flags (applied by the binary)
 ///
 /// # Example
 ///
@@ -12,6 +100,10 @@ use std::env;
 ///
 /// let config = Config::new();
 /// assert_eq!(config.server_name(), "rust-math-mcp");
+///
+/// let config = Config::from_toml("[limits]\nmax_array_size = 500").unwrap();
+/// assert_eq!(config.max_array_size, 500);
+/// assert!(Config::from_toml("[limits]\nmax_array_size = 0").is_err());
 /// ```
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -23,44 +115,264 @@ pub struct Config {
     pub max_array_size: usize,
     /// Maximum number of decimal places for rounding
     pub max_decimal_places: i32,
+    /// Maximum number of operations in one `batch_operations` call
+    pub max_batch_size: usize,
     /// Enable rate limiting
     pub enable_rate_limit: bool,
     /// Maximum requests per second (when rate limiting enabled)
     pub max_requests_per_second: usize,
+    /// Transport to serve on
+    pub transport: TransportKind,
+    /// Address the HTTP transport listens on
+    pub http_bind: SocketAddr,
+    /// Address the TCP transport listens on
+    pub tcp_bind: SocketAddr,
+    /// Path of the Unix socket the Unix transport listens on
+    pub socket_path: PathBuf,
+    /// Time budget for a tool call (zero disables the deadline)
+    pub tool_timeout: Duration,
+    /// Per-tool overrides of `tool_timeout`, keyed by tool name
+    pub tool_timeouts: HashMap<String, Duration>,
+    /// Per-tool rate limits (calls per second), enforced on top of the session limit
+    pub tool_rate_limits: HashMap<String, usize>,
+    /// Rate limit tokens a call to each tool costs (default 1)
+    pub tool_costs: HashMap<String, f64>,
+    /// Only offer these categories (together with `enabled_tools`); `None` offers all
+    pub enabled_categories: Option<BTreeSet<String>>,
+    /// Only offer these tools (together with `enabled_categories`); `None` offers all
+    pub enabled_tools: Option<BTreeSet<String>>,
+    /// Tool categories that are hidden from `tools/list` and refused
+    pub disabled_categories: BTreeSet<String>,
+    /// Tools that are hidden from `tools/list` and refused, whatever their category
+    pub disabled_tools: BTreeSet<String>,
+    /// Tools defined in terms of other tools, listed after the built-in ones
+    pub composite_tools: Vec<CompositeTool>,
+    /// Reuse the results of earlier identical tool calls
+    pub cache_enabled: bool,
+    /// Most results the cache holds
+    pub cache_max_entries: usize,
+    /// Most bytes the cached results take
+    pub cache_max_bytes: usize,
+    /// Tools whose results are never cached, e.g. non-deterministic ones
+    pub cache_exclude: BTreeSet<String>,
+    /// Accept numbers written as strings (`"3.5"`, `"1/3"`, `"5%"`, `"2pi"`)
+    /// and report how each was read in the tool result
+    pub lenient_numbers: bool,
+    /// Append a JSON line for every tool call to this file; `None` disables auditing
+    pub audit_log: Option<PathBuf>,
+    /// Rotate the audit log once it reaches this many bytes
+    pub audit_max_bytes: u64,
+    /// Rotated audit log files kept (`<path>.1` is the newest)
+    pub audit_max_files: usize,
+    /// Record arguments redacted, or only their hash
+    pub audit_arguments: AuditArguments,
+    /// Arrays longer than this are written to the audit log as their length and hash
+    pub audit_max_array_len: usize,
+    /// Pretty-print the JSON text block of tool results
+    pub pretty_output: bool,
+    /// Round every number in tool results to this many decimal places
+    pub output_decimal_places: Option<u32>,
 }
 
 impl Default for Config {
+    /// Built-in defaults, ignoring the environment
     fn default() -> Self {
         Self {
-            server_name: env::var("MCP_SERVER_NAME")
-                .unwrap_or_else(|_| crate::protocol::constants::server::DEFAULT_NAME.to_string()),
-            server_version: env::var("MCP_SERVER_VERSION").unwrap_or_else(|_| {
-                crate::protocol::constants::server::DEFAULT_VERSION.to_string()
-            }),
-            max_array_size: env::var("MCP_MAX_ARRAY_SIZE")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(10_000),
-            max_decimal_places: env::var("MCP_MAX_DECIMAL_PLACES")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(15),
-            enable_rate_limit: env::var("MCP_ENABLE_RATE_LIMIT")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(true), // Enabled by default for security
-            max_requests_per_second: env::var("MCP_MAX_REQUESTS_PER_SECOND")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(1000),
+            server_name: crate::protocol::constants::server::DEFAULT_NAME.to_string(),
+            server_version: crate::protocol::constants::server::DEFAULT_VERSION.to_string(),
+            max_array_size: 10_000,
+            max_decimal_places: 15,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            enable_rate_limit: true, // Enabled by default for security
+            max_requests_per_second: 1000,
+            transport: TransportKind::default(),
+            http_bind: DEFAULT_HTTP_BIND.parse().expect("valid default address"),
+            tcp_bind: DEFAULT_TCP_BIND.parse().expect("valid default address"),
+            socket_path: env::temp_dir().join(DEFAULT_SOCKET_NAME),
+            tool_timeout: Duration::from_millis(DEFAULT_TOOL_TIMEOUT_MS),
+            tool_timeouts: HashMap::new(),
+            tool_rate_limits: HashMap::new(),
+            tool_costs: HashMap::new(),
+            enabled_categories: None,
+            enabled_tools: None,
+            disabled_categories: BTreeSet::new(),
+            disabled_tools: BTreeSet::new(),
+            composite_tools: Vec::new(),
+            cache_enabled: false,
+            cache_max_entries: DEFAULT_CACHE_MAX_ENTRIES,
+            cache_max_bytes: DEFAULT_CACHE_MAX_BYTES,
+            cache_exclude: BTreeSet::new(),
+            lenient_numbers: false,
+            audit_log: None,
+            audit_max_bytes: DEFAULT_AUDIT_MAX_BYTES,
+            audit_max_files: DEFAULT_AUDIT_MAX_FILES,
+            audit_arguments: AuditArguments::default(),
+            audit_max_array_len: DEFAULT_AUDIT_MAX_ARRAY_LEN,
+            pretty_output: false,
+            output_decimal_places: None,
         }
     }
 }
 
 impl Config {
-    /// Create a new configuration instance
+    /// Create a configuration from the defaults and the `MCP_*` environment variables.
+    ///
+    /// If the environment holds an invalid value it is logged and the defaults
+    /// are used; the binary uses [`Config::load`] to reject it instead.
     pub fn new() -> Self {
-        Self::default()
+        let mut config = Self::default();
+        match config.apply_env().and_then(|_| config.validate()) {
+            Ok(()) => config,
+            Err(e) => {
+                tracing::warn!("Ignoring environment configuration: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Load the configuration: defaults, then the TOML file at `path` (if
+    /// given), then the `MCP_*` environment variables.
+    ///
+    /// Unreadable files, unknown keys, malformed values and out-of-range
+    /// settings are all errors.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        if let Some(path) = path {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| ConfigError::new(format!("cannot read {}: {}", path.display(), e)))?;
+            config
+                .apply_toml(&text)
+                .map_err(|e| ConfigError::new(format!("{}: {}", path.display(), e)))?;
+        }
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a TOML configuration over the defaults (ignoring the environment)
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_toml(text)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that the settings are usable.
+    ///
+    /// Call this again after changing fields by hand (e.g. from command line flags).
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_array_size == 0 {
+            return Err(ConfigError::new("limits.max_array_size must be at least 1"));
+        }
+        if self.max_batch_size == 0 {
+            return Err(ConfigError::new("limits.max_batch_size must be at least 1"));
+        }
+        if self.max_decimal_places < 0 {
+            return Err(ConfigError::new(
+                "limits.max_decimal_places must not be negative",
+            ));
+        }
+        if self.enable_rate_limit && self.max_requests_per_second == 0 {
+            return Err(ConfigError::new(
+                "rate_limit.requests_per_second must be at least 1 when rate limiting is enabled",
+            ));
+        }
+        if self.cache_enabled && (self.cache_max_entries == 0 || self.cache_max_bytes == 0) {
+            return Err(ConfigError::new(
+                "cache.max_entries and cache.max_bytes must be at least 1 when the cache is enabled",
+            ));
+        }
+        if self.audit_log.is_some() && self.audit_max_bytes == 0 {
+            return Err(ConfigError::new(
+                "audit.max_bytes must be at least 1 when the audit log is enabled",
+            ));
+        }
+        if let Some(places) = self.output_decimal_places {
+            if places > MAX_OUTPUT_DECIMAL_PLACES {
+                return Err(ConfigError::new(format!(
+                    "output.decimal_places must be at most {}",
+                    MAX_OUTPUT_DECIMAL_PLACES
+                )));
+            }
+        }
+
+        let mut tools: HashSet<String> = crate::tools::get_all_tools()
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|tool| tool["name"].as_str().map(str::to_string))
+            .collect();
+        for composite in &self.composite_tools {
+            if !tools.insert(composite.name().to_string()) {
+                return Err(ConfigError::new(format!(
+                    "tools.composite.{}: conflicts with an existing tool",
+                    composite.name()
+                )));
+            }
+        }
+        let configured_tools = [
+            (
+                "limits.tool_timeouts_ms",
+                self.tool_timeouts.keys().collect::<Vec<_>>(),
+            ),
+            ("rate_limit.tools", self.tool_rate_limits.keys().collect()),
+            ("rate_limit.costs", self.tool_costs.keys().collect()),
+            ("tools.allow", self.enabled_tools.iter().flatten().collect()),
+            ("tools.deny", self.disabled_tools.iter().collect()),
+            ("cache.exclude", self.cache_exclude.iter().collect()),
+        ];
+        for (key, names) in configured_tools {
+            if let Some(name) = names.into_iter().find(|name| !tools.contains(*name)) {
+                return Err(ConfigError::new(format!(
+                    "{}: unknown tool '{}'",
+                    key, name
+                )));
+            }
+        }
+
+        if let Some((tool, _)) = self.tool_rate_limits.iter().find(|(_, &limit)| limit == 0) {
+            return Err(ConfigError::new(format!(
+                "rate_limit.tools.{} must be at least 1",
+                tool
+            )));
+        }
+        if let Some((tool, _)) = self
+            .tool_costs
+            .iter()
+            .find(|(_, cost)| !cost.is_finite() || **cost <= 0.0)
+        {
+            return Err(ConfigError::new(format!(
+                "rate_limit.costs.{} must be a positive number",
+                tool
+            )));
+        }
+        let configured_categories = [
+            (
+                "categories",
+                self.disabled_categories.iter().collect::<Vec<_>>(),
+            ),
+            (
+                "tools.categories",
+                self.enabled_categories.iter().flatten().collect(),
+            ),
+        ];
+        for (key, names) in configured_categories {
+            if let Some(name) = names
+                .into_iter()
+                .find(|name| crate::tools::registry::category(name).is_none())
+            {
+                return Err(ConfigError::new(format!(
+                    "{}: unknown category '{}'",
+                    key, name
+                )));
+            }
+        }
+        if !tools.iter().any(|tool| self.tool_enabled(tool)) {
+            return Err(ConfigError::new(
+                "tools: the category and tool selection leaves no tools enabled",
+            ));
+        }
+        Ok(())
     }
 
     /// Get the server name
@@ -72,4 +384,453 @@ impl Config {
     pub fn server_version(&self) -> &str {
         &self.server_version
     }
+
+    /// Time budget for a call to `tool`; zero means no deadline
+    pub fn tool_timeout(&self, tool: &str) -> Duration {
+        self.tool_timeouts
+            .get(tool)
+            .copied()
+            .unwrap_or(self.tool_timeout)
+    }
+
+    /// Rate limit tokens one call to `tool` costs
+    pub fn tool_cost(&self, tool: &str) -> f64 {
+        self.tool_costs.get(tool).copied().unwrap_or(1.0)
+    }
+
+    /// The composite tool called `name`, if one is configured
+    pub fn composite_tool(&self, name: &str) -> Option<&CompositeTool> {
+        self.composite_tools.iter().find(|tool| tool.name() == name)
+    }
+
+    /// Whether `category` is selected as a whole and not switched off
+    pub fn category_enabled(&self, category: &str) -> bool {
+        !self.disabled_categories.contains(category)
+            && self
+                .enabled_categories
+                .as_ref()
+                .is_none_or(|enabled| enabled.contains(category))
+    }
+
+    /// Whether `tool` is offered in `tools/list` and may be executed.
+    ///
+    /// A tool is offered when it is selected (all tools are, unless
+    /// `enabled_categories` or `enabled_tools` narrow the selection to the
+    /// listed categories plus the listed tools) and neither it nor its
+    /// category is disabled. Composite and runtime tools have no category, so
+    /// under an allowlist they are only selected by name.
+    pub fn tool_enabled(&self, tool: &str) -> bool {
+        let category = crate::tools::registry::tool_category(tool).map(|c| c.name);
+        if self.disabled_tools.contains(tool)
+            || category.is_some_and(|c| self.disabled_categories.contains(c))
+        {
+            return false;
+        }
+        if self.enabled_categories.is_none() && self.enabled_tools.is_none() {
+            return true;
+        }
+        let by_category = category.is_some_and(|c| {
+            self.enabled_categories
+                .as_ref()
+                .is_some_and(|enabled| enabled.contains(c))
+        });
+        let by_name = self
+            .enabled_tools
+            .as_ref()
+            .is_some_and(|enabled| enabled.contains(tool));
+        by_category || by_name
+    }
+
+    fn apply_toml(&mut self, text: &str) -> Result<(), ConfigError> {
+        let file: FileConfig = toml::from_str(text).map_err(|e| ConfigError::new(e.to_string()))?;
+
+        let server = file.server;
+        if let Some(name) = server.name {
+            self.server_name = name;
+        }
+        if let Some(version) = server.version {
+            self.server_version = version;
+        }
+        if let Some(transport) = server.transport {
+            self.transport = transport
+                .parse()
+                .map_err(|e| ConfigError::new(format!("server.transport: {}", e)))?;
+        }
+        if let Some(bind) = server.http_bind {
+            self.http_bind = bind;
+        }
+        if let Some(bind) = server.tcp_bind {
+            self.tcp_bind = bind;
+        }
+        if let Some(path) = server.socket_path {
+            self.socket_path = path;
+        }
+
+        let limits = file.limits;
+        if let Some(size) = limits.max_array_size {
+            self.max_array_size = size;
+        }
+        if let Some(places) = limits.max_decimal_places {
+            self.max_decimal_places = places;
+        }
+        if let Some(size) = limits.max_batch_size {
+            self.max_batch_size = size;
+        }
+        if let Some(ms) = limits.tool_timeout_ms {
+            self.tool_timeout = Duration::from_millis(ms);
+        }
+        self.tool_timeouts.extend(
+            limits
+                .tool_timeouts_ms
+                .into_iter()
+                .map(|(tool, ms)| (tool, Duration::from_millis(ms))),
+        );
+
+        let rate_limit = file.rate_limit;
+        if let Some(enabled) = rate_limit.enabled {
+            self.enable_rate_limit = enabled;
+        }
+        if let Some(rps) = rate_limit.requests_per_second {
+            self.max_requests_per_second = rps;
+        }
+        self.tool_rate_limits.extend(rate_limit.tools);
+        self.tool_costs.extend(rate_limit.costs);
+
+        for (category, enabled) in file.categories {
+            if enabled {
+                self.disabled_categories.remove(&category);
+            } else {
+                self.disabled_categories.insert(category);
+            }
+        }
+
+        let tools = file.tools;
+        if let Some(categories) = tools.categories {
+            self.enabled_categories = Some(categories);
+        }
+        if let Some(allow) = tools.allow {
+            self.enabled_tools = Some(allow);
+        }
+        self.disabled_tools.extend(tools.deny);
+        if !tools.composite.is_empty() {
+            let known = crate::tools::get_all_tools();
+            let known = known.as_array().map(Vec::as_slice).unwrap_or_default();
+            for (name, spec) in tools.composite {
+                let tool = CompositeTool::from_spec(&name, spec, known)
+                    .map_err(|e| ConfigError::new(format!("tools.composite.{}: {}", name, e)))?;
+                self.composite_tools
+                    .retain(|existing| existing.name() != name);
+                self.composite_tools.push(tool);
+            }
+        }
+
+        if let Some(enabled) = file.cache.enabled {
+            self.cache_enabled = enabled;
+        }
+        if let Some(entries) = file.cache.max_entries {
+            self.cache_max_entries = entries;
+        }
+        if let Some(bytes) = file.cache.max_bytes {
+            self.cache_max_bytes = bytes;
+        }
+        self.cache_exclude.extend(file.cache.exclude);
+        if let Some(lenient) = file.input.lenient_numbers {
+            self.lenient_numbers = lenient;
+        }
+        let audit = file.audit;
+        if let Some(path) = audit.path {
+            self.audit_log = Some(path);
+        }
+        if let Some(bytes) = audit.max_bytes {
+            self.audit_max_bytes = bytes;
+        }
+        if let Some(files) = audit.max_files {
+            self.audit_max_files = files;
+        }
+        if let Some(arguments) = audit.arguments {
+            self.audit_arguments = arguments
+                .parse()
+                .map_err(|e| ConfigError::new(format!("audit.arguments: {}", e)))?;
+        }
+        if let Some(len) = audit.max_array_len {
+            self.audit_max_array_len = len;
+        }
+        if let Some(pretty) = file.output.pretty {
+            self.pretty_output = pretty;
+        }
+        if let Some(places) = file.output.decimal_places {
+            self.output_decimal_places = Some(places);
+        }
+        Ok(())
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Ok(name) = env::var("MCP_SERVER_NAME") {
+            self.server_name = name;
+        }
+        if let Ok(version) = env::var("MCP_SERVER_VERSION") {
+            self.server_version = version;
+        }
+        if let Some(size) = env_value("MCP_MAX_ARRAY_SIZE")? {
+            self.max_array_size = size;
+        }
+        if let Some(places) = env_value("MCP_MAX_DECIMAL_PLACES")? {
+            self.max_decimal_places = places;
+        }
+        if let Some(size) = env_value("MCP_MAX_BATCH_SIZE")? {
+            self.max_batch_size = size;
+        }
+        if let Some(enabled) = env_value("MCP_ENABLE_RATE_LIMIT")? {
+            self.enable_rate_limit = enabled;
+        }
+        if let Some(rps) = env_value("MCP_MAX_REQUESTS_PER_SECOND")? {
+            self.max_requests_per_second = rps;
+        }
+        if let Some(transport) = env_value("MCP_TRANSPORT")? {
+            self.transport = transport;
+        }
+        if let Some(bind) = env_value("MCP_HTTP_BIND")? {
+            self.http_bind = bind;
+        }
+        if let Some(bind) = env_value("MCP_TCP_BIND")? {
+            self.tcp_bind = bind;
+        }
+        if let Ok(path) = env::var("MCP_SOCKET_PATH") {
+            self.socket_path = PathBuf::from(path);
+        }
+        if let Some(ms) = env_value("MCP_TOOL_TIMEOUT_MS")? {
+            self.tool_timeout = Duration::from_millis(ms);
+        }
+        if let Some(timeouts) = env_tool_map::<u64>("MCP_TOOL_TIMEOUTS")? {
+            self.tool_timeouts.extend(
+                timeouts
+                    .into_iter()
+                    .map(|(tool, ms)| (tool, Duration::from_millis(ms))),
+            );
+        }
+        if let Some(limits) = env_tool_map("MCP_TOOL_RATE_LIMITS")? {
+            self.tool_rate_limits.extend(limits);
+        }
+        if let Some(costs) = env_tool_map("MCP_TOOL_COSTS")? {
+            self.tool_costs.extend(costs);
+        }
+        if let Some(categories) = env_list("MCP_ENABLED_CATEGORIES") {
+            self.enabled_categories = Some(categories);
+        }
+        if let Some(tools) = env_list("MCP_ENABLED_TOOLS") {
+            self.enabled_tools = Some(tools);
+        }
+        if let Some(categories) = env_list("MCP_DISABLED_CATEGORIES") {
+            self.disabled_categories = categories;
+        }
+        if let Some(tools) = env_list("MCP_DISABLED_TOOLS") {
+            self.disabled_tools = tools;
+        }
+        if let Some(enabled) = env_value("MCP_ENABLE_CACHE")? {
+            self.cache_enabled = enabled;
+        }
+        if let Some(entries) = env_value("MCP_CACHE_MAX_ENTRIES")? {
+            self.cache_max_entries = entries;
+        }
+        if let Some(bytes) = env_value("MCP_CACHE_MAX_BYTES")? {
+            self.cache_max_bytes = bytes;
+        }
+        if let Some(tools) = env_list("MCP_CACHE_EXCLUDE") {
+            self.cache_exclude = tools;
+        }
+        if let Some(lenient) = env_value("MCP_LENIENT_NUMBERS")? {
+            self.lenient_numbers = lenient;
+        }
+        if let Ok(path) = env::var("MCP_AUDIT_LOG") {
+            self.audit_log = (!path.is_empty()).then(|| PathBuf::from(path));
+        }
+        if let Some(bytes) = env_value("MCP_AUDIT_MAX_BYTES")? {
+            self.audit_max_bytes = bytes;
+        }
+        if let Some(files) = env_value("MCP_AUDIT_MAX_FILES")? {
+            self.audit_max_files = files;
+        }
+        if let Some(arguments) = env_value("MCP_AUDIT_ARGUMENTS")? {
+            self.audit_arguments = arguments;
+        }
+        if let Some(len) = env_value("MCP_AUDIT_MAX_ARRAY_LEN")? {
+            self.audit_max_array_len = len;
+        }
+        if let Some(pretty) = env_value("MCP_PRETTY_OUTPUT")? {
+            self.pretty_output = pretty;
+        }
+        if let Some(places) = env_value("MCP_OUTPUT_DECIMAL_PLACES")? {
+            self.output_decimal_places = Some(places);
+        }
+        Ok(())
+    }
+}
+
+/// A configuration value that could not be used; reported at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError(String);
+
+impl ConfigError {
+    /// Create an error with the given message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Layout of the TOML configuration file; every key is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    server: ServerSection,
+    limits: LimitsSection,
+    rate_limit: RateLimitSection,
+    /// `category = false` disables a category
+    categories: HashMap<String, bool>,
+    tools: ToolsSection,
+    cache: CacheSection,
+    input: InputSection,
+    audit: AuditSection,
+    output: OutputSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ToolsSection {
+    /// Only offer these categories (plus `allow`)
+    categories: Option<BTreeSet<String>>,
+    /// Only offer these tools (plus `categories`)
+    allow: Option<BTreeSet<String>>,
+    /// Never offer these tools
+    deny: BTreeSet<String>,
+    /// Tools defined in terms of other tools, by name
+    composite: BTreeMap<String, CompositeSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerSection {
+    name: Option<String>,
+    version: Option<String>,
+    transport: Option<String>,
+    http_bind: Option<SocketAddr>,
+    tcp_bind: Option<SocketAddr>,
+    socket_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LimitsSection {
+    max_array_size: Option<usize>,
+    max_decimal_places: Option<i32>,
+    max_batch_size: Option<usize>,
+    tool_timeout_ms: Option<u64>,
+    tool_timeouts_ms: HashMap<String, u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RateLimitSection {
+    enabled: Option<bool>,
+    requests_per_second: Option<usize>,
+    tools: HashMap<String, usize>,
+    costs: HashMap<String, f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CacheSection {
+    enabled: Option<bool>,
+    max_entries: Option<usize>,
+    max_bytes: Option<usize>,
+    /// Tools whose results are never cached
+    exclude: BTreeSet<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct InputSection {
+    lenient_numbers: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AuditSection {
+    path: Option<PathBuf>,
+    max_bytes: Option<u64>,
+    max_files: Option<usize>,
+    /// `"redacted"` or `"hash"`
+    arguments: Option<String>,
+    max_array_len: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct OutputSection {
+    pretty: Option<bool>,
+    decimal_places: Option<u32>,
+}
+
+/// Parse an environment variable, if set
+fn env_value<T>(name: &str) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    match env::var(name) {
+        Ok(value) => {
+            value.trim().parse().map(Some).map_err(|e| {
+                ConfigError::new(format!("{}: invalid value '{}': {}", name, value, e))
+            })
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Split a comma-separated environment variable into names, if set
+fn env_list(name: &str) -> Option<BTreeSet<String>> {
+    let value = env::var(name).ok()?;
+    Some(
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Parse an environment variable of the form `"name=value,name=value"`, if set
+fn env_tool_map<T>(name: &str) -> Result<Option<HashMap<String, T>>, ConfigError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let Ok(value) = env::var(name) else {
+        return Ok(None);
+    };
+    value
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (tool, value) = entry
+                .split_once('=')
+                .filter(|(tool, _)| !tool.trim().is_empty())
+                .ok_or_else(|| {
+                    ConfigError::new(format!("{}: expected 'tool=value', got '{}'", name, entry))
+                })?;
+            let value = value.trim().parse().map_err(|e| {
+                ConfigError::new(format!("{}: invalid value for '{}': {}", name, tool, e))
+            })?;
+            Ok((tool.trim().to_string(), value))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
 }
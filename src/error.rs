@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
+use std::time::Duration;
 
 /// Custom error type for MCP server operations.
 ///
@@ -84,6 +85,54 @@ impl McpError {
     pub fn resource_limit(message: impl Into<String>) -> Self {
         Self::new(-32002, message)
     }
+
+    /// Rate limit error (-32002) with a `retry_after_ms` hint in `data`
+    pub fn rate_limited(message: impl Into<String>, retry_after: Duration) -> Self {
+        let retry_after_ms = retry_after.as_millis() + 1;
+        Self::with_data(
+            -32002,
+            format!("{}; retry after {} ms", message.into(), retry_after_ms),
+            serde_json::json!({ "retry_after_ms": retry_after_ms as u64 }),
+        )
+    }
+
+    /// Whether this error describes a broken request rather than a failed tool call.
+    ///
+    /// Protocol errors (parse, invalid request, method not found, internal) are
+    /// returned as JSON-RPC errors; everything else is reported to the client as
+    /// an `isError` tool result so the model can see and correct it.
+    pub fn is_protocol_error(&self) -> bool {
+        matches!(self.code, -32700 | -32600 | -32601 | -32603)
+    }
+
+    /// The `isError` tool result reporting this error.
+    ///
+    /// The text content is `Error: <message>`; `structuredContent.error`
+    /// carries the code, message and any `data`, such as the path of an
+    /// invalid argument.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_math_mcp::error::McpError;
+    /// use serde_json::json;
+    ///
+    /// let error = McpError::with_data(-32602, "Bad argument", json!({ "argument": "numbers[1]" }));
+    /// let result = error.to_tool_result();
+    /// assert_eq!(result.is_error, Some(true));
+    /// assert_eq!(result.structured_content.unwrap()["error"]["data"]["argument"], "numbers[1]");
+    /// ```
+    pub fn to_tool_result(&self) -> rmcp::model::CallToolResult {
+        rmcp::model::CallToolResult {
+            content: vec![rmcp::model::Content::text(format!(
+                "Error: {}",
+                self.message
+            ))],
+            structured_content: Some(serde_json::json!({ "error": self })),
+            is_error: Some(true),
+            meta: None,
+        }
+    }
 }
 
 impl fmt::Display for McpError {
@@ -114,6 +163,13 @@ impl From<serde_json::Error> for McpError {
     }
 }
 
+/// Convert from McpError to the rmcp JSON-RPC error type
+impl From<McpError> for rmcp::model::ErrorData {
+    fn from(err: McpError) -> Self {
+        Self::new(rmcp::model::ErrorCode(err.code), err.message, err.data)
+    }
+}
+
 /// Convert from std::io::Error to McpError
 impl From<std::io::Error> for McpError {
     fn from(err: std::io::Error) -> Self {
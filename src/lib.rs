@@ -1,7 +1,25 @@
 // Library crate for Rust Math MCP
-// Main functionality is in the binary crate in src/main.rs
+// The binary in src/main.rs serves `MathService` over rmcp; everything it
+// needs (tool registry, configuration, error types) lives here. Rust code
+// can also call the math directly through the typed functions in `math`,
+// which the JSON tools in `tools` wrap.
 
+pub mod audit;
+pub mod cli;
+pub mod config;
+pub mod error;
+pub mod math;
+pub mod metrics;
+pub mod prompts;
+pub mod protocol;
+pub mod resources;
 pub mod service;
+pub mod session;
+pub mod tools;
+pub mod transport;
+pub mod utils;
 
-// Re-export service for use in tests
+pub use config::Config;
+pub use error::{McpError, McpResult};
 pub use service::MathService;
+pub use tools::{DefaultToolRegistry, ToolRegistry};
@@ -1,12 +1,199 @@
-use anyhow::Result;
-use rmcp::{ServiceExt, transport::stdio};
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use rmcp::ServiceExt;
+use rust_math_mcp::audit::AuditLog;
+use rust_math_mcp::cli::{self, History, OutputFormat};
+use rust_math_mcp::config::{Config, TransportKind};
+use rust_math_mcp::{transport, MathService};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 
-mod service;
-use service::MathService;
+/// Command line options; anything not given falls back to the config file, the
+/// environment and then the defaults in `Config`
+#[derive(Debug, Parser)]
+#[command(name = "rust-math-mcp", version, about)]
+struct Cli {
+    /// Run a tool from the command line instead of serving MCP
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// TOML configuration file
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Transport to serve on: `stdio` (default), `http`, `tcp` or `unix`
+    #[arg(long)]
+    transport: Option<TransportKind>,
+
+    /// Address for the HTTP or TCP transport to listen on
+    /// (defaults: 127.0.0.1:8080 for HTTP, 127.0.0.1:9090 for TCP)
+    #[arg(long)]
+    bind: Option<SocketAddr>,
+
+    /// Socket path for the Unix transport (default: <tmp>/rust-math-mcp.sock)
+    #[arg(long)]
+    socket: Option<PathBuf>,
+
+    /// Largest array a tool accepts
+    #[arg(long, value_name = "N")]
+    max_array_size: Option<usize>,
+
+    /// Most operations one `batch_operations` call may contain
+    #[arg(long, value_name = "N")]
+    max_batch_size: Option<usize>,
+
+    /// Time budget for a tool call in milliseconds (0 disables it)
+    #[arg(long, value_name = "MS")]
+    tool_timeout_ms: Option<u64>,
+
+    /// Tool calls allowed per second in each session
+    #[arg(long, value_name = "N", conflicts_with = "no_rate_limit")]
+    rate_limit: Option<usize>,
+
+    /// Turn rate limiting off
+    #[arg(long)]
+    no_rate_limit: bool,
+
+    /// Only offer this category, plus any other `--category` and `--tool` (repeatable)
+    #[arg(long, value_name = "CATEGORY")]
+    category: Vec<String>,
+
+    /// Only offer this tool, plus any other `--category` and `--tool` (repeatable)
+    #[arg(long, value_name = "TOOL")]
+    tool: Vec<String>,
+
+    /// Hide and refuse the tools in a category (repeatable)
+    #[arg(long, value_name = "CATEGORY")]
+    disable_category: Vec<String>,
+
+    /// Hide and refuse a tool (repeatable)
+    #[arg(long, value_name = "TOOL")]
+    disable_tool: Vec<String>,
+
+    /// Reuse the results of earlier identical tool calls
+    #[arg(long)]
+    cache: bool,
+
+    /// Accept numbers written as strings, fractions, percentages and constants
+    #[arg(long)]
+    lenient_numbers: bool,
+
+    /// Append a JSON line for every tool call to this file
+    #[arg(long, value_name = "PATH")]
+    audit_log: Option<PathBuf>,
+
+    /// Pretty-print the JSON text of tool results
+    #[arg(long)]
+    pretty: bool,
+
+    /// Round numbers in tool results to this many decimal places
+    #[arg(long, value_name = "N")]
+    decimal_places: Option<u32>,
+}
+
+/// Ways to use the tools without an MCP client; server flags such as
+/// `--config` go before the subcommand
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Call a tool and print its result; exits with status 1 if the call fails
+    Call {
+        /// Tool name, e.g. `mean`
+        tool: String,
+
+        /// Arguments as a JSON object, e.g. '{"numbers":[1,2,3]}'
+        #[arg(long, value_name = "JSON", default_value = "{}")]
+        args: String,
+
+        /// Print results as `json` or `text`
+        #[arg(long, default_value_t)]
+        format: OutputFormat,
+    },
+    /// List the available tools
+    List {
+        /// Only list the tools in this category
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Print the list as `json` or `text`
+        #[arg(long, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Call tools interactively
+    Repl {
+        /// History file (default: ~/.rust-math-mcp_history)
+        #[arg(long, value_name = "PATH")]
+        history: Option<PathBuf>,
+
+        /// Print results as `json` or `text`
+        #[arg(long, default_value_t)]
+        format: OutputFormat,
+    },
+}
+
+impl Cli {
+    /// Apply the flags that were given on top of `config`
+    fn apply(self, config: &mut Config) {
+        if let Some(transport) = self.transport {
+            config.transport = transport;
+        }
+        if let Some(bind) = self.bind {
+            match config.transport {
+                TransportKind::Tcp => config.tcp_bind = bind,
+                _ => config.http_bind = bind,
+            }
+        }
+        if let Some(socket) = self.socket {
+            config.socket_path = socket;
+        }
+        if let Some(size) = self.max_array_size {
+            config.max_array_size = size;
+        }
+        if let Some(size) = self.max_batch_size {
+            config.max_batch_size = size;
+        }
+        if let Some(ms) = self.tool_timeout_ms {
+            config.tool_timeout = Duration::from_millis(ms);
+        }
+        if let Some(rps) = self.rate_limit {
+            config.enable_rate_limit = true;
+            config.max_requests_per_second = rps;
+        }
+        if self.no_rate_limit {
+            config.enable_rate_limit = false;
+        }
+        if !self.category.is_empty() {
+            config.enabled_categories = Some(self.category.into_iter().collect());
+        }
+        if !self.tool.is_empty() {
+            config.enabled_tools = Some(self.tool.into_iter().collect());
+        }
+        config.disabled_categories.extend(self.disable_category);
+        config.disabled_tools.extend(self.disable_tool);
+        if self.cache {
+            config.cache_enabled = true;
+        }
+        if self.lenient_numbers {
+            config.lenient_numbers = true;
+        }
+        if let Some(path) = self.audit_log {
+            config.audit_log = Some(path);
+        }
+        if self.pretty {
+            config.pretty_output = true;
+        }
+        if let Some(places) = self.decimal_places {
+            config.output_decimal_places = Some(places);
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let mut cli = Cli::parse();
+
     // Configure tracing to write to stderr to avoid polluting stdout (MCP protocol)
     // MCP uses stdout for protocol communication, so ALL output must go to stderr
     tracing_subscriber::fmt()
@@ -16,21 +203,114 @@ async fn main() -> Result<()> {
         .with_thread_ids(false) // Disable thread IDs to reduce noise
         .with_thread_names(false) // Disable thread names to reduce noise
         .with_env_filter(
-            std::env::var("RUST_LOG")
-                .unwrap_or_else(|_| "rust_math_mcp=warn".to_string()), // Default to warn
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "rust_math_mcp=warn".to_string()), // Default to warn
         )
         .init();
 
-    info!("Starting Rust Math MCP Server");
+    let mut config = Config::load(cli.config.as_deref())?;
+    let command = cli.command.take();
+    cli.apply(&mut config);
+    config.validate()?;
+    if let Some(command) = command {
+        return run_command(command, Arc::new(config));
+    }
+    // Open the audit log now so an unwritable path stops the server at startup
+    AuditLog::shared(&config).context("failed to open the audit log")?;
+    let config = Arc::new(config);
 
-    // Create and serve the MathService via stdio transport
-    let service = MathService::new()
-        .serve(stdio())
-        .await
-        .inspect_err(|e| tracing::error!("Server error: {:?}", e))?;
+    info!("Starting Rust Math MCP Server ({})", config.transport);
 
-    // Wait for the server to complete
-    service.waiting().await?;
+    match config.transport {
+        TransportKind::Stdio => {
+            // Create and serve the MathService via stdio transport
+            let service = MathService::with_config(config)
+                .serve(transport::stdio())
+                .await
+                .inspect_err(|e| tracing::error!("Server error: {:?}", e))?;
+
+            // Wait for the server to complete
+            service.waiting().await?;
+        }
+        TransportKind::Http => {
+            let listener = tokio::net::TcpListener::bind(config.http_bind).await?;
+            transport::http::serve(listener, config, shutdown_signal()).await?;
+        }
+        TransportKind::Tcp => {
+            let listener = tokio::net::TcpListener::bind(config.tcp_bind).await?;
+            transport::socket::serve_tcp(listener, config, shutdown_signal()).await?;
+        }
+        #[cfg(unix)]
+        TransportKind::Unix => {
+            let path = config.socket_path.clone();
+            remove_stale_socket(&path)?;
+            let listener = tokio::net::UnixListener::bind(&path)?;
+            transport::socket::serve_unix(listener, config, shutdown_signal()).await?;
+            let _ = std::fs::remove_file(&path);
+        }
+        #[cfg(not(unix))]
+        TransportKind::Unix => anyhow::bail!("Unix sockets are not supported on this platform"),
+    }
 
     Ok(())
 }
+
+/// Run a subcommand, printing results to stdout and errors to stderr
+fn run_command(command: Command, config: Arc<Config>) -> Result<()> {
+    let pretty = config.pretty_output;
+    let (outcome, format) = match command {
+        Command::Call { tool, args, format } => (
+            cli::call(&config, &tool, &args)
+                .map(|result| cli::format_result(&result, format, pretty)),
+            format,
+        ),
+        Command::List { category, format } => (
+            cli::list(&config, category.as_deref())
+                .map(|tools| cli::format_tools(&tools, format, pretty)),
+            format,
+        ),
+        Command::Repl { history, format } => {
+            let mut history = match history.or_else(History::default_path) {
+                Some(path) => History::load(&path)
+                    .with_context(|| format!("failed to read history from {}", path.display()))?,
+                None => History::new(),
+            };
+            let stdin = std::io::stdin();
+            cli::repl(
+                &config,
+                stdin.lock(),
+                std::io::stdout(),
+                &mut history,
+                format,
+            )?;
+            return Ok(());
+        }
+    };
+
+    match outcome {
+        Ok(printed) => {
+            println!("{}", printed);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("{}", cli::format_error(&e, format));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Resolves on Ctrl-C
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Remove a socket file left behind by a previous run, refusing to delete anything else
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => Ok(std::fs::remove_file(path)?),
+        Ok(_) => anyhow::bail!("{} exists and is not a socket", path.display()),
+        Err(_) => Ok(()),
+    }
+}
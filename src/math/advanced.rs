@@ -0,0 +1,43 @@
+use crate::error::{McpError, McpResult};
+
+/// Discrete growth of `initial` at `rate` (as a decimal) per period for `time`
+/// periods: A = P(1 + r)^t
+pub fn exponential_growth(initial: f64, rate: f64, time: f64) -> f64 {
+    initial * (1.0 + rate).powf(time)
+}
+
+/// Continuously compounded growth of `initial` at `rate` for `time`: A = Pe^(rt)
+pub fn continuous_growth(initial: f64, rate: f64, time: f64) -> f64 {
+    initial * (rate * time).exp()
+}
+
+/// Logarithm of `value` in `base`; fails for non-positive values and for
+/// bases that are non-positive or 1
+pub fn logarithm(value: f64, base: f64) -> McpResult<f64> {
+    check_positive(value)?;
+    if base <= 0.0 || base == 1.0 {
+        return Err(McpError::validation_error("Invalid base for logarithm"));
+    }
+    Ok(value.log(base))
+}
+
+/// Natural logarithm; fails for non-positive values
+pub fn ln(value: f64) -> McpResult<f64> {
+    check_positive(value)?;
+    Ok(value.ln())
+}
+
+/// Base-10 logarithm; fails for non-positive values
+pub fn log10(value: f64) -> McpResult<f64> {
+    check_positive(value)?;
+    Ok(value.log10())
+}
+
+fn check_positive(value: f64) -> McpResult<()> {
+    if value <= 0.0 {
+        return Err(McpError::validation_error(
+            "Logarithm is undefined for non-positive values",
+        ));
+    }
+    Ok(())
+}
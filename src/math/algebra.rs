@@ -0,0 +1,38 @@
+use crate::error::{McpError, McpResult};
+
+/// Largest value of `n` accepted by [`factorial`]: 21! does not fit in a `u64`
+pub const MAX_FACTORIAL: u64 = 20;
+
+/// Greatest common divisor of `|a|` and `|b|`, by the Euclidean algorithm
+pub fn gcd(a: i64, b: i64) -> u64 {
+    let mut a = a.unsigned_abs();
+    let mut b = b.unsigned_abs();
+    while b != 0 {
+        let temp = b;
+        b = a % b;
+        a = temp;
+    }
+    a
+}
+
+/// Least common multiple of `|a|` and `|b|` (0 if either is 0); fails on overflow
+pub fn lcm(a: i64, b: i64) -> McpResult<u64> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    // lcm(a, b) = (a / gcd) * b, dividing first to delay overflow
+    (a.unsigned_abs() / gcd(a, b))
+        .checked_mul(b.unsigned_abs())
+        .ok_or_else(|| McpError::validation_error("LCM calculation would overflow"))
+}
+
+/// `n!`; fails for `n` above [`MAX_FACTORIAL`]
+pub fn factorial(n: u64) -> McpResult<u64> {
+    if n > MAX_FACTORIAL {
+        return Err(McpError::validation_error(format!(
+            "Factorial overflow: n must be <= {} to prevent overflow",
+            MAX_FACTORIAL
+        )));
+    }
+    Ok((1..=n).product())
+}
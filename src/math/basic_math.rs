@@ -0,0 +1,68 @@
+use crate::error::{McpError, McpResult};
+
+/// Sum of `numbers` (0 for an empty slice)
+pub fn add(numbers: &[f64]) -> f64 {
+    numbers.iter().sum()
+}
+
+/// `a - b`
+pub fn subtract(a: f64, b: f64) -> f64 {
+    a - b
+}
+
+/// Product of `numbers` (1 for an empty slice)
+pub fn multiply(numbers: &[f64]) -> f64 {
+    numbers.iter().product()
+}
+
+/// `a / b`; fails when `b` is zero
+pub fn divide(a: f64, b: f64) -> McpResult<f64> {
+    if b == 0.0 {
+        return Err(McpError::validation_error("Division by zero"));
+    }
+    Ok(a / b)
+}
+
+/// `base` raised to `exponent`
+pub fn power(base: f64, exponent: f64) -> f64 {
+    base.powf(exponent)
+}
+
+/// Square root; fails for negative numbers
+pub fn sqrt(number: f64) -> McpResult<f64> {
+    if number < 0.0 {
+        return Err(McpError::validation_error(
+            "Cannot take square root of negative number",
+        ));
+    }
+    Ok(number.sqrt())
+}
+
+/// Absolute value
+pub fn abs(number: f64) -> f64 {
+    number.abs()
+}
+
+/// Round to `decimals` decimal places (negative values round to tens, hundreds, …)
+pub fn round(number: f64, decimals: i32) -> f64 {
+    let multiplier = 10.0_f64.powi(decimals);
+    (number * multiplier).round() / multiplier
+}
+
+/// Largest integer not greater than `number`
+pub fn floor(number: f64) -> f64 {
+    number.floor()
+}
+
+/// Smallest integer not less than `number`
+pub fn ceil(number: f64) -> f64 {
+    number.ceil()
+}
+
+/// Remainder of `a / b`, with the sign of `a`; fails when `b` is zero
+pub fn modulo(a: f64, b: f64) -> McpResult<f64> {
+    if b == 0.0 {
+        return Err(McpError::validation_error("Modulo by zero"));
+    }
+    Ok(a % b)
+}
@@ -0,0 +1,41 @@
+use crate::error::{McpError, McpResult};
+
+/// Ordered selections of `r` items out of `n`: P(n, r) = n! / (n - r)!
+///
+/// Fails when `r > n` or the result does not fit in a `u64`.
+pub fn permutation(n: u64, r: u64) -> McpResult<u64> {
+    if r > n {
+        return Err(McpError::validation_error("Permutation: r must be <= n"));
+    }
+
+    let mut result = 1u64;
+    for factor in (0..r).map(|i| n - i) {
+        result = result.checked_mul(factor).ok_or_else(|| {
+            McpError::validation_error(format!("Permutation overflow at factor {}", factor))
+        })?;
+    }
+    Ok(result)
+}
+
+/// Unordered selections of `r` items out of `n`: C(n, r) = n! / (r! × (n - r)!)
+///
+/// Fails when `r > n` or an intermediate product does not fit in a `u64`.
+pub fn combination(n: u64, r: u64) -> McpResult<u64> {
+    if r > n {
+        return Err(McpError::validation_error("Combination: r must be <= n"));
+    }
+
+    // C(n, r) = C(n, n - r); use the smaller to take fewer steps
+    let r = r.min(n - r);
+
+    // C(n, r) = (n × (n - 1) × … × (n - r + 1)) / (r × (r - 1) × … × 1); each
+    // partial product is itself a binomial coefficient, so the division is exact
+    let mut result = 1u64;
+    for i in 0..r {
+        result = result.checked_mul(n - i).ok_or_else(|| {
+            McpError::validation_error("Combination overflow during multiplication")
+        })?;
+        result /= i + 1;
+    }
+    Ok(result)
+}
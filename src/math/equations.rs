@@ -0,0 +1,83 @@
+use crate::error::{McpError, McpResult};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A point in the plane
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, JsonSchema)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Real roots of a quadratic equation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuadraticRoots {
+    /// Two distinct roots, the `+√` root first
+    Distinct(f64, f64),
+    /// One repeated root
+    Repeated(f64),
+    /// No real roots (the discriminant is negative)
+    Complex,
+}
+
+/// Discriminant of ax² + bx + c: b² - 4ac
+pub fn discriminant(a: f64, b: f64, c: f64) -> f64 {
+    b * b - 4.0 * a * c
+}
+
+/// Solve ax² + bx + c = 0 with the quadratic formula; fails when `a` is zero
+pub fn quadratic_formula(a: f64, b: f64, c: f64) -> McpResult<QuadraticRoots> {
+    if a == 0.0 {
+        return Err(McpError::validation_error(
+            "Coefficient 'a' cannot be zero for quadratic equation",
+        ));
+    }
+    let discriminant = discriminant(a, b, c);
+    if discriminant < 0.0 {
+        Ok(QuadraticRoots::Complex)
+    } else if discriminant == 0.0 {
+        Ok(QuadraticRoots::Repeated(-b / (2.0 * a)))
+    } else {
+        let sqrt_disc = discriminant.sqrt();
+        Ok(QuadraticRoots::Distinct(
+            (-b + sqrt_disc) / (2.0 * a),
+            (-b - sqrt_disc) / (2.0 * a),
+        ))
+    }
+}
+
+/// Distance between two points: √((x2 - x1)² + (y2 - y1)²)
+pub fn distance(p1: Point, p2: Point) -> f64 {
+    ((p2.x - p1.x).powi(2) + (p2.y - p1.y).powi(2)).sqrt()
+}
+
+/// Slope of the line through two points; fails for a vertical line
+pub fn slope(p1: Point, p2: Point) -> McpResult<f64> {
+    if (p2.x - p1.x).abs() < 1e-10 {
+        return Err(McpError::validation_error(
+            "Slope is undefined (vertical line)",
+        ));
+    }
+    Ok((p2.y - p1.y) / (p2.x - p1.x))
+}
+
+/// Point halfway between two points
+pub fn midpoint(p1: Point, p2: Point) -> Point {
+    Point::new((p1.x + p2.x) / 2.0, (p1.y + p2.y) / 2.0)
+}
+
+/// Hypotenuse of a right triangle with legs `a` and `b`: √(a² + b²)
+pub fn hypotenuse(a: f64, b: f64) -> f64 {
+    (a * a + b * b).sqrt()
+}
+
+/// Remaining leg of a right triangle: √(c² - b²), NaN when `leg` exceeds `hypotenuse`
+pub fn leg(hypotenuse: f64, leg: f64) -> f64 {
+    (hypotenuse * hypotenuse - leg * leg).sqrt()
+}
@@ -0,0 +1,22 @@
+/// Balance after compounding `principal` at annual `rate` (as a decimal)
+/// `compounds_per_year` times a year for `time` years: A = P(1 + r/n)^(nt)
+pub fn compound_interest(principal: f64, rate: f64, time: f64, compounds_per_year: f64) -> f64 {
+    let n = compounds_per_year;
+    principal * (1.0 + rate / n).powf(n * time)
+}
+
+/// Interest earned on `principal` at annual `rate` (as a decimal) over `time`
+/// years: I = P × r × t
+pub fn simple_interest(principal: f64, rate: f64, time: f64) -> f64 {
+    principal * rate * time
+}
+
+/// What percentage `part` is of `whole`: (part / whole) × 100
+pub fn percentage(part: f64, whole: f64) -> f64 {
+    (part / whole) * 100.0
+}
+
+/// The part of `whole` that is `percent` percent of it: (percent / 100) × whole
+pub fn percent_of(percent: f64, whole: f64) -> f64 {
+    (percent / 100.0) * whole
+}
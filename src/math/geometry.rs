@@ -0,0 +1,41 @@
+use std::f64::consts::PI;
+
+/// Area of a circle: πr²
+pub fn area_circle(radius: f64) -> f64 {
+    PI * radius * radius
+}
+
+/// Area of a rectangle: length × width
+pub fn area_rectangle(length: f64, width: f64) -> f64 {
+    length * width
+}
+
+/// Area of a triangle: ½ × base × height
+pub fn area_triangle(base: f64, height: f64) -> f64 {
+    0.5 * base * height
+}
+
+/// Area of a trapezoid: ½ × (base1 + base2) × height
+pub fn area_trapezoid(base1: f64, base2: f64, height: f64) -> f64 {
+    0.5 * (base1 + base2) * height
+}
+
+/// Volume of a sphere: ⁴⁄₃πr³
+pub fn volume_sphere(radius: f64) -> f64 {
+    (4.0 / 3.0) * PI * radius.powi(3)
+}
+
+/// Volume of a cylinder: πr²h
+pub fn volume_cylinder(radius: f64, height: f64) -> f64 {
+    PI * radius * radius * height
+}
+
+/// Volume of a cone: ⅓πr²h
+pub fn volume_cone(radius: f64, height: f64) -> f64 {
+    (1.0 / 3.0) * PI * radius * radius * height
+}
+
+/// Volume of a rectangular prism (box): length × width × height
+pub fn volume_rectangular_prism(length: f64, width: f64, height: f64) -> f64 {
+    length * width * height
+}
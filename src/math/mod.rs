@@ -0,0 +1,39 @@
+//! Typed math functions.
+//!
+//! The computations behind every built-in tool, callable directly from Rust
+//! without going through JSON. The tools in [`crate::tools`] parse their
+//! arguments, call these functions and serialize the result, so both paths
+//! compute the same values and fail with the same [`McpError`]s.
+//!
+//! Functions that cannot fail return their value directly; the rest return
+//! [`McpResult`] with a `validation_error` (code -32001), as the tools do.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rust_math_mcp::math::{algebra, finance, statistics};
+//! use rust_math_mcp::math::statistics::Sample;
+//!
+//! let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+//! assert_eq!(statistics::median(&data).unwrap(), 4.5);
+//! assert_eq!(statistics::variance(&data, Sample::Population).unwrap(), 4.0);
+//!
+//! assert_eq!(algebra::factorial(5).unwrap(), 120);
+//! assert!(algebra::factorial(21).is_err());
+//!
+//! let balance = finance::compound_interest(1000.0, 0.05, 10.0, 12.0);
+//! assert!((balance - 1647.01).abs() < 0.01);
+//! ```
+//!
+//! [`McpError`]: crate::error::McpError
+//! [`McpResult`]: crate::error::McpResult
+
+pub mod advanced;
+pub mod algebra;
+pub mod basic_math;
+pub mod combinatorics;
+pub mod equations;
+pub mod finance;
+pub mod geometry;
+pub mod statistics;
+pub mod trigonometry;
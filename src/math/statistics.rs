@@ -0,0 +1,235 @@
+use crate::error::{McpError, McpResult};
+use crate::tools::context::ToolContext;
+use std::collections::HashMap;
+
+/// Values processed between progress updates and cancellation checks.
+///
+/// Arrays no longer than this are handled in one step without progress updates.
+pub const PROGRESS_CHUNK: usize = 1_000;
+
+/// Whether `numbers` is a sample of a larger population or the whole of it
+///
+/// Sample statistics divide by n - 1 (Bessel's correction) instead of n.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sample {
+    /// Divide by n
+    #[default]
+    Population,
+    /// Divide by n - 1
+    Sample,
+}
+
+impl From<bool> for Sample {
+    /// `true` for a sample, as in the tools' `sample` argument
+    fn from(sample: bool) -> Self {
+        if sample {
+            Sample::Sample
+        } else {
+            Sample::Population
+        }
+    }
+}
+
+/// The most frequent value(s) of a data set
+#[derive(Debug, Clone, PartialEq)]
+pub struct Modes {
+    /// Every value occurring `frequency` times, in no particular order
+    pub values: Vec<f64>,
+    /// Number of occurrences of each mode
+    pub frequency: usize,
+}
+
+/// Visit `numbers` in chunks, checking `ctx` and reporting progress after each.
+///
+/// Tools that read the data several times pass which `pass` this is out of
+/// `passes`, so progress keeps increasing towards a single total.
+fn scan(
+    numbers: &[f64],
+    ctx: Option<&ToolContext>,
+    pass: usize,
+    passes: usize,
+    mut visit: impl FnMut(f64),
+) -> McpResult<()> {
+    let Some(ctx) = ctx else {
+        numbers.iter().for_each(|&x| visit(x));
+        return Ok(());
+    };
+    let report = ctx.reports_progress() && numbers.len() > PROGRESS_CHUNK;
+    let total = (numbers.len() * passes) as f64;
+    let mut done = pass * numbers.len();
+
+    for chunk in numbers.chunks(PROGRESS_CHUNK) {
+        ctx.check()?;
+        chunk.iter().for_each(|&x| visit(x));
+        done += chunk.len();
+        if report {
+            ctx.report_progress(done as f64, Some(total), None);
+        }
+    }
+    Ok(())
+}
+
+/// Arithmetic mean; fails for an empty slice
+pub fn mean(numbers: &[f64]) -> McpResult<f64> {
+    mean_pass(numbers, None, 1)
+}
+
+/// [`mean`], stopping when `ctx` is cancelled and reporting progress to it
+pub(crate) fn mean_with_context(numbers: &[f64], ctx: &ToolContext) -> McpResult<f64> {
+    mean_pass(numbers, Some(ctx), 1)
+}
+
+/// The mean, computed as the first of `passes` scans over the data
+fn mean_pass(numbers: &[f64], ctx: Option<&ToolContext>, passes: usize) -> McpResult<f64> {
+    if numbers.is_empty() {
+        return Err(McpError::validation_error(
+            "Cannot calculate mean of empty array",
+        ));
+    }
+    let mut total = 0.0;
+    scan(numbers, ctx, 0, passes, |x| total += x)?;
+    Ok(total / numbers.len() as f64)
+}
+
+/// Middle value, or the mean of the two middle values; fails for an empty slice
+///
+/// Values are ordered by [`f64::total_cmp`], which puts NaN above every number.
+pub fn median(numbers: &[f64]) -> McpResult<f64> {
+    if numbers.is_empty() {
+        return Err(McpError::validation_error(
+            "Cannot calculate median of empty array",
+        ));
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let len = sorted.len();
+    if len.is_multiple_of(2) {
+        Ok((sorted[len / 2 - 1] + sorted[len / 2]) / 2.0)
+    } else {
+        Ok(sorted[len / 2])
+    }
+}
+
+/// Most frequent value(s), or `None` when every value is unique; fails for an
+/// empty slice
+///
+/// Values are compared to 10 decimal places.
+pub fn mode(numbers: &[f64]) -> McpResult<Option<Modes>> {
+    if numbers.is_empty() {
+        return Err(McpError::validation_error(
+            "Cannot calculate mode of empty array",
+        ));
+    }
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    for num in numbers {
+        let key = format!("{:.10}", num);
+        *frequency.entry(key).or_insert(0) += 1;
+    }
+    let max_freq = frequency.values().max().copied().unwrap_or(0);
+    let modes: Vec<f64> = frequency
+        .iter()
+        .filter(|(_, &freq)| freq == max_freq)
+        .filter_map(|(key, _)| key.parse::<f64>().ok())
+        .collect();
+
+    if modes.len() == numbers.len() {
+        Ok(None)
+    } else {
+        Ok(Some(Modes {
+            values: modes,
+            frequency: max_freq,
+        }))
+    }
+}
+
+/// Variance of a population or sample; fails for an empty slice
+///
+/// A sample of one value has variance 0.
+pub fn variance(numbers: &[f64], sample: Sample) -> McpResult<f64> {
+    variance_in(numbers, sample, None)
+}
+
+/// [`variance`], stopping when `ctx` is cancelled and reporting progress to it
+pub(crate) fn variance_with_context(
+    numbers: &[f64],
+    sample: Sample,
+    ctx: &ToolContext,
+) -> McpResult<f64> {
+    variance_in(numbers, sample, Some(ctx))
+}
+
+fn variance_in(numbers: &[f64], sample: Sample, ctx: Option<&ToolContext>) -> McpResult<f64> {
+    if numbers.is_empty() {
+        return Err(McpError::validation_error(
+            "Cannot calculate variance of empty array",
+        ));
+    }
+    let mean_val = mean_pass(numbers, ctx, 2)?;
+    let n = numbers.len() as f64;
+    let divisor = if sample == Sample::Sample && n > 1.0 {
+        n - 1.0
+    } else {
+        n
+    };
+    let mut sum_squared_diff = 0.0;
+    scan(numbers, ctx, 1, 2, |x| {
+        sum_squared_diff += (x - mean_val).powi(2)
+    })?;
+    Ok(sum_squared_diff / divisor)
+}
+
+/// Standard deviation of a population or sample; fails for an empty slice
+pub fn std_dev(numbers: &[f64], sample: Sample) -> McpResult<f64> {
+    Ok(variance(numbers, sample)?.sqrt())
+}
+
+/// [`std_dev`], stopping when `ctx` is cancelled and reporting progress to it
+pub(crate) fn std_dev_with_context(
+    numbers: &[f64],
+    sample: Sample,
+    ctx: &ToolContext,
+) -> McpResult<f64> {
+    Ok(variance_with_context(numbers, sample, ctx)?.sqrt())
+}
+
+/// Smallest value by [`f64::total_cmp`]; fails for an empty slice
+pub fn min(numbers: &[f64]) -> McpResult<f64> {
+    numbers
+        .iter()
+        .min_by(|a, b| a.total_cmp(b))
+        .copied()
+        .ok_or_else(|| McpError::validation_error("Cannot find min of empty array"))
+}
+
+/// Largest value by [`f64::total_cmp`] (NaN if present); fails for an empty slice
+pub fn max(numbers: &[f64]) -> McpResult<f64> {
+    numbers
+        .iter()
+        .max_by(|a, b| a.total_cmp(b))
+        .copied()
+        .ok_or_else(|| McpError::validation_error("Cannot find max of empty array"))
+}
+
+/// Sum of all values (0 for an empty slice)
+pub fn sum(numbers: &[f64]) -> f64 {
+    numbers.iter().sum()
+}
+
+/// [`sum`], stopping when `ctx` is cancelled and reporting progress to it
+pub(crate) fn sum_with_context(numbers: &[f64], ctx: &ToolContext) -> McpResult<f64> {
+    let mut total = 0.0;
+    scan(numbers, Some(ctx), 0, 1, |x| total += x)?;
+    Ok(total)
+}
+
+/// Product of all values (1 for an empty slice)
+pub fn product(numbers: &[f64]) -> f64 {
+    numbers.iter().product()
+}
+
+/// [`product`], stopping when `ctx` is cancelled and reporting progress to it
+pub(crate) fn product_with_context(numbers: &[f64], ctx: &ToolContext) -> McpResult<f64> {
+    let mut total = 1.0;
+    scan(numbers, Some(ctx), 0, 1, |x| total *= x)?;
+    Ok(total)
+}
@@ -0,0 +1,80 @@
+use crate::error::{McpError, McpResult};
+
+/// Sine of an angle in radians
+pub fn sin(angle: f64) -> f64 {
+    angle.sin()
+}
+
+/// Cosine of an angle in radians
+pub fn cos(angle: f64) -> f64 {
+    angle.cos()
+}
+
+/// Tangent of an angle in radians
+pub fn tan(angle: f64) -> f64 {
+    angle.tan()
+}
+
+/// Arcsine in radians; fails outside [-1, 1]
+pub fn asin(value: f64) -> McpResult<f64> {
+    if !(-1.0..=1.0).contains(&value) {
+        return Err(McpError::validation_error(
+            "Value must be between -1 and 1 for arcsine",
+        ));
+    }
+    Ok(value.asin())
+}
+
+/// Arccosine in radians; fails outside [-1, 1]
+pub fn acos(value: f64) -> McpResult<f64> {
+    if !(-1.0..=1.0).contains(&value) {
+        return Err(McpError::validation_error(
+            "Value must be between -1 and 1 for arccosine",
+        ));
+    }
+    Ok(value.acos())
+}
+
+/// Arctangent in radians
+pub fn atan(value: f64) -> f64 {
+    value.atan()
+}
+
+/// Side c opposite angle C (radians), from sides a and b: c² = a² + b² - 2ab cos(C)
+pub fn law_of_cosines_side(a: f64, b: f64, angle_c: f64) -> f64 {
+    (a * a + b * b - 2.0 * a * b * angle_c.cos()).sqrt()
+}
+
+/// Angle C (radians) opposite side c, from the three sides; fails when they
+/// do not form a triangle
+pub fn law_of_cosines_angle(a: f64, b: f64, c: f64) -> McpResult<f64> {
+    let cos_c = (a * a + b * b - c * c) / (2.0 * a * b);
+    if cos_c.abs() > 1.0 {
+        return Err(McpError::validation_error(
+            "Invalid triangle: sides do not satisfy triangle inequality",
+        ));
+    }
+    Ok(cos_c.acos())
+}
+
+/// Side opposite `angle`, given another side and its opposite angle (radians):
+/// b = a × sin(B) / sin(A)
+pub fn law_of_sines_side(side: f64, opposite_angle: f64, angle: f64) -> f64 {
+    side * angle.sin() / opposite_angle.sin()
+}
+
+/// Ratio of a side to the sine of its opposite angle (radians), equal for
+/// every side of a triangle
+pub fn sine_ratio(side: f64, opposite_angle: f64) -> f64 {
+    side / opposite_angle.sin()
+}
+
+/// Convert degrees to radians
+pub fn degrees_to_radians(degrees: f64) -> f64 {
+    degrees * std::f64::consts::PI / 180.0
+}
+
+/// Convert radians to degrees
+pub fn radians_to_degrees(radians: f64) -> f64 {
+    radians * 180.0 / std::f64::consts::PI
+}
@@ -0,0 +1,358 @@
+//! Per-tool usage metrics.
+//!
+//! Every tool call dispatched by [`crate::tools::DefaultToolRegistry`],
+//! including the operations inside `batch_operations`, is counted here with
+//! its outcome and latency. The metrics are process-wide and served as the
+//! `math://metrics` resource and, over HTTP, as a Prometheus text page.
+
+use crate::error::McpResult;
+use crate::tools::CacheStats;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets, in seconds
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0,
+];
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// The process-wide metrics the registry records into
+pub fn global() -> &'static Metrics {
+    &METRICS
+}
+
+/// Counters and latency histograms, by tool.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::error::{McpError, McpResult};
+/// use rust_math_mcp::metrics::Metrics;
+/// use std::time::Duration;
+///
+/// let metrics = Metrics::new();
+/// let failed: McpResult<()> = Err(McpError::validation_error("Division by zero"));
+/// metrics.record("divide", Duration::from_millis(2), &Ok(()));
+/// metrics.record("divide", Duration::from_millis(3), &failed);
+///
+/// let snapshot = metrics.snapshot();
+/// assert_eq!(snapshot["tools"]["divide"]["calls"], 2);
+/// assert_eq!(snapshot["tools"]["divide"]["errors"]["-32001"], 1);
+/// assert!(metrics.render_prometheus().contains("mcp_tool_calls_total{tool=\"divide\"} 2"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Metrics {
+    tools: Mutex<BTreeMap<String, ToolMetrics>>,
+}
+
+#[derive(Debug, Clone)]
+struct ToolMetrics {
+    calls: u64,
+    /// Failed calls by `McpError` code
+    errors: BTreeMap<i32, u64>,
+    /// Batch operations not run because an earlier one failed
+    skipped: u64,
+    /// Calls per latency bucket (not cumulative); the last counts the rest
+    buckets: Vec<u64>,
+    total_seconds: f64,
+    max_seconds: f64,
+}
+
+impl Default for ToolMetrics {
+    fn default() -> Self {
+        Self {
+            calls: 0,
+            errors: BTreeMap::new(),
+            skipped: 0,
+            buckets: vec![0; LATENCY_BUCKETS.len() + 1],
+            total_seconds: 0.0,
+            max_seconds: 0.0,
+        }
+    }
+}
+
+impl Metrics {
+    /// Create empty metrics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a call to `tool` that took `elapsed` and ended with `outcome`
+    pub fn record<T>(&self, tool: &str, elapsed: Duration, outcome: &McpResult<T>) {
+        let seconds = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+
+        let mut tools = self.lock();
+        let metrics = tools.entry(tool.to_string()).or_default();
+        metrics.calls += 1;
+        if let Err(e) = outcome {
+            *metrics.errors.entry(e.code).or_default() += 1;
+        }
+        metrics.buckets[bucket] += 1;
+        metrics.total_seconds += seconds;
+        metrics.max_seconds = metrics.max_seconds.max(seconds);
+    }
+
+    /// Record a batch operation on `tool` that was skipped after an earlier
+    /// operation failed
+    pub fn record_skipped(&self, tool: &str) {
+        self.lock().entry(tool.to_string()).or_default().skipped += 1;
+    }
+
+    /// Forget everything recorded so far
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    /// The metrics as JSON, with latencies in milliseconds
+    pub fn snapshot(&self) -> Value {
+        let tools: Map<String, Value> = self
+            .lock()
+            .iter()
+            .map(|(tool, metrics)| {
+                let errors: Map<String, Value> = metrics
+                    .errors
+                    .iter()
+                    .map(|(code, count)| (code.to_string(), Value::from(*count)))
+                    .collect();
+                let mut cumulative = 0;
+                let buckets: Vec<Value> = metrics
+                    .buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(index, count)| {
+                        cumulative += count;
+                        let bound = LATENCY_BUCKETS.get(index).map(|bound| bound * 1000.0);
+                        json!({ "le_ms": bound, "count": cumulative })
+                    })
+                    .collect();
+                let mean = if metrics.calls > 0 {
+                    metrics.total_seconds * 1000.0 / metrics.calls as f64
+                } else {
+                    0.0
+                };
+                let value = json!({
+                    "calls": metrics.calls,
+                    "errors": errors,
+                    "skipped": metrics.skipped,
+                    "latency_ms": {
+                        "mean": mean,
+                        "max": metrics.max_seconds * 1000.0,
+                        "total": metrics.total_seconds * 1000.0,
+                        "buckets": buckets,
+                    },
+                });
+                (tool.clone(), value)
+            })
+            .collect();
+        json!({ "tools": tools, "cache": cache_stats() })
+    }
+
+    /// The metrics in the Prometheus text exposition format, with latencies
+    /// in seconds
+    pub fn render_prometheus(&self) -> String {
+        let tools = self.lock().clone();
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "mcp_tool_calls_total",
+            "counter",
+            "Tool calls by tool",
+        );
+        for (tool, metrics) in &tools {
+            let _ = writeln!(
+                out,
+                "mcp_tool_calls_total{{tool=\"{}\"}} {}",
+                escape(tool),
+                metrics.calls
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_tool_errors_total",
+            "counter",
+            "Failed tool calls by tool and error code",
+        );
+        for (tool, metrics) in &tools {
+            for (code, count) in &metrics.errors {
+                let _ = writeln!(
+                    out,
+                    "mcp_tool_errors_total{{tool=\"{}\",code=\"{}\"}} {}",
+                    escape(tool),
+                    code,
+                    count
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "mcp_batch_operations_skipped_total",
+            "counter",
+            "Batch operations skipped after an earlier operation failed, by tool",
+        );
+        for (tool, metrics) in tools.iter().filter(|(_, metrics)| metrics.skipped > 0) {
+            let _ = writeln!(
+                out,
+                "mcp_batch_operations_skipped_total{{tool=\"{}\"}} {}",
+                escape(tool),
+                metrics.skipped
+            );
+        }
+
+        header(
+            &mut out,
+            "mcp_tool_duration_seconds",
+            "histogram",
+            "Tool call latency in seconds",
+        );
+        for (tool, metrics) in &tools {
+            let tool = escape(tool);
+            let mut cumulative = 0;
+            for (index, count) in metrics.buckets.iter().enumerate() {
+                cumulative += count;
+                let bound = LATENCY_BUCKETS
+                    .get(index)
+                    .map_or("+Inf".to_string(), |bound| bound.to_string());
+                let _ = writeln!(
+                    out,
+                    "mcp_tool_duration_seconds_bucket{{tool=\"{}\",le=\"{}\"}} {}",
+                    tool, bound, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "mcp_tool_duration_seconds_sum{{tool=\"{}\"}} {}",
+                tool, metrics.total_seconds
+            );
+            let _ = writeln!(
+                out,
+                "mcp_tool_duration_seconds_count{{tool=\"{}\"}} {}",
+                tool, metrics.calls
+            );
+        }
+
+        let cache = cache_stats();
+        let counters = [
+            (
+                "mcp_cache_hits_total",
+                "Tool calls answered from the result cache",
+                cache.hits,
+            ),
+            (
+                "mcp_cache_misses_total",
+                "Cacheable tool calls that had to run",
+                cache.misses,
+            ),
+            (
+                "mcp_cache_evictions_total",
+                "Results evicted from the result cache",
+                cache.evictions,
+            ),
+        ];
+        for (name, help, value) in counters {
+            header(&mut out, name, "counter", help);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+        header(
+            &mut out,
+            "mcp_cache_entries",
+            "gauge",
+            "Results in the result cache",
+        );
+        let _ = writeln!(out, "mcp_cache_entries {}", cache.entries);
+        header(
+            &mut out,
+            "mcp_cache_bytes",
+            "gauge",
+            "Approximate size of the result cache",
+        );
+        let _ = writeln!(out, "mcp_cache_bytes {}", cache.bytes);
+        out
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, ToolMetrics>> {
+        self.tools.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn cache_stats() -> CacheStats {
+    crate::tools::DefaultToolRegistry.cache_stats()
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value: backslashes, quotes and newlines
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::McpError;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let metrics = Metrics::new();
+        metrics.record("add", Duration::from_micros(50), &Ok(()));
+        metrics.record("add", Duration::from_millis(20), &Ok(()));
+        metrics.record("add", Duration::from_secs(20), &Ok(()));
+
+        let snapshot = metrics.snapshot();
+        let buckets = snapshot["tools"]["add"]["latency_ms"]["buckets"]
+            .as_array()
+            .unwrap();
+        assert_eq!(buckets[0], json!({ "le_ms": 0.1, "count": 1 }));
+        assert_eq!(buckets[5]["count"], 2);
+        assert_eq!(
+            buckets.last().unwrap(),
+            &json!({ "le_ms": null, "count": 3 })
+        );
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("mcp_tool_duration_seconds_bucket{tool=\"add\",le=\"0.0001\"} 1"));
+        assert!(text.contains("mcp_tool_duration_seconds_bucket{tool=\"add\",le=\"+Inf\"} 3"));
+        assert!(text.contains("mcp_tool_duration_seconds_count{tool=\"add\"} 3"));
+    }
+
+    #[test]
+    fn test_errors_by_code_and_skipped() {
+        let metrics = Metrics::new();
+        let error: McpResult<()> = Err(McpError::invalid_params("bad"));
+        metrics.record("mean", Duration::ZERO, &error);
+        metrics.record("mean", Duration::ZERO, &error);
+        metrics.record_skipped("mean");
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot["tools"]["mean"]["errors"]["-32602"], 2);
+        assert_eq!(snapshot["tools"]["mean"]["skipped"], 1);
+        let text = metrics.render_prometheus();
+        assert!(text.contains("mcp_tool_errors_total{tool=\"mean\",code=\"-32602\"} 2"));
+        assert!(text.contains("mcp_batch_operations_skipped_total{tool=\"mean\"} 1"));
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -0,0 +1,361 @@
+//! MCP prompt templates for guided multi-step problems.
+//!
+//! Each prompt takes string arguments and expands into a short message sequence
+//! that tells the model which tools to use and in what order, so clients can
+//! offer them as slash-commands.
+
+use crate::error::{McpError, McpResult};
+use crate::tools::batch::TOOL_BATCH;
+use serde_json::{json, Map, Value};
+
+/// A prompt argument as advertised in `prompts/list`
+#[derive(Debug, Clone, Copy)]
+pub struct PromptArgument {
+    /// Argument name
+    pub name: &'static str,
+    /// What the argument should contain
+    pub description: &'static str,
+    /// Whether `prompts/get` fails without it
+    pub required: bool,
+}
+
+/// A prompt template as advertised in `prompts/list`
+#[derive(Debug, Clone, Copy)]
+pub struct Prompt {
+    /// Prompt name
+    pub name: &'static str,
+    /// What the prompt helps with
+    pub description: &'static str,
+    /// Arguments the prompt accepts
+    pub arguments: &'static [PromptArgument],
+}
+
+/// Who a prompt message is attributed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// One message of an expanded prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptMessage {
+    pub role: Role,
+    pub text: String,
+}
+
+impl PromptMessage {
+    fn user(text: impl Into<String>) -> Self {
+        Self {
+            role: Role::User,
+            text: text.into(),
+        }
+    }
+
+    fn assistant(text: impl Into<String>) -> Self {
+        Self {
+            role: Role::Assistant,
+            text: text.into(),
+        }
+    }
+}
+
+pub const PROMPT_SOLVE_WORD_PROBLEM: &str = "solve_word_problem";
+pub const PROMPT_ANALYZE_DATASET: &str = "analyze_dataset";
+pub const PROMPT_LOAN_COMPARISON: &str = "loan_comparison";
+
+/// All available prompts
+pub static PROMPTS: &[Prompt] = &[
+    Prompt {
+        name: PROMPT_SOLVE_WORD_PROBLEM,
+        description: "Work through a math word problem step by step using the math tools",
+        arguments: &[PromptArgument {
+            name: "problem",
+            description: "The word problem, as written",
+            required: true,
+        }],
+    },
+    Prompt {
+        name: PROMPT_ANALYZE_DATASET,
+        description: "Summarize a list of numbers with descriptive statistics",
+        arguments: &[
+            PromptArgument {
+                name: "data",
+                description: "Numbers separated by commas or spaces, or a JSON array",
+                required: true,
+            },
+            PromptArgument {
+                name: "sample",
+                description: "\"true\" if the data is a sample rather than the whole population (default: true)",
+                required: false,
+            },
+            PromptArgument {
+                name: "context",
+                description: "What the numbers measure, e.g. \"daily response times in ms\"",
+                required: false,
+            },
+        ],
+    },
+    Prompt {
+        name: PROMPT_LOAN_COMPARISON,
+        description: "Compare the cost of a loan at several interest rates",
+        arguments: &[
+            PromptArgument {
+                name: "principal",
+                description: "Amount borrowed",
+                required: true,
+            },
+            PromptArgument {
+                name: "rates",
+                description: "Annual interest rates to compare, separated by commas (e.g. \"4.5%, 5.25%\" or \"0.045, 0.0525\")",
+                required: true,
+            },
+            PromptArgument {
+                name: "years",
+                description: "Loan term in years",
+                required: true,
+            },
+            PromptArgument {
+                name: "compounds_per_year",
+                description: "How often interest compounds per year (default: 12)",
+                required: false,
+            },
+        ],
+    },
+];
+
+/// Look up a prompt by name
+pub fn prompt(name: &str) -> Option<&'static Prompt> {
+    PROMPTS.iter().find(|prompt| prompt.name == name)
+}
+
+/// Expand a prompt with the given arguments.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::prompts;
+/// use serde_json::json;
+///
+/// let args = json!({ "data": "3, 5, 7" });
+/// let messages = prompts::get("analyze_dataset", args.as_object().unwrap()).unwrap();
+/// assert!(messages[0].text.contains("[3.0,5.0,7.0]"));
+/// ```
+pub fn get(name: &str, arguments: &Map<String, Value>) -> McpResult<Vec<PromptMessage>> {
+    let prompt = prompt(name)
+        .ok_or_else(|| McpError::invalid_params(format!("Unknown prompt: {}", name)))?;
+
+    for argument in prompt.arguments.iter().filter(|a| a.required) {
+        if argument_text(arguments, argument.name).is_none() {
+            return Err(McpError::invalid_params(format!(
+                "Prompt '{}' requires argument '{}'",
+                name, argument.name
+            )));
+        }
+    }
+
+    match name {
+        PROMPT_SOLVE_WORD_PROBLEM => solve_word_problem(arguments),
+        PROMPT_ANALYZE_DATASET => analyze_dataset(arguments),
+        PROMPT_LOAN_COMPARISON => loan_comparison(arguments),
+        _ => Err(McpError::invalid_params(format!(
+            "Unknown prompt: {}",
+            name
+        ))),
+    }
+}
+
+fn solve_word_problem(arguments: &Map<String, Value>) -> McpResult<Vec<PromptMessage>> {
+    let problem = argument_text(arguments, "problem").unwrap_or_default();
+
+    Ok(vec![
+        PromptMessage::user(format!(
+            "Solve this word problem:\n\n{problem}\n\n\
+             Work in these steps:\n\
+             1. List the known quantities (with units) and the quantity asked for.\n\
+             2. Write the formula or equation that connects them. The math://formulas/{{category}} \
+             resources list the formulas each tool implements.\n\
+             3. Do every calculation with the math tools rather than mentally. When several \
+             calculations do not depend on each other, send them together in one `{batch}` call.\n\
+             4. Check that the answer is reasonable (sign, magnitude, units).\n\
+             5. State the final answer in a sentence, with units.",
+            problem = problem,
+            batch = TOOL_BATCH,
+        )),
+        PromptMessage::assistant(
+            "I'll start by listing the known quantities and what the problem is asking for.",
+        ),
+    ])
+}
+
+fn analyze_dataset(arguments: &Map<String, Value>) -> McpResult<Vec<PromptMessage>> {
+    let data = argument_text(arguments, "data").unwrap_or_default();
+    let numbers = parse_numbers(&data)
+        .ok_or_else(|| McpError::invalid_params("Argument 'data' must be a list of numbers"))?;
+    let sample = match argument_text(arguments, "sample") {
+        Some(text) => text.trim().parse::<bool>().map_err(|_| {
+            McpError::invalid_params("Argument 'sample' must be \"true\" or \"false\"")
+        })?,
+        None => true,
+    };
+    let context = argument_text(arguments, "context")
+        .map(|context| format!(" The values are {}.", context))
+        .unwrap_or_default();
+
+    let data = json!(numbers);
+    let operations = json!([
+        { "id": "mean", "tool": "mean", "arguments": { "numbers": data } },
+        { "id": "median", "tool": "median", "arguments": { "numbers": data } },
+        { "id": "mode", "tool": "mode", "arguments": { "numbers": data } },
+        { "id": "std_dev", "tool": "std_dev", "arguments": { "numbers": data, "sample": sample } },
+        { "id": "min", "tool": "min", "arguments": { "numbers": data } },
+        { "id": "max", "tool": "max", "arguments": { "numbers": data } },
+    ]);
+
+    Ok(vec![PromptMessage::user(format!(
+        "Analyze this dataset of {count} values:{context}\n\n{data}\n\n\
+         Call `{batch}` once with these operations to get the summary statistics:\n\n{operations}\n\n\
+         Then describe the center (mean vs. median), the spread (standard deviation, range from \
+         min to max), and anything notable such as skew, repeated values or outliers. Use the \
+         tools for any further arithmetic.",
+        count = numbers.len(),
+        context = context,
+        data = data,
+        batch = TOOL_BATCH,
+        operations = operations,
+    ))])
+}
+
+fn loan_comparison(arguments: &Map<String, Value>) -> McpResult<Vec<PromptMessage>> {
+    let principal = number_argument(arguments, "principal")?;
+    let years = number_argument(arguments, "years")?;
+    let compounds = match argument_text(arguments, "compounds_per_year") {
+        Some(_) => number_argument(arguments, "compounds_per_year")?,
+        None => 12.0,
+    };
+    let rates: Vec<f64> = argument_text(arguments, "rates")
+        .unwrap_or_default()
+        .split(',')
+        .map(parse_rate)
+        .collect::<Option<_>>()
+        .filter(|rates: &Vec<f64>| !rates.is_empty())
+        .ok_or_else(|| {
+            McpError::invalid_params(
+                "Argument 'rates' must be a comma-separated list of rates, e.g. \"4.5%, 5.25%\"",
+            )
+        })?;
+
+    let operations: Vec<Value> = rates
+        .iter()
+        .enumerate()
+        .map(|(i, rate)| {
+            json!({
+                "id": format!("option_{}", i + 1),
+                "tool": "compound_interest",
+                "arguments": {
+                    "principal": principal,
+                    "rate": rate,
+                    "time": years,
+                    "compounds_per_year": compounds,
+                }
+            })
+        })
+        .collect();
+    let options: Vec<String> = rates
+        .iter()
+        .enumerate()
+        .map(|(i, rate)| {
+            // Round away float noise such as 4.499999999999999
+            let percent = (rate * 100.0 * 1e9).round() / 1e9;
+            format!("- option_{}: {}% per year", i + 1, percent)
+        })
+        .collect();
+
+    Ok(vec![PromptMessage::user(format!(
+        "Compare borrowing {principal} over {years} years, with interest compounded {compounds} \
+         times per year, at these rates:\n{options}\n\n\
+         Call `{batch}` once with these operations to get the amount owed under each option:\n\n\
+         {operations}\n\n\
+         For each option, the total interest is the amount owed minus the principal; compute it \
+         with `subtract`. Then present a table of rate, amount owed and total interest, state how \
+         much the cheapest option saves over each of the others, and note that this assumes the \
+         balance is repaid in one lump sum at the end of the term.",
+        principal = principal,
+        years = years,
+        compounds = compounds,
+        options = options.join("\n"),
+        batch = TOOL_BATCH,
+        operations = Value::from(operations),
+    ))])
+}
+
+/// Get an argument as text; clients usually send strings but may send numbers
+fn argument_text(arguments: &Map<String, Value>, name: &str) -> Option<String> {
+    match arguments.get(name)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Array(_) => Some(arguments[name].to_string()),
+        _ => None,
+    }
+}
+
+fn number_argument(arguments: &Map<String, Value>, name: &str) -> McpResult<f64> {
+    argument_text(arguments, name)
+        .and_then(|text| text.trim().parse::<f64>().ok())
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| McpError::invalid_params(format!("Argument '{}' must be a number", name)))
+}
+
+/// Parse "1, 2 3" or "[1, 2, 3]" into numbers
+fn parse_numbers(text: &str) -> Option<Vec<f64>> {
+    let numbers: Vec<f64> = text
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f64>().ok().filter(|n| n.is_finite()))
+        .collect::<Option<_>>()?;
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+/// Parse "5%" as 0.05; bare numbers above 1 are also read as percentages
+fn parse_rate(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (number, percent) = match text.strip_suffix('%') {
+        Some(number) => (number.trim(), true),
+        None => (text, false),
+    };
+    let value = number
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)?;
+    Some(if percent || value > 1.0 {
+        value / 100.0
+    } else {
+        value
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_numbers("1, 2 3"), Some(vec![1.0, 2.0, 3.0]));
+        assert_eq!(parse_numbers("[1.5,-2]"), Some(vec![1.5, -2.0]));
+        assert_eq!(parse_numbers("1, two"), None);
+        assert_eq!(parse_numbers(" "), None);
+    }
+
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(parse_rate("5%"), Some(0.05));
+        assert_eq!(parse_rate(" 0.05 "), Some(0.05));
+        assert_eq!(parse_rate("5"), Some(0.05));
+        assert_eq!(parse_rate("-1%"), None);
+        assert_eq!(parse_rate("abc"), None);
+    }
+}
@@ -6,6 +6,7 @@ pub use parser::ParseResult;
 
 use crate::config::Config;
 use crate::error::{McpError, McpResult};
+use crate::tools::ToolContext;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::io::{self, Write};
@@ -249,9 +250,19 @@ pub fn handle_method_with_config<T: crate::tools::ToolRegistry>(
         }
         constants::methods::TOOLS_LIST => {
             debug!("Listing tools, id: {:?}", id);
-            let result = serde_json::json!({
-                "tools": registry.get_all_tools()
-            });
+            let tools: Vec<Value> = registry
+                .get_all_tools()
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|tool| {
+                    tool["name"]
+                        .as_str()
+                        .is_some_and(|name| config.tool_enabled(name))
+                })
+                .cloned()
+                .collect();
+            let result = serde_json::json!({ "tools": tools });
             Ok(JsonRpcResponse {
                 jsonrpc: constants::JSON_RPC_VERSION.to_string(),
                 id: id.clone(),
@@ -269,7 +280,14 @@ pub fn handle_method_with_config<T: crate::tools::ToolRegistry>(
                 "Executing tool"
             );
 
-            match registry.execute_tool(&call_params.name, &call_params.arguments) {
+            let ctx = ToolContext::new()
+                .with_config(config.clone())
+                .with_timeout(config.tool_timeout(&call_params.name));
+            match registry.execute_tool_with_context(
+                &call_params.name,
+                &call_params.arguments,
+                &ctx,
+            ) {
                 Ok(result) => {
                     debug!("Tool execution success, id: {:?}", id);
                     Ok(JsonRpcResponse {
@@ -281,7 +299,8 @@ pub fn handle_method_with_config<T: crate::tools::ToolRegistry>(
                                     "type": "text",
                                     "text": serde_json::to_string(&result)?
                                 }
-                            ]
+                            ],
+                            "structuredContent": result
                         })),
                         error: None,
                     })
@@ -292,21 +311,13 @@ pub fn handle_method_with_config<T: crate::tools::ToolRegistry>(
                         error = %e,
                         "Tool execution error"
                     );
-                    // MCP requires result to always be present, even for errors
-                    // Return error information in the result content
+                    // MCP reports tool failures as an `isError` result, not a
+                    // JSON-RPC error; unknown methods get the latter below
                     debug!("Tool execution error, id: {:?}", id);
                     Ok(JsonRpcResponse {
                         jsonrpc: constants::JSON_RPC_VERSION.to_string(),
                         id: id.clone(),
-                        result: Some(serde_json::json!({
-                            "content": [
-                                {
-                                    "type": "text",
-                                    "text": format!("Error: {}", e.message)
-                                }
-                            ],
-                            "isError": true
-                        })),
+                        result: Some(serde_json::to_value(e.to_tool_result())?),
                         error: None,
                     })
                 }
@@ -328,20 +339,11 @@ pub fn handle_method_with_config<T: crate::tools::ToolRegistry>(
             // Log as debug instead of error for unknown methods (might be optional notifications)
             debug!(method = %method, "Unknown method");
             debug!("Unknown method, id: {:?}", id);
-            // MCP requires result to always be present, even for errors
             Ok(JsonRpcResponse {
                 jsonrpc: constants::JSON_RPC_VERSION.to_string(),
                 id: id.clone(),
-                result: Some(serde_json::json!({
-                    "content": [
-                        {
-                            "type": "text",
-                            "text": format!("Method not found: {}", method)
-                        }
-                    ],
-                    "isError": true
-                })),
-                error: None,
+                result: None,
+                error: Some(McpError::method_not_found(method).into()),
             })
         }
     }
@@ -0,0 +1,165 @@
+use serde::Serialize;
+
+/// A named constant with its value and SI unit
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Constant {
+    /// Identifier, e.g. `speed_of_light`
+    pub name: &'static str,
+    /// Conventional symbol
+    pub symbol: &'static str,
+    /// Value (exact where the SI defines it, otherwise CODATA 2018)
+    pub value: f64,
+    /// SI unit; empty for dimensionless constants
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub unit: &'static str,
+    /// What the constant is
+    pub description: &'static str,
+}
+
+/// Mathematical constants
+pub const MATHEMATICAL: &[Constant] = &[
+    Constant {
+        name: "pi",
+        symbol: "π",
+        value: std::f64::consts::PI,
+        unit: "",
+        description: "Ratio of a circle's circumference to its diameter",
+    },
+    Constant {
+        name: "tau",
+        symbol: "τ",
+        value: std::f64::consts::TAU,
+        unit: "",
+        description: "Ratio of a circle's circumference to its radius (2π)",
+    },
+    Constant {
+        name: "e",
+        symbol: "e",
+        value: std::f64::consts::E,
+        unit: "",
+        description: "Euler's number, the base of the natural logarithm",
+    },
+    Constant {
+        name: "phi",
+        symbol: "φ",
+        value: 1.618_033_988_749_895,
+        unit: "",
+        description: "Golden ratio, (1 + √5) / 2",
+    },
+    Constant {
+        name: "sqrt_2",
+        symbol: "√2",
+        value: std::f64::consts::SQRT_2,
+        unit: "",
+        description: "Square root of 2",
+    },
+    Constant {
+        name: "ln_2",
+        symbol: "ln 2",
+        value: std::f64::consts::LN_2,
+        unit: "",
+        description: "Natural logarithm of 2",
+    },
+    Constant {
+        name: "ln_10",
+        symbol: "ln 10",
+        value: std::f64::consts::LN_10,
+        unit: "",
+        description: "Natural logarithm of 10",
+    },
+    Constant {
+        name: "euler_gamma",
+        symbol: "γ",
+        value: 0.577_215_664_901_532_9,
+        unit: "",
+        description: "Euler–Mascheroni constant",
+    },
+];
+
+/// Physical constants
+pub const PHYSICAL: &[Constant] = &[
+    Constant {
+        name: "speed_of_light",
+        symbol: "c",
+        value: 299_792_458.0,
+        unit: "m/s",
+        description: "Speed of light in vacuum (exact)",
+    },
+    Constant {
+        name: "gravitational_constant",
+        symbol: "G",
+        value: 6.674_30e-11,
+        unit: "m³/(kg·s²)",
+        description: "Newtonian constant of gravitation",
+    },
+    Constant {
+        name: "standard_gravity",
+        symbol: "g₀",
+        value: 9.806_65,
+        unit: "m/s²",
+        description: "Standard acceleration of gravity (exact)",
+    },
+    Constant {
+        name: "planck",
+        symbol: "h",
+        value: 6.626_070_15e-34,
+        unit: "J·s",
+        description: "Planck constant (exact)",
+    },
+    Constant {
+        name: "reduced_planck",
+        symbol: "ħ",
+        value: 1.054_571_817e-34,
+        unit: "J·s",
+        description: "Reduced Planck constant, h / 2π",
+    },
+    Constant {
+        name: "elementary_charge",
+        symbol: "e",
+        value: 1.602_176_634e-19,
+        unit: "C",
+        description: "Elementary charge (exact)",
+    },
+    Constant {
+        name: "boltzmann",
+        symbol: "k_B",
+        value: 1.380_649e-23,
+        unit: "J/K",
+        description: "Boltzmann constant (exact)",
+    },
+    Constant {
+        name: "avogadro",
+        symbol: "N_A",
+        value: 6.022_140_76e23,
+        unit: "1/mol",
+        description: "Avogadro constant (exact)",
+    },
+    Constant {
+        name: "gas_constant",
+        symbol: "R",
+        value: 8.314_462_618,
+        unit: "J/(mol·K)",
+        description: "Molar gas constant, N_A × k_B",
+    },
+    Constant {
+        name: "vacuum_permittivity",
+        symbol: "ε₀",
+        value: 8.854_187_812_8e-12,
+        unit: "F/m",
+        description: "Electric constant",
+    },
+    Constant {
+        name: "electron_mass",
+        symbol: "mₑ",
+        value: 9.109_383_701_5e-31,
+        unit: "kg",
+        description: "Rest mass of the electron",
+    },
+    Constant {
+        name: "proton_mass",
+        symbol: "mₚ",
+        value: 1.672_621_923_69e-27,
+        unit: "kg",
+        description: "Rest mass of the proton",
+    },
+];
@@ -0,0 +1,209 @@
+//! Read-only MCP resources.
+//!
+//! - `math://constants`: mathematical and physical constants with units
+//! - `math://metrics`: per-tool call counts, error counts and latencies
+//! - `math://formulas/{category}`: the formulas each tool category implements
+//! - `math://tools/{name}`: extended documentation for one tool, with a worked example
+//!
+//! Resources are served as JSON text. Tools the configuration disables are
+//! left out of the formula references and have no documentation page.
+
+pub mod constants;
+
+use crate::config::Config;
+use crate::error::{McpError, McpResult};
+use crate::metrics;
+use crate::tools::registry::{category, ToolCategory, CATEGORIES};
+use serde_json::{json, Value};
+
+/// URI of the constants table
+pub const CONSTANTS_URI: &str = "math://constants";
+/// URI of the tool usage metrics
+pub const METRICS_URI: &str = "math://metrics";
+/// URI prefix of the per-category formula references
+pub const FORMULAS_PREFIX: &str = "math://formulas/";
+/// URI prefix of the per-tool documentation
+pub const TOOLS_PREFIX: &str = "math://tools/";
+/// MIME type of every resource
+pub const MIME_TYPE: &str = "application/json";
+
+/// A resource (or resource template) as advertised to clients
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceInfo {
+    /// Resource URI, or URI template for [`templates`]
+    pub uri: String,
+    /// Short name
+    pub name: String,
+    /// What the resource contains
+    pub description: String,
+}
+
+impl ResourceInfo {
+    fn new(
+        uri: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// List every concrete resource: the constants table, the metrics, one formula
+/// reference per category with enabled tools and one documentation page per
+/// enabled tool
+pub fn list(config: &Config) -> Vec<ResourceInfo> {
+    let mut resources = vec![
+        ResourceInfo::new(
+            CONSTANTS_URI,
+            "constants",
+            "Mathematical and physical constants with units",
+        ),
+        ResourceInfo::new(
+            METRICS_URI,
+            "metrics",
+            "Call counts, error counts by code and latency histograms for each tool",
+        ),
+    ];
+
+    for category in CATEGORIES {
+        if enabled_tools(category, config).next().is_none() {
+            continue;
+        }
+        resources.push(ResourceInfo::new(
+            format!("{}{}", FORMULAS_PREFIX, category.name),
+            format!("formulas/{}", category.name),
+            format!("Formulas used by the {} tools", category.name),
+        ));
+    }
+
+    for category in CATEGORIES {
+        for name in enabled_tools(category, config) {
+            resources.push(ResourceInfo::new(
+                format!("{}{}", TOOLS_PREFIX, name),
+                format!("tools/{}", name),
+                format!("Documentation and example for the {} tool", name),
+            ));
+        }
+    }
+
+    resources
+}
+
+/// List the parameterized resource URIs
+pub fn templates() -> Vec<ResourceInfo> {
+    vec![
+        ResourceInfo::new(
+            format!("{}{{category}}", FORMULAS_PREFIX),
+            "formulas",
+            format!(
+                "Formulas used by a tool category ({})",
+                CATEGORIES
+                    .iter()
+                    .map(|category| category.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        ResourceInfo::new(
+            format!("{}{{name}}", TOOLS_PREFIX),
+            "tools",
+            "Documentation, formula, input schema and a worked example for a tool",
+        ),
+    ]
+}
+
+/// Read a resource by URI.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::resources;
+///
+/// let doc = resources::read("math://tools/compound_interest", &Config::new()).unwrap();
+/// assert_eq!(doc["formula"], "A = P(1 + r/n)^(nt)");
+/// ```
+pub fn read(uri: &str, config: &Config) -> McpResult<Value> {
+    if uri == CONSTANTS_URI {
+        return Ok(read_constants());
+    }
+    if uri == METRICS_URI {
+        return Ok(metrics::global().snapshot());
+    }
+    if let Some(name) = uri.strip_prefix(FORMULAS_PREFIX) {
+        return read_formulas(name, config).ok_or_else(|| unknown_resource(uri));
+    }
+    if let Some(name) = uri.strip_prefix(TOOLS_PREFIX) {
+        return read_tool(name, config).ok_or_else(|| unknown_resource(uri));
+    }
+    Err(unknown_resource(uri))
+}
+
+fn read_constants() -> Value {
+    json!({
+        "mathematical": constants::MATHEMATICAL,
+        "physical": constants::PHYSICAL,
+    })
+}
+
+/// Names of the tools in `category` that `config` enables
+fn enabled_tools<'a>(
+    category: &ToolCategory,
+    config: &'a Config,
+) -> impl Iterator<Item = String> + 'a {
+    (category.definitions)()
+        .into_iter()
+        .filter_map(|definition| definition["name"].as_str().map(str::to_string))
+        .filter(|name| config.tool_enabled(name))
+}
+
+fn read_formulas(name: &str, config: &Config) -> Option<Value> {
+    let category = category(name)?;
+    let formulas: Vec<Value> = (category.documentation)()
+        .into_iter()
+        .filter(|doc| {
+            doc["name"]
+                .as_str()
+                .is_some_and(|name| config.tool_enabled(name))
+        })
+        .map(|doc| {
+            json!({
+                "tool": doc["name"],
+                "description": doc["description"],
+                "formula": doc.get("formula").cloned().unwrap_or(Value::Null),
+            })
+        })
+        .collect();
+    if formulas.is_empty() {
+        return None;
+    }
+
+    Some(json!({
+        "category": category.name,
+        "description": category.description,
+        "formulas": formulas,
+    }))
+}
+
+fn read_tool(name: &str, config: &Config) -> Option<Value> {
+    if !config.tool_enabled(name) {
+        return None;
+    }
+    CATEGORIES.iter().find_map(|category| {
+        (category.documentation)()
+            .into_iter()
+            .find(|doc| doc["name"] == name)
+            .map(|mut doc| {
+                doc["category"] = Value::from(category.name);
+                doc
+            })
+    })
+}
+
+fn unknown_resource(uri: &str) -> McpError {
+    McpError::invalid_params(format!("Unknown resource: {}", uri))
+}
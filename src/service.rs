@@ -1,53 +1,403 @@
+use crate::audit::AuditLog;
+use crate::config::Config;
+use crate::error::{McpError, McpResult};
+use crate::prompts::{self, Role};
+use crate::resources::{self, ResourceInfo};
+use crate::session::Session;
+use crate::tools::batch::TOOL_BATCH;
+use crate::tools::registry::{get_enabled_tools, DefaultToolRegistry};
+use crate::tools::{Progress, ToolContext, ToolRegistry};
+use crate::utils::output::apply_output_settings;
 use rmcp::{
+    model::{
+        AnnotateAble, CallToolRequestParam, CallToolResult, ClientInfo, Content, ErrorData,
+        GetPromptRequestParam, GetPromptResult, Implementation, JsonObject, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+        ProgressNotificationParam, ProgressToken, Prompt, PromptArgument, PromptMessage,
+        PromptMessageRole, ProtocolVersion, RawResource, RawResourceTemplate,
+        ReadResourceRequestParam, ReadResourceResult, Resource, ResourceContents, ResourceTemplate,
+        ServerCapabilities, ServerInfo, Tool,
+    },
+    service::{NotificationContext, Peer, RequestContext, RoleServer},
     ServerHandler,
-    model::{CallToolResult, CallToolRequestParam, ErrorCode, ErrorData, Implementation, ListToolsResult, PaginatedRequestParam, ProtocolVersion, ServerCapabilities, ServerInfo, Tool},
-    service::{RequestContext, RoleServer},
 };
-use std::borrow::Cow;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{debug, error};
 
 /// MathService implements the ServerHandler for rmcp
 /// This bridges the existing tool implementations with the rmcp SDK
-#[derive(Clone, Default)]
-pub struct MathService;
+///
+/// Each `MathService` value is one client session; clones share that session.
+#[derive(Clone)]
+pub struct MathService {
+    config: Arc<Config>,
+    session: Arc<Session>,
+    audit: Option<Arc<AuditLog>>,
+}
+
+impl Default for MathService {
+    fn default() -> Self {
+        Self::with_config(Arc::new(Config::new()))
+    }
+}
 
 impl MathService {
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Build tool list from existing implementations
+    /// Create a service (and a new session) using the given configuration
+    ///
+    /// If the configured audit log cannot be opened the error is logged and
+    /// calls are not audited; the binary opens it first to refuse to start instead.
+    pub fn with_config(config: Arc<Config>) -> Self {
+        let session = Arc::new(Session::new(&config));
+        let audit = AuditLog::shared(&config).unwrap_or_else(|e| {
+            error!(error = %e, "Failed to open the audit log");
+            None
+        });
+        Self {
+            config,
+            session,
+            audit,
+        }
+    }
+
+    /// The client session this service belongs to
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Build tool list from the registry's cached definitions of the enabled tools
     pub fn _list_tools(&self) -> Vec<Tool> {
-        // TODO: Wire up existing tool definitions from src/tools/
-        // For now, returning empty to let compilation succeed
-        vec![]
+        get_enabled_tools(&self.config)
+            .iter()
+            .filter_map(tool_from_definition)
+            .collect()
     }
 
     /// Execute a tool by name
+    ///
+    /// Tool failures (unknown tool, bad arguments, math errors) are reported as
+    /// `isError` results so the model can see and correct them, with the
+    /// error's code and `data` in `structuredContent`; only protocol-level
+    /// failures become JSON-RPC errors.
     pub async fn _call_tool(
         &self,
         tool_name: &str,
         tool_input: Option<serde_json::Map<String, serde_json::Value>>,
     ) -> Result<CallToolResult, ErrorData> {
-        // TODO: Wire up existing tool execution from src/tools/
-        // This bridges to the custom tool implementations
-        let _ = tool_input;
-        Err(ErrorData {
-            code: ErrorCode(-32601),
-            message: Cow::Owned(format!("Tool not implemented: {}", tool_name)),
-            data: None,
+        self._call_tool_with_context(tool_name, tool_input, ToolContext::new())
+            .await
+    }
+
+    /// Execute a tool by name within `ctx`, which supplies cancellation and
+    /// progress reporting; the service's configuration and the tool's
+    /// configured timeout are applied on top
+    ///
+    /// Cancellation and timeouts are reported as `isError` results carrying
+    /// the `resource_limit` message. Hitting the deadline cancels `ctx`, so
+    /// pass a child token when the caller's token must survive.
+    ///
+    /// Every call, including rate-limited ones, is written to the audit log
+    /// when one is configured.
+    pub async fn _call_tool_with_context(
+        &self,
+        tool_name: &str,
+        tool_input: Option<serde_json::Map<String, serde_json::Value>>,
+        ctx: ToolContext,
+    ) -> Result<CallToolResult, ErrorData> {
+        let arguments = Value::Object(tool_input.unwrap_or_default());
+        let audit = self.audit.as_ref().map(|audit| {
+            let call = audit.begin(self.session.id(), tool_name, &arguments);
+            (audit, call)
+        });
+
+        // Refused before the tool runs, as a JSON-RPC error rather than an
+        // `isError` result
+        let cost = self.call_cost(tool_name, &arguments);
+        if let Err(e) = self.session.check_rate_limit(tool_name, cost) {
+            if let Some((audit, call)) = audit {
+                audit.finish(call, Err(&e));
+            }
+            return Err(e.into());
+        }
+
+        let outcome = self.call_tool(tool_name, arguments, ctx).await;
+        if let Some((audit, call)) = audit {
+            audit.finish(call, outcome.as_ref());
+        }
+        match outcome {
+            Ok(result) => Ok(self.tool_result(result)),
+            Err(e) if e.is_protocol_error() => Err(e.into()),
+            Err(e) => {
+                error!(tool_name = %tool_name, error = %e, "Tool execution error");
+                Ok(e.to_tool_result())
+            }
+        }
+    }
+}
+
+impl MathService {
+    /// Run the tool in this session within its configured timeout
+    async fn call_tool(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+        ctx: ToolContext,
+    ) -> McpResult<Value> {
+        debug!(tool_name = %tool_name, session = self.session.id(), "Executing tool");
+        self.session.record_tool_call();
+
+        let ctx = ctx
+            .with_config(self.config.clone())
+            .with_session(self.session.clone())
+            .with_span(tracing::debug_span!(
+                "tool_call",
+                tool = %tool_name,
+                session = self.session.id()
+            ))
+            .with_timeout(self.config.tool_timeout(tool_name));
+
+        execute_with_context(tool_name, arguments, &ctx).await
+    }
+
+    /// Typed JSON in `structuredContent`, with the same JSON as a text block
+    /// for clients that predate structured output, formatted per the
+    /// `output` settings
+    fn tool_result(&self, mut result: Value) -> CallToolResult {
+        apply_output_settings(&mut result, &self.config);
+        let text = if self.config.pretty_output {
+            serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string())
+        } else {
+            result.to_string()
+        };
+        CallToolResult {
+            content: vec![Content::text(text)],
+            structured_content: Some(result),
+            is_error: Some(false),
+            meta: None,
+        }
+    }
+
+    /// Rate limit tokens a call costs: the tool's configured cost, or for
+    /// `batch_operations` the total cost of its operations
+    fn call_cost(&self, tool_name: &str, arguments: &Value) -> f64 {
+        if tool_name != TOOL_BATCH {
+            return self.config.tool_cost(tool_name);
+        }
+        let operations = arguments.get("operations").and_then(Value::as_array);
+        let cost: f64 = operations
+            .into_iter()
+            .flatten()
+            .filter_map(|operation| operation.get("tool").and_then(Value::as_str))
+            .map(|tool| self.config.tool_cost(tool))
+            .sum();
+        cost.max(1.0)
+    }
+}
+
+/// Run a tool on the blocking pool, giving up once `ctx` is cancelled or times out.
+///
+/// Tools are synchronous, so the task is told to stop through `ctx` and checks
+/// it between steps; the response does not wait for it to notice.
+async fn execute_with_context(
+    tool_name: &str,
+    arguments: Value,
+    ctx: &ToolContext,
+) -> McpResult<Value> {
+    let task = tokio::task::spawn_blocking({
+        let tool_name = tool_name.to_string();
+        let ctx = ctx.clone();
+        move || {
+            let _entered = ctx.span().enter();
+            DefaultToolRegistry.execute_tool_with_context(&tool_name, &arguments, &ctx)
+        }
+    });
+
+    tokio::select! {
+        biased;
+        joined = task => joined
+            .map_err(|e| McpError::internal_error(format!("Tool execution failed: {}", e)))?,
+        _ = ctx.done() => {
+            let err = ctx
+                .check()
+                .err()
+                .unwrap_or_else(|| McpError::resource_limit("Operation cancelled"));
+            ctx.cancel();
+            Err(err)
+        }
+    }
+}
+
+impl MathService {
+    /// List the static resources (constants, formula references, tool docs)
+    /// for the tools this service offers
+    pub fn _list_resources(&self) -> Vec<Resource> {
+        resources::list(&self.config)
+            .into_iter()
+            .map(resource_from_info)
+            .collect()
+    }
+
+    /// List the resource URI templates
+    pub fn _list_resource_templates(&self) -> Vec<ResourceTemplate> {
+        resources::templates()
+            .into_iter()
+            .map(|info| {
+                RawResourceTemplate {
+                    uri_template: info.uri,
+                    name: info.name,
+                    title: None,
+                    description: Some(info.description),
+                    mime_type: Some(resources::MIME_TYPE.to_string()),
+                }
+                .no_annotation()
+            })
+            .collect()
+    }
+
+    /// Read a resource as JSON text
+    pub fn _read_resource(&self, uri: &str) -> Result<ReadResourceResult, ErrorData> {
+        let value = resources::read(uri, &self.config)?;
+        let text = serde_json::to_string_pretty(&value).map_err(McpError::from)?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(resources::MIME_TYPE.to_string()),
+                text,
+                meta: None,
+            }],
         })
     }
 }
 
+impl MathService {
+    /// List the prompt templates
+    pub fn _list_prompts(&self) -> Vec<Prompt> {
+        prompts::PROMPTS
+            .iter()
+            .map(|prompt| {
+                let arguments = prompt
+                    .arguments
+                    .iter()
+                    .map(|argument| PromptArgument {
+                        name: argument.name.to_string(),
+                        title: None,
+                        description: Some(argument.description.to_string()),
+                        required: Some(argument.required),
+                    })
+                    .collect();
+                Prompt::new(prompt.name, Some(prompt.description), Some(arguments))
+            })
+            .collect()
+    }
+
+    /// Expand a prompt template with the given arguments
+    pub fn _get_prompt(
+        &self,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let messages = prompts::get(name, &arguments.unwrap_or_default())?
+            .into_iter()
+            .map(|message| {
+                let role = match message.role {
+                    Role::User => PromptMessageRole::User,
+                    Role::Assistant => PromptMessageRole::Assistant,
+                };
+                PromptMessage::new_text(role, message.text)
+            })
+            .collect();
+
+        Ok(GetPromptResult {
+            description: prompts::prompt(name).map(|prompt| prompt.description.to_string()),
+            messages,
+        })
+    }
+}
+
+/// Forward a tool's progress to the client as `notifications/progress`.
+///
+/// Tools run on the blocking pool, so each update is sent before the tool
+/// continues; updates arrive in order and ahead of the call's result.
+fn progress_notifier(
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+) -> impl Fn(Progress) + Send + Sync + 'static {
+    let runtime = tokio::runtime::Handle::current();
+    move |progress| {
+        let notification = ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: progress.progress,
+            total: progress.total,
+            message: progress.message,
+        };
+        if let Err(e) = runtime.block_on(peer.notify_progress(notification)) {
+            debug!(error = %e, "Failed to send progress notification");
+        }
+    }
+}
+
+/// Send `notifications/tools/list_changed` to the client whenever tools are
+/// registered or unregistered at runtime, until its session closes
+async fn forward_tool_list_changes(peer: Peer<RoleServer>) {
+    let mut changes = DefaultToolRegistry.subscribe();
+    while changes.changed().await.is_ok() {
+        if peer.is_transport_closed() {
+            break;
+        }
+        if let Err(e) = peer.notify_tool_list_changed().await {
+            debug!(error = %e, "Failed to send tool list change notification");
+            break;
+        }
+    }
+}
+
+/// Convert a resource description into an rmcp `Resource`
+fn resource_from_info(info: ResourceInfo) -> Resource {
+    let mut resource = RawResource::new(info.uri, info.name);
+    resource.description = Some(info.description);
+    resource.mime_type = Some(resources::MIME_TYPE.to_string());
+    resource.no_annotation()
+}
+
+/// Convert a registry tool definition (`name`, `description`, `inputSchema`,
+/// `outputSchema`) into an rmcp `Tool`
+fn tool_from_definition(definition: &Value) -> Option<Tool> {
+    let name = definition.get("name")?.as_str()?.to_string();
+    let description = definition
+        .get("description")
+        .and_then(|d| d.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let input_schema: JsonObject = definition.get("inputSchema")?.as_object()?.clone();
+    let output_schema = definition
+        .get("outputSchema")
+        .and_then(|schema| schema.as_object())
+        .map(|schema| Arc::new(schema.clone()));
+
+    let mut tool = Tool::new(name, description, Arc::new(input_schema));
+    tool.output_schema = output_schema;
+    Some(tool)
+}
+
 /// Implement ServerHandler for rmcp integration
 impl ServerHandler for MathService {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
             server_info: Implementation {
-                name: "rust-math-mcp".to_string(),
-                version: env!("CARGO_PKG_VERSION").to_string(),
+                name: self.config.server_name().to_string(),
+                version: self.config.server_version().to_string(),
                 title: Some("Rust Math MCP Server".to_string()),
                 website_url: Some("https://github.com/justnorawr/RustMath".to_string()),
                 icons: Some(vec![]),
@@ -61,6 +411,27 @@ impl ServerHandler for MathService {
         }
     }
 
+    /// Answer in the protocol version the client asked for, like
+    /// [`crate::protocol::handle_initialize`]
+    async fn initialize(
+        &self,
+        request: ClientInfo,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ServerInfo, ErrorData> {
+        let protocol_version = request.protocol_version.clone();
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
+        }
+        Ok(ServerInfo {
+            protocol_version,
+            ..self.get_info()
+        })
+    }
+
+    async fn on_initialized(&self, ctx: NotificationContext<RoleServer>) {
+        tokio::spawn(forward_tool_list_changes(ctx.peer));
+    }
+
     async fn list_tools(
         &self,
         _params: Option<PaginatedRequestParam>,
@@ -75,8 +446,63 @@ impl ServerHandler for MathService {
     async fn call_tool(
         &self,
         params: CallToolRequestParam,
-        _ctx: RequestContext<RoleServer>,
+        ctx: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, ErrorData> {
-        self._call_tool(&params.name, params.arguments).await
+        // A child token, so hitting the deadline never cancels the request itself
+        let mut tool_ctx = ToolContext::new().with_cancellation(ctx.ct.child_token());
+        if let Some(token) = ctx.meta.get_progress_token() {
+            tool_ctx = tool_ctx.with_progress(progress_notifier(ctx.peer.clone(), token));
+        }
+        self._call_tool_with_context(&params.name, params.arguments, tool_ctx)
+            .await
+    }
+
+    async fn list_resources(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        Ok(ListResourcesResult {
+            resources: self._list_resources(),
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: self._list_resource_templates(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        params: ReadResourceRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self._read_resource(&params.uri)
+    }
+
+    async fn list_prompts(
+        &self,
+        _params: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult {
+            prompts: self._list_prompts(),
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        params: GetPromptRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        self._get_prompt(&params.name, params.arguments)
     }
 }
@@ -0,0 +1,139 @@
+use crate::config::Config;
+use crate::error::{McpError, McpResult};
+use crate::utils::rate_limiter::RateLimiter;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Source of unique session ids within this process
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Per-client state.
+///
+/// Every connected client (the stdio peer, an HTTP session, or one TCP/Unix
+/// socket connection) gets its own `Session`, so one noisy client cannot use
+/// up another's rate limit. Tools with their own limit in
+/// [`Config::tool_rate_limits`] also get a per-session bucket each. The tool
+/// registry itself is static and shared.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::session::Session;
+///
+/// let first = Session::new(&Config::new());
+/// let second = Session::new(&Config::new());
+/// assert_ne!(first.id(), second.id());
+/// ```
+pub struct Session {
+    id: u64,
+    started_at: Instant,
+    rate_limit_enabled: bool,
+    rate_limiter: RateLimiter,
+    tool_rate_limiters: HashMap<String, RateLimiter>,
+    tool_calls: AtomicU64,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("id", &self.id)
+            .field("uptime", &self.uptime())
+            .field("tool_calls", &self.tool_calls())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    /// Create a session with a fresh rate limiter sized from `config`
+    pub fn new(config: &Config) -> Self {
+        Self {
+            id: NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed),
+            started_at: Instant::now(),
+            rate_limit_enabled: config.enable_rate_limit,
+            rate_limiter: RateLimiter::new(config.max_requests_per_second, Duration::from_secs(1)),
+            tool_rate_limiters: config
+                .tool_rate_limits
+                .iter()
+                .map(|(tool, &limit)| {
+                    (
+                        tool.clone(),
+                        RateLimiter::new(limit, Duration::from_secs(1)),
+                    )
+                })
+                .collect(),
+            tool_calls: AtomicU64::new(0),
+        }
+    }
+
+    /// Process-unique session id
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Time since the session was created
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// This session's rate limiter
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// Charge `cost` tokens for a call to `tool` against the tool's own bucket
+    /// (if it has one) and the session's, taking them from both or neither.
+    ///
+    /// Fails with a `resource_limit` error whose `data` holds `retry_after_ms`.
+    /// Always succeeds when rate limiting is disabled.
+    pub fn check_rate_limit(&self, tool: &str, cost: f64) -> McpResult<()> {
+        if !self.rate_limit_enabled {
+            return Ok(());
+        }
+        let Some(tool_limiter) = self.tool_rate_limiters.get(tool) else {
+            return self.rate_limiter.try_acquire(cost).map_err(|retry_after| {
+                McpError::rate_limited("Rate limit exceeded for this session", retry_after)
+            });
+        };
+        RateLimiter::try_acquire_all(&[tool_limiter, &self.rate_limiter], cost).map_err(
+            |(index, retry_after)| match index {
+                0 => Self::tool_limit_error(tool, retry_after),
+                _ => McpError::rate_limited("Rate limit exceeded for this session", retry_after),
+            },
+        )
+    }
+
+    /// Charge `cost` tokens against `tool`'s own bucket only, for a call made
+    /// from inside another tool (a batch operation, a composite step) whose
+    /// session tokens were taken when the outer call started.
+    ///
+    /// Always succeeds for tools without their own limit, or when rate
+    /// limiting is disabled.
+    pub fn check_tool_rate_limit(&self, tool: &str, cost: f64) -> McpResult<()> {
+        match self.tool_rate_limiters.get(tool) {
+            Some(limiter) if self.rate_limit_enabled => limiter
+                .try_acquire(cost)
+                .map_err(|retry_after| Self::tool_limit_error(tool, retry_after)),
+            _ => Ok(()),
+        }
+    }
+
+    fn tool_limit_error(tool: &str, retry_after: Duration) -> McpError {
+        McpError::rate_limited(
+            format!("Rate limit exceeded for tool '{}'", tool),
+            retry_after,
+        )
+    }
+
+    /// Number of tool calls this session has made
+    pub fn tool_calls(&self) -> u64 {
+        self.tool_calls.load(Ordering::Relaxed)
+    }
+
+    /// Record a tool call
+    pub(crate) fn record_tool_call(&self) {
+        self.tool_calls.fetch_add(1, Ordering::Relaxed);
+    }
+}
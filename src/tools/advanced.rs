@@ -1,92 +1,100 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_bool_opt, get_number, get_number_opt, result_json};
-use serde_json::Value;
+use crate::math::advanced as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExponentialGrowthInput {
+    /// Initial value (P)
+    pub initial: f64,
+    /// Growth rate (as decimal)
+    pub rate: f64,
+    /// Time period
+    pub time: f64,
+    /// If true, use continuous compounding (e^rt), otherwise discrete (1+r)^t
+    pub continuous: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LogarithmInput {
+    /// Value to take logarithm of
+    pub value: f64,
+    /// Base of logarithm (default: 10 for common log)
+    pub base: Option<f64>,
+    /// If true, use natural logarithm (ln)
+    pub natural: Option<bool>,
+}
+
+pub struct ExponentialGrowth;
+pub struct Logarithm;
+
+impl MathTool for ExponentialGrowth {
+    const NAME: &'static str = "exponential_growth";
+    const DESCRIPTION: &'static str =
+        "Calculate exponential growth: A = P × e^(rt) or A = P × (1 + r)^t";
+    const FORMULA: &'static str = "A = P(1 + r)^t, or A = Pe^(rt) when continuous";
+    type Input = ExponentialGrowthInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "initial": 100, "rate": 0.1, "time": 5, "continuous": true })
+    }
+
+    fn run(input: ExponentialGrowthInput) -> McpResult<NumberResult> {
+        let grow = if input.continuous.unwrap_or(false) {
+            math::continuous_growth
+        } else {
+            math::exponential_growth
+        };
+        Ok(grow(input.initial, input.rate, input.time).into())
+    }
+}
+
+impl MathTool for Logarithm {
+    const NAME: &'static str = "logarithm";
+    const DESCRIPTION: &'static str = "Calculate logarithm: log_base(value) or natural log";
+    const FORMULA: &'static str = "log_b(x) = ln(x) / ln(b); base 10 by default";
+    type Input = LogarithmInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "value": 1000, "base": 10 })
+    }
+
+    fn run(input: LogarithmInput) -> McpResult<NumberResult> {
+        if input.natural.unwrap_or(false) {
+            math::ln(input.value)
+        } else if let Some(base) = input.base {
+            math::logarithm(input.value, base)
+        } else {
+            math::log10(input.value)
+        }
+        .map(NumberResult::from)
+    }
+}
 
 pub fn get_tool_definitions() -> Vec<Value> {
+    vec![ExponentialGrowth::definition(), Logarithm::definition()]
+}
+
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
     vec![
-        serde_json::json!({
-            "name": "exponential_growth",
-            "description": "Calculate exponential growth: A = P × e^(rt) or A = P × (1 + r)^t",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "initial": {"type": "number", "description": "Initial value (P)"},
-                    "rate": {"type": "number", "description": "Growth rate (as decimal)"},
-                    "time": {"type": "number", "description": "Time period"},
-                    "continuous": {"type": "boolean", "description": "If true, use continuous compounding (e^rt), otherwise discrete (1+r)^t"}
-                },
-                "required": ["initial", "rate", "time"]
-            }
-        }),
-        serde_json::json!({
-            "name": "logarithm",
-            "description": "Calculate logarithm: log_base(value) or natural log",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "value": {"type": "number", "description": "Value to take logarithm of"},
-                    "base": {"type": "number", "description": "Base of logarithm (default: e for natural log, 10 for common log)"},
-                    "natural": {"type": "boolean", "description": "If true, use natural logarithm (ln)"}
-                },
-                "required": ["value"]
-            }
-        }),
+        ExponentialGrowth::documentation(),
+        Logarithm::documentation(),
     ]
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
     match name {
-        "exponential_growth" => {
-            let initial = get_number(arguments, "initial")?;
-            let rate = get_number(arguments, "rate")?;
-            let time = get_number(arguments, "time")?;
-            let continuous = get_bool_opt(arguments, "continuous");
-            Ok(result_json(exponential_growth(
-                initial, rate, time, continuous,
-            )?))
-        }
-        "logarithm" => {
-            let value = get_number(arguments, "value")?;
-            let base = get_number_opt(arguments, "base");
-            let natural = get_bool_opt(arguments, "natural");
-            Ok(result_json(logarithm(value, base, natural)?))
-        }
+        ExponentialGrowth::NAME => ExponentialGrowth::call_with_context(arguments, ctx),
+        Logarithm::NAME => Logarithm::call_with_context(arguments, ctx),
         _ => Err(crate::error::McpError::tool_error(format!(
             "Unknown advanced tool: {}",
             name
         ))),
     }
 }
-
-fn exponential_growth(
-    initial: f64,
-    rate: f64,
-    time: f64,
-    continuous: Option<bool>,
-) -> McpResult<f64> {
-    if continuous.unwrap_or(false) {
-        Ok(initial * (rate * time).exp())
-    } else {
-        Ok(initial * (1.0 + rate).powf(time))
-    }
-}
-
-fn logarithm(value: f64, base: Option<f64>, natural: Option<bool>) -> McpResult<f64> {
-    if value <= 0.0 {
-        return Err(crate::error::McpError::validation_error(
-            "Logarithm is undefined for non-positive values",
-        ));
-    }
-    if natural.unwrap_or(false) {
-        Ok(value.ln())
-    } else if let Some(b) = base {
-        if b <= 0.0 || b == 1.0 {
-            return Err(crate::error::McpError::validation_error(
-                "Invalid base for logarithm",
-            ));
-        }
-        Ok(value.log(b))
-    } else {
-        Ok(value.log10())
-    }
-}
@@ -1,130 +1,113 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, result_json};
-use serde_json::Value;
-
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "gcd",
-            "description": "Calculate the greatest common divisor of two numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "First number"},
-                    "b": {"type": "number", "description": "Second number"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": "lcm",
-            "description": "Calculate the least common multiple of two numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "First number"},
-                    "b": {"type": "number", "description": "Second number"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": "factorial",
-            "description": "Calculate the factorial of a non-negative integer",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "n": {"type": "number", "description": "Non-negative integer"}
-                },
-                "required": ["n"]
-            }
-        }),
-    ]
+use crate::math::algebra as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use crate::utils::validation::validate_integer;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Arguments for tools operating on a pair of integers (`gcd`, `lcm`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PairInput {
+    /// First number
+    pub a: f64,
+    /// Second number
+    pub b: f64,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "gcd" => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            Ok(result_json(gcd(a, b)?))
-        }
-        "lcm" => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            Ok(result_json(lcm(a, b)?))
-        }
-        "factorial" => {
-            let n = get_number(arguments, "n")?;
-            Ok(result_json(factorial(n)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown algebra tool: {}",
-            name
-        ))),
-    }
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FactorialInput {
+    /// Non-negative integer
+    pub n: f64,
 }
 
-fn gcd(a: f64, b: f64) -> McpResult<f64> {
-    let mut a = a.abs() as i64;
-    let mut b = b.abs() as i64;
-    while b != 0 {
-        let temp = b;
-        b = a % b;
-        a = temp;
-    }
-    Ok(a as f64)
-}
+pub struct Gcd;
+pub struct Lcm;
+pub struct Factorial;
 
-fn lcm(a: f64, b: f64) -> McpResult<f64> {
-    use crate::utils::validation::validate_integer;
+impl MathTool for Gcd {
+    const NAME: &'static str = "gcd";
+    const DESCRIPTION: &'static str = "Calculate the greatest common divisor of two numbers";
+    const FORMULA: &'static str = "gcd(a, b) by the Euclidean algorithm";
+    type Input = PairInput;
+    type Output = NumberResult;
 
-    let a_int = validate_integer(a, "a")?;
-    let b_int = validate_integer(b, "b")?;
-
-    let a_abs = a_int.abs();
-    let b_abs = b_int.abs();
+    fn example() -> Value {
+        json!({ "a": 48, "b": 18 })
+    }
 
-    if a_abs == 0 || b_abs == 0 {
-        return Ok(0.0);
+    fn run(input: PairInput) -> McpResult<NumberResult> {
+        // Unlike lcm, gcd truncates fractional arguments instead of rejecting them
+        Ok((math::gcd(input.a.abs() as i64, input.b.abs() as i64) as f64).into())
     }
+}
 
-    // Calculate GCD first
-    let gcd_val = gcd(a, b)? as i64;
+impl MathTool for Lcm {
+    const NAME: &'static str = "lcm";
+    const DESCRIPTION: &'static str = "Calculate the least common multiple of two numbers";
+    const FORMULA: &'static str = "lcm(a, b) = |a × b| / gcd(a, b)";
+    type Input = PairInput;
+    type Output = NumberResult;
 
-    // Use checked arithmetic to prevent overflow: lcm(a,b) = (a / gcd) * b
-    let result = (a_abs / gcd_val).checked_mul(b_abs).ok_or_else(|| {
-        crate::error::McpError::validation_error("LCM calculation would overflow")
-    })?;
+    fn example() -> Value {
+        json!({ "a": 4, "b": 6 })
+    }
 
-    Ok(result as f64)
+    fn run(input: PairInput) -> McpResult<NumberResult> {
+        let a = validate_integer(input.a, "a")?;
+        let b = validate_integer(input.b, "b")?;
+        Ok((math::lcm(a, b)? as f64).into())
+    }
 }
 
-fn factorial(n: f64) -> McpResult<f64> {
-    use crate::utils::validation::validate_integer;
-
-    let n_int = validate_integer(n, "n")?;
+impl MathTool for Factorial {
+    const NAME: &'static str = "factorial";
+    const DESCRIPTION: &'static str = "Calculate the factorial of a non-negative integer";
+    const FORMULA: &'static str = "n! = n × (n - 1) × … × 1, 0! = 1";
+    type Input = FactorialInput;
+    type Output = NumberResult;
 
-    if n_int < 0 {
-        return Err(crate::error::McpError::validation_error(
-            "Factorial is not defined for negative numbers",
-        ));
+    fn example() -> Value {
+        json!({ "n": 5 })
     }
 
-    // Limit factorial to prevent overflow (20! < u64::MAX, but 21! overflows)
-    if n_int > 170 {
-        return Err(crate::error::McpError::validation_error(
-            "Factorial overflow: n must be <= 170 to prevent overflow",
-        ));
+    fn run(input: FactorialInput) -> McpResult<NumberResult> {
+        let n = validate_integer(input.n, "n")?;
+        if n < 0 {
+            return Err(crate::error::McpError::validation_error(
+                "Factorial is not defined for negative numbers",
+            ));
+        }
+        Ok((math::factorial(n as u64)? as f64).into())
     }
+}
 
-    // Use checked multiplication to detect overflow
-    let mut result = 1u64;
-    for i in 1..=n_int as u64 {
-        result = result.checked_mul(i).ok_or_else(|| {
-            crate::error::McpError::validation_error(format!("Factorial overflow at i={}", i))
-        })?;
-    }
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        Gcd::definition(),
+        Lcm::definition(),
+        Factorial::definition(),
+    ]
+}
 
-    Ok(result as f64)
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        Gcd::documentation(),
+        Lcm::documentation(),
+        Factorial::documentation(),
+    ]
+}
+
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        Gcd::NAME => Gcd::call_with_context(arguments, ctx),
+        Lcm::NAME => Lcm::call_with_context(arguments, ctx),
+        Factorial::NAME => Factorial::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown algebra tool: {}",
+            name
+        ))),
+    }
 }
@@ -1,6 +1,13 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, get_number_array, get_number_opt, result_json};
-use serde_json::Value;
+use crate::math::basic_math as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use crate::utils::args::check_number_array;
+use crate::utils::limits::Limits;
+use crate::utils::validation::within;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
 
 // Tool name constants
 pub const TOOL_ADD: &str = "add";
@@ -15,257 +22,319 @@ pub const TOOL_FLOOR: &str = "floor";
 pub const TOOL_CEIL: &str = "ceil";
 pub const TOOL_MODULO: &str = "modulo";
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": TOOL_ADD,
-            "description": "Add two or more numbers together",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers to add"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_SUBTRACT,
-            "description": "Subtract numbers. Subtracts all subsequent numbers from the first.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "First number"},
-                    "b": {"type": "number", "description": "Number to subtract"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_MULTIPLY,
-            "description": "Multiply two or more numbers together",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers to multiply"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_DIVIDE,
-            "description": "Divide two numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "Dividend"},
-                    "b": {"type": "number", "description": "Divisor"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_POWER,
-            "description": "Raise a number to a power",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "base": {"type": "number", "description": "Base number"},
-                    "exponent": {"type": "number", "description": "Exponent"}
-                },
-                "required": ["base", "exponent"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_SQRT,
-            "description": "Calculate the square root of a number",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "number": {"type": "number", "description": "Number to take square root of"}
-                },
-                "required": ["number"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_ABS,
-            "description": "Get the absolute value of a number",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "number": {"type": "number", "description": "Number"}
-                },
-                "required": ["number"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_ROUND,
-            "description": "Round a number to the nearest integer",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "number": {"type": "number", "description": "Number to round"},
-                    "decimals": {"type": "number", "description": "Number of decimal places (default: 0)"}
-                },
-                "required": ["number"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_FLOOR,
-            "description": "Round down to the nearest integer",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "number": {"type": "number", "description": "Number"}
-                },
-                "required": ["number"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_CEIL,
-            "description": "Round up to the nearest integer",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "number": {"type": "number", "description": "Number"}
-                },
-                "required": ["number"]
-            }
-        }),
-        serde_json::json!({
-            "name": TOOL_MODULO,
-            "description": "Calculate the remainder of division",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "Dividend"},
-                    "b": {"type": "number", "description": "Divisor"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-    ]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AddInput {
+    /// Array of numbers to add
+    pub numbers: Vec<f64>,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        TOOL_ADD => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(add(numbers)?))
-        }
-        TOOL_SUBTRACT => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            Ok(result_json(subtract(a, b)?))
-        }
-        TOOL_MULTIPLY => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(multiply(numbers)?))
-        }
-        TOOL_DIVIDE => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            Ok(result_json(divide(a, b)?))
-        }
-        TOOL_POWER => {
-            let base = get_number(arguments, "base")?;
-            let exponent = get_number(arguments, "exponent")?;
-            Ok(result_json(power(base, exponent)?))
-        }
-        TOOL_SQRT => {
-            let number = get_number(arguments, "number")?;
-            Ok(result_json(sqrt(number)?))
-        }
-        TOOL_ABS => {
-            let number = get_number(arguments, "number")?;
-            Ok(result_json(abs(number)?))
-        }
-        TOOL_ROUND => {
-            let number = get_number(arguments, "number")?;
-            let decimals = get_number_opt(arguments, "decimals");
-            Ok(result_json(round(number, decimals)?))
-        }
-        TOOL_FLOOR => {
-            let number = get_number(arguments, "number")?;
-            Ok(result_json(floor(number)?))
-        }
-        TOOL_CEIL => {
-            let number = get_number(arguments, "number")?;
-            Ok(result_json(ceil(number)?))
-        }
-        TOOL_MODULO => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            Ok(result_json(modulo(a, b)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown basic math tool: {}",
-            name
-        ))),
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SubtractInput {
+    /// First number
+    pub a: f64,
+    /// Number to subtract
+    pub b: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MultiplyInput {
+    /// Array of numbers to multiply
+    pub numbers: Vec<f64>,
+}
+
+/// Arguments for division-like tools (`divide`, `modulo`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DivisionInput {
+    /// Dividend
+    pub a: f64,
+    /// Divisor
+    pub b: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PowerInput {
+    /// Base number
+    pub base: f64,
+    /// Exponent
+    pub exponent: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SqrtInput {
+    /// Number to take square root of
+    pub number: f64,
+}
+
+/// Arguments for single-number tools (`abs`, `floor`, `ceil`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NumberInput {
+    /// Number
+    pub number: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RoundInput {
+    /// Number to round
+    pub number: f64,
+    /// Number of decimal places (default: 0)
+    pub decimals: Option<f64>,
+}
+
+pub struct Add;
+pub struct Subtract;
+pub struct Multiply;
+pub struct Divide;
+pub struct Power;
+pub struct Sqrt;
+pub struct Abs;
+pub struct Round;
+pub struct Floor;
+pub struct Ceil;
+pub struct Modulo;
+
+impl MathTool for Add {
+    const NAME: &'static str = TOOL_ADD;
+    const DESCRIPTION: &'static str = "Add two or more numbers together";
+    const FORMULA: &'static str = "sum = x₁ + x₂ + … + xₙ";
+    type Input = AddInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [2, 3, 5] })
+    }
+
+    fn validate(input: &AddInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: AddInput) -> McpResult<NumberResult> {
+        Ok(math::add(&input.numbers).into())
+    }
+}
+
+impl MathTool for Subtract {
+    const NAME: &'static str = TOOL_SUBTRACT;
+    const DESCRIPTION: &'static str = "Subtract two numbers: a - b";
+    const FORMULA: &'static str = "a - b";
+    type Input = SubtractInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "a": 10, "b": 4 })
+    }
+
+    fn run(input: SubtractInput) -> McpResult<NumberResult> {
+        Ok(math::subtract(input.a, input.b).into())
+    }
+}
+
+impl MathTool for Multiply {
+    const NAME: &'static str = TOOL_MULTIPLY;
+    const DESCRIPTION: &'static str = "Multiply two or more numbers together";
+    const FORMULA: &'static str = "product = x₁ × x₂ × … × xₙ";
+    type Input = MultiplyInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [2, 3, 4] })
+    }
+
+    fn validate(input: &MultiplyInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: MultiplyInput) -> McpResult<NumberResult> {
+        Ok(math::multiply(&input.numbers).into())
     }
 }
 
-// Implementation functions
-fn add(numbers: Vec<f64>) -> McpResult<f64> {
-    Ok(numbers.iter().sum())
+impl MathTool for Divide {
+    const NAME: &'static str = TOOL_DIVIDE;
+    const DESCRIPTION: &'static str = "Divide two numbers";
+    const FORMULA: &'static str = "a / b, b ≠ 0";
+    type Input = DivisionInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "a": 22, "b": 7 })
+    }
+
+    fn run(input: DivisionInput) -> McpResult<NumberResult> {
+        math::divide(input.a, input.b).map(NumberResult::from)
+    }
 }
 
-fn subtract(a: f64, b: f64) -> McpResult<f64> {
-    Ok(a - b)
+impl MathTool for Power {
+    const NAME: &'static str = TOOL_POWER;
+    const DESCRIPTION: &'static str = "Raise a number to a power";
+    const FORMULA: &'static str = "base^exponent";
+    type Input = PowerInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "base": 2, "exponent": 10 })
+    }
+
+    fn run(input: PowerInput) -> McpResult<NumberResult> {
+        Ok(math::power(input.base, input.exponent).into())
+    }
 }
 
-fn multiply(numbers: Vec<f64>) -> McpResult<f64> {
-    Ok(numbers.iter().product())
+impl MathTool for Sqrt {
+    const NAME: &'static str = TOOL_SQRT;
+    const DESCRIPTION: &'static str = "Calculate the square root of a number";
+    const FORMULA: &'static str = "√x, x ≥ 0";
+    type Input = SqrtInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "number": 144 })
+    }
+
+    fn run(input: SqrtInput) -> McpResult<NumberResult> {
+        math::sqrt(input.number).map(NumberResult::from)
+    }
 }
 
-fn divide(a: f64, b: f64) -> McpResult<f64> {
-    if b == 0.0 {
-        return Err(crate::error::McpError::validation_error("Division by zero"));
+impl MathTool for Abs {
+    const NAME: &'static str = TOOL_ABS;
+    const DESCRIPTION: &'static str = "Get the absolute value of a number";
+    const FORMULA: &'static str = "|x|";
+    type Input = NumberInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "number": -7.5 })
+    }
+
+    fn run(input: NumberInput) -> McpResult<NumberResult> {
+        Ok(math::abs(input.number).into())
     }
-    Ok(a / b)
 }
 
-fn power(base: f64, exponent: f64) -> McpResult<f64> {
-    Ok(base.powf(exponent))
+impl MathTool for Round {
+    const NAME: &'static str = TOOL_ROUND;
+    const DESCRIPTION: &'static str = "Round a number to the nearest integer";
+    const FORMULA: &'static str = "round(x × 10^d) / 10^d";
+    type Input = RoundInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "number": 12.3456, "decimals": 2 })
+    }
+
+    fn validate(input: &RoundInput, limits: &Limits) -> McpResult<()> {
+        match input.decimals {
+            Some(decimals) => limits
+                .check_decimal_places(decimals as i32)
+                .map_err(|e| within(e, "decimals")),
+            None => Ok(()),
+        }
+    }
+
+    fn run(input: RoundInput) -> McpResult<NumberResult> {
+        let decimals = input.decimals.unwrap_or(0.0) as i32;
+        Ok(math::round(input.number, decimals).into())
+    }
 }
 
-fn sqrt(number: f64) -> McpResult<f64> {
-    if number < 0.0 {
-        return Err(crate::error::McpError::validation_error(
-            "Cannot take square root of negative number",
-        ));
+impl MathTool for Floor {
+    const NAME: &'static str = TOOL_FLOOR;
+    const DESCRIPTION: &'static str = "Round down to the nearest integer";
+    const FORMULA: &'static str = "⌊x⌋";
+    type Input = NumberInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "number": 4.7 })
+    }
+
+    fn run(input: NumberInput) -> McpResult<NumberResult> {
+        Ok(math::floor(input.number).into())
     }
-    Ok(number.sqrt())
 }
 
-fn abs(number: f64) -> McpResult<f64> {
-    Ok(number.abs())
+impl MathTool for Ceil {
+    const NAME: &'static str = TOOL_CEIL;
+    const DESCRIPTION: &'static str = "Round up to the nearest integer";
+    const FORMULA: &'static str = "⌈x⌉";
+    type Input = NumberInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "number": 4.2 })
+    }
+
+    fn run(input: NumberInput) -> McpResult<NumberResult> {
+        Ok(math::ceil(input.number).into())
+    }
 }
 
-fn round(number: f64, decimals: Option<f64>) -> McpResult<f64> {
-    let places = decimals.unwrap_or(0.0) as i32;
-    let multiplier = 10.0_f64.powi(places);
-    Ok((number * multiplier).round() / multiplier)
+impl MathTool for Modulo {
+    const NAME: &'static str = TOOL_MODULO;
+    const DESCRIPTION: &'static str = "Calculate the remainder of division";
+    const FORMULA: &'static str = "a mod b (remainder has the sign of a)";
+    type Input = DivisionInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "a": 17, "b": 5 })
+    }
+
+    fn run(input: DivisionInput) -> McpResult<NumberResult> {
+        math::modulo(input.a, input.b).map(NumberResult::from)
+    }
 }
 
-fn floor(number: f64) -> McpResult<f64> {
-    Ok(number.floor())
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        Add::definition(),
+        Subtract::definition(),
+        Multiply::definition(),
+        Divide::definition(),
+        Power::definition(),
+        Sqrt::definition(),
+        Abs::definition(),
+        Round::definition(),
+        Floor::definition(),
+        Ceil::definition(),
+        Modulo::definition(),
+    ]
 }
 
-fn ceil(number: f64) -> McpResult<f64> {
-    Ok(number.ceil())
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        Add::documentation(),
+        Subtract::documentation(),
+        Multiply::documentation(),
+        Divide::documentation(),
+        Power::documentation(),
+        Sqrt::documentation(),
+        Abs::documentation(),
+        Round::documentation(),
+        Floor::documentation(),
+        Ceil::documentation(),
+        Modulo::documentation(),
+    ]
 }
 
-fn modulo(a: f64, b: f64) -> McpResult<f64> {
-    if b == 0.0 {
-        return Err(crate::error::McpError::validation_error("Modulo by zero"));
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        TOOL_ADD => Add::call_with_context(arguments, ctx),
+        TOOL_SUBTRACT => Subtract::call_with_context(arguments, ctx),
+        TOOL_MULTIPLY => Multiply::call_with_context(arguments, ctx),
+        TOOL_DIVIDE => Divide::call_with_context(arguments, ctx),
+        TOOL_POWER => Power::call_with_context(arguments, ctx),
+        TOOL_SQRT => Sqrt::call_with_context(arguments, ctx),
+        TOOL_ABS => Abs::call_with_context(arguments, ctx),
+        TOOL_ROUND => Round::call_with_context(arguments, ctx),
+        TOOL_FLOOR => Floor::call_with_context(arguments, ctx),
+        TOOL_CEIL => Ceil::call_with_context(arguments, ctx),
+        TOOL_MODULO => Modulo::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown basic math tool: {}",
+            name
+        ))),
     }
-    Ok(a % b)
 }
@@ -1,24 +1,39 @@
 use crate::error::{McpError, McpResult};
-use crate::tools::registry::DefaultToolRegistry;
+use crate::metrics;
+use crate::tools::context::ToolContext;
+use crate::tools::references;
+use crate::tools::registry::{is_known, DefaultToolRegistry};
+use crate::tools::traits::MathTool;
 use crate::tools::ToolRegistry;
+use crate::utils::limits::Limits;
+use crate::utils::validation::{within, InvalidArgument};
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 pub const TOOL_BATCH: &str = "batch_operations";
 
 /// Represents a single operation in a batch
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchOperation {
-    /// Unique identifier for this operation (used to match results)
+    /// Unique identifier for this operation (e.g., 'op1', 'step1', 'calc_a')
     pub id: String,
-    /// Name of the tool to execute
+    /// Name of the tool to execute (e.g., 'add', 'multiply', 'mean')
     pub tool: String,
-    /// Arguments for the tool
+    /// Arguments to pass to the tool. Any value may instead refer to (a field
+    /// of) another operation's result, as `{"$ref": "op1.result"}` or
+    /// `"${op1.result}"`; the operation then runs after the one it refers to
+    #[schemars(with = "serde_json::Map<String, Value>")]
     pub arguments: Value,
 }
 
 /// Result of a single operation in a batch
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchOperationResult {
     /// ID matching the original operation
     pub id: String,
@@ -30,118 +45,493 @@ pub struct BatchOperationResult {
     /// Error message (if failed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Whether the operation was not run because an earlier one failed
+    /// (`fail_fast` and `all_or_nothing` modes)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+    /// Structured error details (if failed), such as the path of an invalid
+    /// argument, e.g. `operations[3].arguments.numbers[7]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_data: Option<Value>,
+    /// Time the operation took, in milliseconds (absent if it did not run)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+}
+
+impl BatchOperationResult {
+    fn succeeded(id: &str, value: Value, duration: Duration) -> Self {
+        Self {
+            id: id.to_string(),
+            success: true,
+            result: Some(value),
+            error: None,
+            skipped: false,
+            error_data: None,
+            duration_ms: Some(duration.as_secs_f64() * 1000.0),
+        }
+    }
+
+    fn failed(id: &str, error: McpError, duration: Option<Duration>) -> Self {
+        Self {
+            id: id.to_string(),
+            success: false,
+            result: None,
+            error: Some(error.message),
+            skipped: false,
+            error_data: error.data,
+            duration_ms: duration.map(|d| d.as_secs_f64() * 1000.0),
+        }
+    }
+
+    fn skipped(id: &str) -> Self {
+        let error = McpError::tool_error("Skipped after an earlier operation failed");
+        Self {
+            skipped: true,
+            ..Self::failed(id, error, None)
+        }
+    }
+}
+
+/// How a batch reacts to a failed operation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// Run every operation; a failure only affects the operations that refer to it
+    #[default]
+    Independent,
+    /// Start no further operations once one fails; the rest are reported as skipped
+    FailFast,
+    /// Like `fail_fast`, but the whole call fails if any operation fails
+    AllOrNothing,
 }
 
 /// Arguments for batch operations
-#[derive(Debug, Deserialize)]
-struct BatchArgs {
-    operations: Vec<BatchOperation>,
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BatchArgs {
+    /// Array of operations to execute
+    pub operations: Vec<BatchOperation>,
+    /// What to do when an operation fails (default: `independent`)
+    #[serde(default)]
+    pub mode: BatchMode,
+}
+
+/// Success and failure counts for a batch
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub successful: usize,
+    /// Operations that failed, including those whose references failed
+    pub failed: usize,
+    /// Operations not run because an earlier one failed
+    #[serde(default)]
+    pub skipped: usize,
+}
+
+/// Output of the `batch_operations` tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BatchResult {
+    /// One entry per operation, in request order
+    pub results: Vec<BatchOperationResult>,
+    pub summary: BatchSummary,
+}
+
+pub struct BatchOperations;
+
+impl MathTool for BatchOperations {
+    const NAME: &'static str = TOOL_BATCH;
+    const DESCRIPTION: &'static str = "Execute multiple math operations in a single call. Allows the LLM to batch multiple calculations and get all results back together. Each operation has a unique ID to match results. An argument can use an earlier result with {\"$ref\": \"op1.result\"} or \"${op1.result}\" (also inside arrays); operations run after the ones they refer to, and operations that do not depend on each other run in parallel. In the default mode 'independent' a failed operation only affects the operations that refer to it; mode 'fail_fast' skips the remaining operations after a failure, and 'all_or_nothing' fails the whole call. batch_operations cannot be nested.";
+    type Input = BatchArgs;
+    type Output = BatchResult;
+
+    fn example() -> Value {
+        json!({ "operations": [ { "id": "area", "tool": "area_circle", "arguments": { "radius": 2 } }, { "id": "sum", "tool": "add", "arguments": { "numbers": [1, 2, { "$ref": "area.result" }] } } ] })
+    }
+
+    // Running the example would dispatch `area_circle` and `add` through the
+    // registry; `test_example_result` checks this against a real run
+    fn example_result() -> Value {
+        json!({
+            "results": [
+                { "id": "area", "success": true, "result": { "result": 12.566370614359172 } },
+                { "id": "sum", "success": true, "result": { "result": 15.566370614359172 } }
+            ],
+            "summary": { "total": 2, "successful": 2, "failed": 0, "skipped": 0 }
+        })
+    }
+
+    fn validate(input: &BatchArgs, limits: &Limits) -> McpResult<()> {
+        limits.check_batch_size(input.operations.len())
+    }
+
+    fn run(input: BatchArgs) -> McpResult<BatchResult> {
+        run_batch(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: BatchArgs, ctx: &ToolContext) -> McpResult<BatchResult> {
+        run_batch(input, ctx)
+    }
 }
 
 /// Get tool definitions for batch operations
 pub fn get_tool_definitions() -> Vec<Value> {
-    vec![json!({
-        "name": TOOL_BATCH,
-        "description": "Execute multiple math operations in a single call. Allows the LLM to batch multiple calculations and get all results back together. Each operation has a unique ID to match results. Operations are executed independently - if one fails, others still execute.",
-        "inputSchema": {
-            "type": "object",
-            "properties": {
-                "operations": {
-                    "type": "array",
-                    "description": "Array of operations to execute",
-                    "items": {
-                        "type": "object",
-                        "properties": {
-                            "id": {
-                                "type": "string",
-                                "description": "Unique identifier for this operation (e.g., 'op1', 'step1', 'calc_a')"
-                            },
-                            "tool": {
-                                "type": "string",
-                                "description": "Name of the tool to execute (e.g., 'add', 'multiply', 'mean')"
-                            },
-                            "arguments": {
-                                "type": "object",
-                                "description": "Arguments to pass to the tool"
-                            }
-                        },
-                        "required": ["id", "tool", "arguments"]
-                    }
-                }
-            },
-            "required": ["operations"]
-        }
-    })]
+    vec![BatchOperations::definition()]
+}
+
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![BatchOperations::documentation()]
 }
 
 /// Execute batch operations tool
-pub fn execute(_tool_name: &str, args: &Value) -> McpResult<Value> {
-    let batch_args: BatchArgs = serde_json::from_value(args.clone())
-        .map_err(|e| McpError::invalid_params(format!("Invalid batch arguments: {}", e)))?;
+pub fn execute(_tool_name: &str, args: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    BatchOperations::call_with_context(args, ctx)
+}
 
+fn run_batch(batch_args: BatchArgs, ctx: &ToolContext) -> McpResult<BatchResult> {
     if batch_args.operations.is_empty() {
         return Err(McpError::invalid_params("No operations provided"));
     }
 
-    // Limit batch size to prevent abuse
-    const MAX_BATCH_SIZE: usize = 50;
-    if batch_args.operations.len() > MAX_BATCH_SIZE {
-        return Err(McpError::invalid_params(format!(
-            "Batch size exceeds maximum of {} operations",
-            MAX_BATCH_SIZE
-        )));
-    }
-
     // Check for duplicate IDs
-    let mut seen_ids = std::collections::HashSet::new();
-    for op in &batch_args.operations {
+    let mut seen_ids = HashSet::new();
+    for (i, op) in batch_args.operations.iter().enumerate() {
         if !seen_ids.insert(&op.id) {
-            return Err(McpError::invalid_params(format!(
-                "Duplicate operation ID: {}",
-                op.id
-            )));
+            return Err(InvalidArgument::new("an ID not used by another operation")
+                .at(format!("operations[{}].id", i))
+                .received(op.id.as_str())
+                .invalid_params(format!("Duplicate operation ID: {}", op.id)));
         }
     }
 
-    let registry = DefaultToolRegistry;
-    let mut results = Vec::new();
-
-    // Execute each operation independently
-    for operation in batch_args.operations {
-        let result = match registry.execute_tool(&operation.tool, &operation.arguments) {
-            Ok(value) => BatchOperationResult {
-                id: operation.id.clone(),
-                success: true,
-                result: Some(value),
-                error: None,
-            },
-            Err(e) => BatchOperationResult {
-                id: operation.id.clone(),
-                success: false,
-                result: None,
-                error: Some(e.message),
-            },
-        };
-        results.push(result);
+    // A nested batch would multiply the work a single call may do. Caught
+    // here before anything runs; the registry refuses batches reached through
+    // other tools (such as a composite) once the operation calls them
+    if let Some((i, op)) = batch_args
+        .operations
+        .iter()
+        .enumerate()
+        .find(|(_, op)| op.tool == TOOL_BATCH)
+    {
+        return Err(
+            InvalidArgument::new(format!("a tool other than {}", TOOL_BATCH))
+                .at(format!("operations[{}].tool", i))
+                .received(op.tool.as_str())
+                .suggestion("Add the nested operations to this batch instead")
+                .invalid_params(format!(
+                    "Operation '{}': {} cannot be nested",
+                    op.id, TOOL_BATCH
+                )),
+        );
+    }
+
+    let mode = batch_args.mode;
+    let operations = batch_args.operations;
+    let waves = execution_waves(&operations)?;
+    let run = BatchRun {
+        operations: &operations,
+        ctx,
+        // Progress is reported per operation, not from inside each operation
+        op_ctx: ctx.nested().within_batch(),
+        completed: Mutex::new(0),
+        stop: AtomicBool::new(false),
+        stop_on_failure: mode != BatchMode::Independent,
+    };
+
+    let mut results: Vec<Option<BatchOperationResult>> = vec![None; operations.len()];
+    // Results of the successful operations, by id, for references to use
+    let mut scope = Map::new();
+    let mut failed = HashSet::new();
+
+    for wave in waves {
+        // A cancelled or timed-out batch stops as a whole instead of failing
+        // each remaining operation
+        ctx.check()?;
+        for (index, result) in run.wave(&wave, &scope, &failed)? {
+            let id = operations[index].id.as_str();
+            match &result.result {
+                Some(value) => {
+                    scope.insert(id.to_string(), value.clone());
+                }
+                None => {
+                    failed.insert(id);
+                }
+            }
+            results[index] = Some(result);
+        }
+    }
+    let results: Vec<BatchOperationResult> = results.into_iter().flatten().collect();
+
+    if mode == BatchMode::AllOrNothing {
+        if let Some(failure) = results.iter().find(|r| !r.success && !r.skipped) {
+            let mut data = match &failure.error_data {
+                Some(Value::Object(details)) => details.clone(),
+                _ => Map::new(),
+            };
+            data.insert("operation".to_string(), Value::from(failure.id.as_str()));
+            let mut error = McpError::tool_error(format!(
+                "Operation '{}' failed: {}",
+                failure.id,
+                failure.error.as_deref().unwrap_or_default()
+            ));
+            error.data = Some(Value::Object(data));
+            return Err(error);
+        }
     }
 
     // Count successes and failures
     let successful = results.iter().filter(|r| r.success).count();
-    let failed = results.len() - successful;
-
-    Ok(json!({
-        "results": results,
-        "summary": {
-            "total": results.len(),
-            "successful": successful,
-            "failed": failed
+    let skipped = results.iter().filter(|r| r.skipped).count();
+
+    Ok(BatchResult {
+        summary: BatchSummary {
+            total: results.len(),
+            successful,
+            failed: results.len() - successful - skipped,
+            skipped,
+        },
+        results,
+    })
+}
+
+/// State shared by the threads running one batch
+struct BatchRun<'a> {
+    operations: &'a [BatchOperation],
+    ctx: &'a ToolContext,
+    op_ctx: ToolContext,
+    /// Operations finished so far, for progress updates
+    completed: Mutex<usize>,
+    /// Set once an operation fails, if that should stop the batch
+    stop: AtomicBool,
+    stop_on_failure: bool,
+}
+
+impl BatchRun<'_> {
+    /// Run the operations at the indices in `wave` in parallel.
+    ///
+    /// They only refer to operations in earlier waves, whose results are in
+    /// `scope` (or, for failures, in `failed`). Fails only if the batch is
+    /// cancelled or out of time.
+    fn wave(
+        &self,
+        wave: &[usize],
+        scope: &Map<String, Value>,
+        failed: &HashSet<&str>,
+    ) -> McpResult<Vec<(usize, BatchOperationResult)>> {
+        // Rayon's global pool is shared by every batch in the process, so
+        // concurrent batches together use at most one thread per CPU
+        wave.par_iter()
+            .map(|&index| {
+                let _entered = self.ctx.span().enter();
+                Ok((index, self.operation(index, scope, failed)?))
+            })
+            .collect()
+    }
+
+    /// Run one operation, unless the batch has been stopped
+    fn operation(
+        &self,
+        index: usize,
+        scope: &Map<String, Value>,
+        failed: &HashSet<&str>,
+    ) -> McpResult<BatchOperationResult> {
+        let operation = &self.operations[index];
+        self.ctx.check()?;
+        if self.stop.load(Ordering::Relaxed) {
+            record_skipped(&operation.tool, self.ctx);
+            return Ok(BatchOperationResult::skipped(&operation.id));
         }
-    }))
+
+        let result = match failed_dependency(operation, failed) {
+            // Never started, so it has no duration and is not a call
+            Some(dependency) => {
+                record_skipped(&operation.tool, self.ctx);
+                BatchOperationResult::failed(
+                    &operation.id,
+                    McpError::tool_error(format!(
+                        "Operation '{}' failed, so its result is not available",
+                        dependency
+                    )),
+                    None,
+                )
+            }
+            None => {
+                let start = Instant::now();
+                match run_operation(operation, scope, &self.op_ctx) {
+                    Ok(value) => {
+                        BatchOperationResult::succeeded(&operation.id, value, start.elapsed())
+                    }
+                    Err(e) => {
+                        self.ctx.check()?;
+                        let e = within(e, &format!("operations[{}].arguments", index));
+                        BatchOperationResult::failed(&operation.id, e, Some(start.elapsed()))
+                    }
+                }
+            }
+        };
+        if !result.success && self.stop_on_failure {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+
+        // Counted under the lock so updates arrive in increasing order
+        let mut completed = lock(&self.completed);
+        *completed += 1;
+        if self.ctx.reports_progress() {
+            self.ctx.report_progress(
+                *completed as f64,
+                Some(self.operations.len() as f64),
+                Some(format!("Completed operation '{}'", operation.id)),
+            );
+        }
+        Ok(result)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Count an operation that never started; like the registry, unknown tool
+/// names are not recorded, so clients cannot grow the metrics
+fn record_skipped(tool: &str, ctx: &ToolContext) {
+    if is_known(tool, ctx) {
+        metrics::global().record_skipped(tool);
+    }
+}
+
+/// The first operation `operation` refers to that has already failed
+fn failed_dependency<'a>(operation: &'a BatchOperation, failed: &HashSet<&str>) -> Option<&'a str> {
+    references::references(&operation.arguments)
+        .into_iter()
+        .map(references::root)
+        .find(|id| failed.contains(id))
+}
+
+/// Resolve an operation's references against the earlier results, then run it
+fn run_operation(
+    operation: &BatchOperation,
+    scope: &Map<String, Value>,
+    ctx: &ToolContext,
+) -> McpResult<Value> {
+    let start = Instant::now();
+    let arguments = references::substitute(&operation.arguments, scope);
+    let arguments = match arguments {
+        Ok(arguments) => arguments,
+        // The registry never sees the call, so it is counted here, under the
+        // same rule that unknown names are not recorded
+        Err(e) => {
+            let result = Err(e);
+            if is_known(&operation.tool, ctx) {
+                metrics::global().record(&operation.tool, start.elapsed(), &result);
+            }
+            return result;
+        }
+    };
+    DefaultToolRegistry.execute_tool_with_context(&operation.tool, &arguments, ctx)
+}
+
+/// Group the operations into waves that can each run in parallel: every
+/// operation comes in a later wave than those it refers to, and each wave
+/// keeps request order.
+///
+/// Fails with `invalid_params` on references to unknown operations and on
+/// cycles.
+fn execution_waves(operations: &[BatchOperation]) -> McpResult<Vec<Vec<usize>>> {
+    let index: HashMap<&str, usize> = operations
+        .iter()
+        .enumerate()
+        .map(|(i, op)| (op.id.as_str(), i))
+        .collect();
+    let dependencies = operations
+        .iter()
+        .enumerate()
+        .map(|(i, op)| {
+            references::references(&op.arguments)
+                .into_iter()
+                .map(|path| {
+                    let id = references::root(path);
+                    index.get(id).copied().ok_or_else(|| {
+                        InvalidArgument::new("a reference to an operation in this batch")
+                            .at(format!("operations[{}].arguments", i))
+                            .received(path)
+                            .invalid_params(format!(
+                                "Operation '{}' refers to unknown operation '{}'",
+                                op.id, id
+                            ))
+                    })
+                })
+                .collect::<McpResult<HashSet<usize>>>()
+        })
+        .collect::<McpResult<Vec<_>>>()?;
+
+    let mut waves: Vec<Vec<usize>> = Vec::new();
+    let mut done = vec![false; operations.len()];
+    let mut remaining = operations.len();
+    while remaining > 0 {
+        let wave: Vec<usize> = (0..operations.len())
+            .filter(|&i| !done[i] && dependencies[i].iter().all(|&d| done[d]))
+            .collect();
+        if wave.is_empty() {
+            return Err(McpError::invalid_params(format!(
+                "Operations refer to each other in a cycle: {}",
+                find_cycle(operations, &dependencies, &done)
+            )));
+        }
+        for &i in &wave {
+            done[i] = true;
+        }
+        remaining -= wave.len();
+        waves.push(wave);
+    }
+    Ok(waves)
+}
+
+/// Describe a reference cycle among the operations not yet `done`, e.g. `a -> b -> a`
+fn find_cycle(
+    operations: &[BatchOperation],
+    dependencies: &[HashSet<usize>],
+    done: &[bool],
+) -> String {
+    // Every remaining operation waits on another remaining one, so following
+    // those links from anywhere must eventually revisit an operation
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = (0..operations.len())
+        .find(|&i| !done[i])
+        .unwrap_or_default();
+    while !path.contains(&current) {
+        path.push(current);
+        let mut waiting_on: Vec<usize> = dependencies[current]
+            .iter()
+            .copied()
+            .filter(|&d| !done[d])
+            .collect();
+        waiting_on.sort_unstable();
+        match waiting_on.first() {
+            Some(&next) => current = next,
+            None => break,
+        }
+    }
+    let start = path.iter().position(|&i| i == current).unwrap_or_default();
+    path[start..]
+        .iter()
+        .chain(std::iter::once(&current))
+        .map(|&i| operations[i].id.as_str())
+        .collect::<Vec<_>>()
+        .join(" -> ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_example_result() {
+        let mut result = BatchOperations::call(&BatchOperations::example()).unwrap();
+        for operation in result["results"].as_array_mut().unwrap() {
+            operation.as_object_mut().unwrap().remove("duration_ms");
+        }
+        assert_eq!(result, BatchOperations::example_result());
+    }
 
     #[test]
     fn test_batch_operations_success() {
@@ -165,7 +555,7 @@ mod tests {
             ]
         });
 
-        let result = execute(TOOL_BATCH, &args).unwrap();
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
 
         assert!(result["results"].is_array());
         assert_eq!(result["summary"]["total"], 3);
@@ -212,7 +602,7 @@ mod tests {
             ]
         });
 
-        let result = execute(TOOL_BATCH, &args).unwrap();
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
 
         assert_eq!(result["summary"]["total"], 3);
         assert_eq!(result["summary"]["successful"], 2);
@@ -245,7 +635,7 @@ mod tests {
             ]
         });
 
-        let result = execute(TOOL_BATCH, &args).unwrap();
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
         let results = result["results"].as_array().unwrap();
         let unknown = &results[0];
 
@@ -259,7 +649,7 @@ mod tests {
             "operations": []
         });
 
-        let result = execute(TOOL_BATCH, &args);
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().message.contains("No operations"));
     }
@@ -281,7 +671,7 @@ mod tests {
             ]
         });
 
-        let result = execute(TOOL_BATCH, &args);
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default());
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -289,6 +679,32 @@ mod tests {
             .contains("Duplicate operation ID"));
     }
 
+    #[test]
+    fn test_batch_operations_report_progress() {
+        let updates = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let ctx = ToolContext::new().with_progress({
+            let updates = updates.clone();
+            move |progress| updates.lock().unwrap().push(progress)
+        });
+        let args = json!({
+            "operations": [
+                { "id": "a", "tool": "add", "arguments": { "numbers": [1.0, 2.0] } },
+                { "id": "b", "tool": "divide", "arguments": { "a": "${a.result}", "b": 0.0 } }
+            ]
+        });
+
+        execute(TOOL_BATCH, &args, &ctx).unwrap();
+
+        let updates = updates.lock().unwrap();
+        let steps: Vec<(f64, Option<f64>)> =
+            updates.iter().map(|p| (p.progress, p.total)).collect();
+        assert_eq!(steps, vec![(1.0, Some(2.0)), (2.0, Some(2.0))]);
+        assert_eq!(
+            updates[1].message.as_deref(),
+            Some("Completed operation 'b'")
+        );
+    }
+
     #[test]
     fn test_batch_operations_max_size() {
         let mut operations = Vec::new();
@@ -304,7 +720,7 @@ mod tests {
             "operations": operations
         });
 
-        let result = execute(TOOL_BATCH, &args);
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().message.contains("exceeds maximum"));
     }
@@ -342,7 +758,7 @@ mod tests {
             ]
         });
 
-        let result = execute(TOOL_BATCH, &args).unwrap();
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
         assert_eq!(result["summary"]["successful"], 5);
         assert_eq!(result["summary"]["failed"], 0);
 
@@ -354,4 +770,210 @@ mod tests {
             assert_eq!(r["success"], true);
         }
     }
+
+    #[test]
+    fn test_batch_operations_references() {
+        // Listed out of order: z-score of 7 needs the mean and std_dev first
+        let args = json!({
+            "operations": [
+                {
+                    "id": "z",
+                    "tool": "divide",
+                    "arguments": { "a": "${diff.result}", "b": { "$ref": "sd.result" } }
+                },
+                {
+                    "id": "diff",
+                    "tool": "subtract",
+                    "arguments": { "a": 7.0, "b": "${mean.result}" }
+                },
+                { "id": "mean", "tool": "mean", "arguments": { "numbers": [2.0, 4.0, 6.0] } },
+                { "id": "sd", "tool": "std_dev", "arguments": { "numbers": [2.0, 4.0, 6.0], "sample": true } },
+                {
+                    "id": "total",
+                    "tool": "add",
+                    "arguments": { "numbers": ["${mean.result}", { "$ref": "sd.result" }, 1.0] }
+                }
+            ]
+        });
+
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
+        assert_eq!(result["summary"]["successful"], 5);
+
+        // Results stay in request order
+        let results = result["results"].as_array().unwrap();
+        let ids: Vec<&str> = results.iter().filter_map(|r| r["id"].as_str()).collect();
+        assert_eq!(ids, vec!["z", "diff", "mean", "sd", "total"]);
+        assert_eq!(results[0]["result"]["result"], 1.5);
+        assert_eq!(results[4]["result"]["result"], 7.0);
+    }
+
+    #[test]
+    fn test_batch_operations_reference_to_failed_operation() {
+        let args = json!({
+            "operations": [
+                { "id": "bad", "tool": "divide", "arguments": { "a": 1.0, "b": 0.0 } },
+                { "id": "uses_bad", "tool": "sqrt", "arguments": { "number": "${bad.result}" } },
+                { "id": "missing_field", "tool": "sqrt", "arguments": { "number": "${ok.nothing}" } },
+                { "id": "ok", "tool": "add", "arguments": { "numbers": [1.0, 2.0] } }
+            ]
+        });
+
+        let result = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap();
+        assert_eq!(result["summary"]["successful"], 1);
+        let results = result["results"].as_array().unwrap();
+        assert_eq!(
+            results[1]["error"],
+            "Operation 'bad' failed, so its result is not available"
+        );
+        assert_eq!(results[2]["error"], "Reference 'ok.nothing' not found");
+    }
+
+    #[test]
+    fn test_batch_operations_reference_errors() {
+        let unknown = json!({
+            "operations": [
+                { "id": "a", "tool": "sqrt", "arguments": { "number": "${nope.result}" } }
+            ]
+        });
+        let err = execute(TOOL_BATCH, &unknown, &ToolContext::default()).unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains("unknown operation 'nope'"));
+
+        let cycle = json!({
+            "operations": [
+                { "id": "start", "tool": "add", "arguments": { "numbers": [1.0] } },
+                { "id": "a", "tool": "sqrt", "arguments": { "number": "${b.result}" } },
+                { "id": "b", "tool": "sqrt", "arguments": { "number": "${c.result}" } },
+                { "id": "c", "tool": "sqrt", "arguments": { "number": { "$ref": "a.result" } } }
+            ]
+        });
+        let err = execute(TOOL_BATCH, &cycle, &ToolContext::default()).unwrap_err();
+        assert!(err.message.contains("a -> b -> c -> a"), "{}", err.message);
+
+        let itself = json!({
+            "operations": [
+                { "id": "a", "tool": "sqrt", "arguments": { "number": "${a.result}" } }
+            ]
+        });
+        let err = execute(TOOL_BATCH, &itself, &ToolContext::default()).unwrap_err();
+        assert!(err.message.contains("a -> a"));
+    }
+
+    fn failing_batch(mode: &str) -> Value {
+        json!({
+            "mode": mode,
+            "operations": [
+                { "id": "first", "tool": "add", "arguments": { "numbers": [1.0, 2.0] } },
+                { "id": "bad", "tool": "sqrt", "arguments": { "number": "${first.result}" } },
+                { "id": "broken", "tool": "divide", "arguments": { "a": "${bad.result}", "b": 0.0 } },
+                { "id": "worse", "tool": "add", "arguments": { "numbers": ["${broken.result}"] } },
+                { "id": "last", "tool": "add", "arguments": { "numbers": ["${worse.result}"] } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_batch_operations_independent_mode_reports_timing() {
+        let result = execute(
+            TOOL_BATCH,
+            &failing_batch("independent"),
+            &ToolContext::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result["summary"]["successful"], 2);
+        assert_eq!(result["summary"]["failed"], 3);
+        assert_eq!(result["summary"]["skipped"], 0);
+        let results = result["results"].as_array().unwrap();
+        assert!(results[0]["duration_ms"].as_f64().unwrap() >= 0.0);
+        assert!(results[2]["duration_ms"].is_number());
+        // Never ran: its reference failed
+        assert!(results[3]["duration_ms"].is_null());
+        assert!(results.iter().all(|r| r.get("skipped").is_none()));
+    }
+
+    #[test]
+    fn test_batch_operations_fail_fast_mode() {
+        let result = execute(
+            TOOL_BATCH,
+            &failing_batch("fail_fast"),
+            &ToolContext::default(),
+        )
+        .unwrap();
+
+        // Nothing starts after `broken` fails
+        assert_eq!(result["summary"]["successful"], 2);
+        assert_eq!(result["summary"]["failed"], 1);
+        assert_eq!(result["summary"]["skipped"], 2);
+        let results = result["results"].as_array().unwrap();
+        assert_eq!(results[3]["skipped"], true);
+        assert_eq!(results[4]["skipped"], true);
+        assert!(results[4]["duration_ms"].is_null());
+    }
+
+    #[test]
+    fn test_batch_operations_all_or_nothing_mode() {
+        let err = execute(
+            TOOL_BATCH,
+            &failing_batch("all_or_nothing"),
+            &ToolContext::default(),
+        )
+        .unwrap_err();
+        assert!(err.message.contains("Operation 'broken' failed"));
+        assert_eq!(err.data.unwrap()["operation"], "broken");
+
+        let mut ok = failing_batch("all_or_nothing");
+        ok["operations"].as_array_mut().unwrap().truncate(2);
+        let result = execute(TOOL_BATCH, &ok, &ToolContext::default()).unwrap();
+        assert_eq!(result["summary"]["successful"], 2);
+    }
+
+    #[test]
+    fn test_batch_operations_run_in_parallel() {
+        let operations: Vec<Value> = (0..20)
+            .map(|i| json!({ "id": format!("op{}", i), "tool": "factorial", "arguments": { "n": i } }))
+            .collect();
+        let result = execute(
+            TOOL_BATCH,
+            &json!({ "operations": operations }),
+            &ToolContext::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result["summary"]["successful"], 20);
+        let results = result["results"].as_array().unwrap();
+        assert_eq!(results[5]["id"], "op5");
+        assert_eq!(results[5]["result"]["result"], 120.0);
+    }
+
+    #[test]
+    fn test_batch_operations_nested_batch_rejected() {
+        let args = json!({
+            "operations": [
+                { "id": "inner", "tool": TOOL_BATCH, "arguments": { "operations": [] } }
+            ]
+        });
+
+        let err = execute(TOOL_BATCH, &args, &ToolContext::default()).unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains("cannot be nested"));
+    }
+
+    #[test]
+    fn test_batch_operations_configured_max_size() {
+        let config = crate::config::Config {
+            max_batch_size: 2,
+            ..crate::config::Config::default()
+        };
+        let ctx = ToolContext::new().with_config(std::sync::Arc::new(config));
+        let operation =
+            |id: &str| json!({ "id": id, "tool": "add", "arguments": { "numbers": [1.0] } });
+
+        let two = json!({ "operations": [operation("a"), operation("b")] });
+        assert!(execute(TOOL_BATCH, &two, &ctx).is_ok());
+
+        let three = json!({ "operations": [operation("a"), operation("b"), operation("c")] });
+        let err = execute(TOOL_BATCH, &three, &ctx).unwrap_err();
+        assert!(err.message.contains("maximum of 2"));
+    }
 }
@@ -0,0 +1,296 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// Counters and current size of a [`ResultCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to run the tool
+    pub misses: u64,
+    /// Entries dropped to stay within the bounds
+    pub evictions: u64,
+    /// Results currently cached
+    pub entries: usize,
+    /// Approximate size of the cached keys and results, in bytes
+    pub bytes: usize,
+}
+
+/// Least-recently-used cache of tool results, keyed on the tool name and its
+/// canonicalized arguments.
+///
+/// Bounds are passed on each insert, so one cache can serve configurations
+/// with different limits; an insert evicts the least recently used entries
+/// until both bounds hold.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::tools::cache::ResultCache;
+/// use serde_json::json;
+///
+/// let cache = ResultCache::new();
+/// cache.insert("add", &json!({ "numbers": [1, 2] }), json!({ "result": 3.0 }), 10, 1024);
+/// // Same arguments written differently
+/// let hit = cache.get("add", &json!({ "numbers": [1.0, 2.0] }));
+/// assert_eq!(hit, Some(json!({ "result": 3.0 })));
+/// assert_eq!(cache.stats().hits, 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    /// Keys by last use, oldest first
+    order: BTreeMap<u64, String>,
+    /// Incremented on every use, so larger is more recent
+    clock: u64,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    bytes: usize,
+    last_used: u64,
+}
+
+impl ResultCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached result of calling `tool` with `arguments`, counting a hit or a miss
+    pub fn get(&self, tool: &str, arguments: &Value) -> Option<Value> {
+        let key = cache_key(tool, arguments);
+        let mut inner = self.lock();
+        inner.clock += 1;
+        let now = inner.clock;
+        let Some(entry) = inner.entries.get_mut(&key) else {
+            inner.misses += 1;
+            return None;
+        };
+        let previous = std::mem::replace(&mut entry.last_used, now);
+        let value = entry.value.clone();
+        inner.order.remove(&previous);
+        inner.order.insert(now, key);
+        inner.hits += 1;
+        Some(value)
+    }
+
+    /// Cache `value` as the result of calling `tool` with `arguments`, then
+    /// evict the least recently used entries until at most `max_entries`
+    /// remain and they take at most `max_bytes`.
+    ///
+    /// A result larger than `max_bytes` on its own is not cached.
+    pub fn insert(
+        &self,
+        tool: &str,
+        arguments: &Value,
+        value: Value,
+        max_entries: usize,
+        max_bytes: usize,
+    ) {
+        let key = cache_key(tool, arguments);
+        let bytes = key.len() + value.to_string().len();
+        if bytes > max_bytes || max_entries == 0 {
+            return;
+        }
+
+        let mut inner = self.lock();
+        inner.remove(&key);
+        inner.clock += 1;
+        let now = inner.clock;
+        inner.order.insert(now, key.clone());
+        inner.entries.insert(
+            key,
+            Entry {
+                value,
+                bytes,
+                last_used: now,
+            },
+        );
+        inner.bytes += bytes;
+
+        while inner.entries.len() > max_entries || inner.bytes > max_bytes {
+            let Some((_, oldest)) = inner.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = inner.entries.remove(&oldest) {
+                inner.bytes -= entry.bytes;
+                inner.evictions += 1;
+            }
+        }
+    }
+
+    /// Drop every cached result of `tool`
+    pub fn remove_tool(&self, tool: &str) {
+        let prefix = cache_key_prefix(tool);
+        let mut inner = self.lock();
+        let keys: Vec<String> = inner
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for key in keys {
+            inner.remove(&key);
+        }
+    }
+
+    /// Drop every cached result; the counters are kept
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.order.clear();
+        inner.bytes = 0;
+    }
+
+    /// Current counters and size
+    pub fn stats(&self) -> CacheStats {
+        let inner = self.lock();
+        CacheStats {
+            hits: inner.hits,
+            misses: inner.misses,
+            evictions: inner.evictions,
+            entries: inner.entries.len(),
+            bytes: inner.bytes,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Inner {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+            self.bytes -= entry.bytes;
+        }
+    }
+}
+
+fn cache_key_prefix(tool: &str) -> String {
+    format!("{}\0", tool)
+}
+
+/// `tool` followed by its arguments in canonical form
+fn cache_key(tool: &str, arguments: &Value) -> String {
+    let mut key = cache_key_prefix(tool);
+    write_canonical(arguments, &mut key);
+    key
+}
+
+/// `value` as JSON with object keys sorted and every number in its `f64` form
+pub(crate) fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+/// Write `value` as JSON with object keys sorted and every number in its
+/// `f64` form, so `{"b": 1, "a": 2}` and `{"a": 2.0, "b": 1.0}` match
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Number(number) => match number.as_f64() {
+            Some(number) => out.push_str(&format!("{:?}", number)),
+            None => out.push_str(&number.to_string()),
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by_key(|(name, _)| *name);
+            out.push('{');
+            for (index, (name, field)) in fields.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(name.as_str()).to_string());
+                out.push(':');
+                write_canonical(field, out);
+            }
+            out.push('}');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_keys() {
+        assert_eq!(
+            cache_key("t", &json!({ "b": 1, "a": [2.5, null] })),
+            cache_key("t", &json!({ "a": [2.5, null], "b": 1.0 }))
+        );
+        assert_ne!(
+            cache_key("t", &json!({ "a": 1 })),
+            cache_key("u", &json!({ "a": 1 }))
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ResultCache::new();
+        cache.insert("t", &json!(1), json!(1), 2, 1024);
+        cache.insert("t", &json!(2), json!(2), 2, 1024);
+        // Using 1 makes 2 the oldest
+        assert!(cache.get("t", &json!(1)).is_some());
+        cache.insert("t", &json!(3), json!(3), 2, 1024);
+
+        assert!(cache.get("t", &json!(2)).is_none());
+        assert!(cache.get("t", &json!(1)).is_some());
+        assert!(cache.get("t", &json!(3)).is_some());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (3, 1, 1));
+        assert_eq!(stats.entries, 2);
+    }
+
+    #[test]
+    fn test_byte_bound() {
+        let cache = ResultCache::new();
+        let big = json!(vec![1.0; 100]);
+        cache.insert("t", &json!(1), big.clone(), 10, 500);
+        assert_eq!(cache.stats().entries, 1);
+        cache.insert("t", &json!(2), big.clone(), 10, 500);
+        assert_eq!(cache.stats().entries, 1);
+        assert!(cache.stats().bytes <= 500);
+        // Too big to cache at all
+        cache.insert("t", &json!(3), big, 10, 50);
+        assert!(cache.get("t", &json!(3)).is_none());
+    }
+
+    #[test]
+    fn test_remove_tool() {
+        let cache = ResultCache::new();
+        cache.insert("a", &json!(1), json!(1), 10, 1024);
+        cache.insert("ab", &json!(1), json!(1), 10, 1024);
+        cache.remove_tool("a");
+        assert!(cache.get("a", &json!(1)).is_none());
+        assert!(cache.get("ab", &json!(1)).is_some());
+        assert_eq!(cache.stats().entries, 1);
+    }
+}
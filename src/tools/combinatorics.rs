@@ -1,138 +1,87 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, result_json};
-use serde_json::Value;
-
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "permutation",
-            "description": "Calculate permutations: P(n, r) = n! / (n - r)!",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "n": {"type": "number", "description": "Total number of items"},
-                    "r": {"type": "number", "description": "Number of items to arrange"}
-                },
-                "required": ["n", "r"]
-            }
-        }),
-        serde_json::json!({
-            "name": "combination",
-            "description": "Calculate combinations: C(n, r) = n! / (r! × (n - r)!)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "n": {"type": "number", "description": "Total number of items"},
-                    "r": {"type": "number", "description": "Number of items to choose"}
-                },
-                "required": ["n", "r"]
-            }
-        }),
-    ]
-}
-
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "permutation" => {
-            let n = get_number(arguments, "n")?;
-            let r = get_number(arguments, "r")?;
-            Ok(result_json(permutation(n, r)?))
-        }
-        "combination" => {
-            let n = get_number(arguments, "n")?;
-            let r = get_number(arguments, "r")?;
-            Ok(result_json(combination(n, r)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown combinatorics tool: {}",
-            name
-        ))),
-    }
+use crate::math::combinatorics as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use crate::utils::validation::validate_integer;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Arguments for selecting `r` items out of `n`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SelectionInput {
+    /// Total number of items
+    pub n: f64,
+    /// Number of items to arrange or choose
+    pub r: f64,
 }
 
-fn permutation(n: f64, r: f64) -> McpResult<f64> {
-    use crate::utils::validation::validate_integer;
-
-    let n_int = validate_integer(n, "n")?;
-    let r_int = validate_integer(r, "r")?;
+pub struct Permutation;
+pub struct Combination;
 
-    if n_int < 0 || r_int < 0 {
-        return Err(crate::error::McpError::validation_error(
-            "Permutation: n and r must be non-negative",
-        ));
-    }
-
-    if r_int > n_int {
-        return Err(crate::error::McpError::validation_error(
-            "Permutation: r must be <= n",
-        ));
-    }
+impl MathTool for Permutation {
+    const NAME: &'static str = "permutation";
+    const DESCRIPTION: &'static str = "Calculate permutations: P(n, r) = n! / (n - r)!";
+    const FORMULA: &'static str = "P(n, r) = n! / (n - r)!";
+    type Input = SelectionInput;
+    type Output = NumberResult;
 
-    // Limit to prevent overflow (conservative limit)
-    if n_int > 170 {
-        return Err(crate::error::McpError::validation_error(
-            "Permutation overflow: n must be <= 170",
-        ));
+    fn example() -> Value {
+        json!({ "n": 5, "r": 2 })
     }
 
-    // Use checked arithmetic
-    let mut result = 1u64;
-    for i in 0..r_int {
-        let factor = (n_int - i) as u64;
-        result = result.checked_mul(factor).ok_or_else(|| {
-            crate::error::McpError::validation_error(format!(
-                "Permutation overflow at factor {}",
-                factor
-            ))
-        })?;
+    fn run(input: SelectionInput) -> McpResult<NumberResult> {
+        let (n, r) = selection(&input, "Permutation")?;
+        Ok((math::permutation(n, r)? as f64).into())
     }
-
-    Ok(result as f64)
 }
 
-fn combination(n: f64, r: f64) -> McpResult<f64> {
-    use crate::utils::validation::validate_integer;
-
-    let n_int = validate_integer(n, "n")?;
-    let r_int = validate_integer(r, "r")?;
+impl MathTool for Combination {
+    const NAME: &'static str = "combination";
+    const DESCRIPTION: &'static str = "Calculate combinations: C(n, r) = n! / (r! × (n - r)!)";
+    const FORMULA: &'static str = "C(n, r) = n! / (r! × (n - r)!)";
+    type Input = SelectionInput;
+    type Output = NumberResult;
 
-    if n_int < 0 || r_int < 0 {
-        return Err(crate::error::McpError::validation_error(
-            "Combination: n and r must be non-negative",
-        ));
+    fn example() -> Value {
+        json!({ "n": 5, "r": 2 })
     }
 
-    if r_int > n_int {
-        return Err(crate::error::McpError::validation_error(
-            "Combination: r must be <= n",
-        ));
+    fn run(input: SelectionInput) -> McpResult<NumberResult> {
+        let (n, r) = selection(&input, "Combination")?;
+        Ok((math::combination(n, r)? as f64).into())
     }
+}
 
-    // Limit to prevent overflow
-    if n_int > 170 {
-        return Err(crate::error::McpError::validation_error(
-            "Combination overflow: n must be <= 170",
-        ));
-    }
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![Permutation::definition(), Combination::definition()]
+}
 
-    // Use smaller of r and n-r for efficiency
-    let r_opt = if r_int > n_int / 2 {
-        n_int - r_int
-    } else {
-        r_int
-    };
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![Permutation::documentation(), Combination::documentation()]
+}
 
-    // Calculate using checked arithmetic to prevent overflow
-    // C(n,r) = (n * (n-1) * ... * (n-r+1)) / (r * (r-1) * ... * 1)
-    let mut result = 1u64;
-    for i in 0..r_opt {
-        result = result.checked_mul((n_int - i) as u64).ok_or_else(|| {
-            crate::error::McpError::validation_error("Combination overflow during multiplication")
-        })?;
-        result = result.checked_div((i + 1) as u64).ok_or_else(|| {
-            crate::error::McpError::validation_error("Combination: division error")
-        })?;
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        Permutation::NAME => Permutation::call_with_context(arguments, ctx),
+        Combination::NAME => Combination::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown combinatorics tool: {}",
+            name
+        ))),
     }
+}
 
-    Ok(result as f64)
+/// `n` and `r` as non-negative integers, or an error naming `tool`
+fn selection(input: &SelectionInput, tool: &str) -> McpResult<(u64, u64)> {
+    let n = validate_integer(input.n, "n")?;
+    let r = validate_integer(input.r, "r")?;
+    if n < 0 || r < 0 {
+        return Err(crate::error::McpError::validation_error(format!(
+            "{}: n and r must be non-negative",
+            tool
+        )));
+    }
+    Ok((n as u64, r as u64))
 }
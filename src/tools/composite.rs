@@ -0,0 +1,751 @@
+use crate::error::{McpError, McpResult};
+use crate::tools::batch::TOOL_BATCH;
+use crate::tools::context::ToolContext;
+use crate::tools::references;
+use crate::tools::registry::DefaultToolRegistry;
+use crate::tools::traits::{output_schema_for, NumberResult, ToolRegistry};
+use crate::utils::validation::validate_arguments;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// A composite tool as declared under `[tools.composite.<name>]`.
+///
+/// Exactly one of `expression` and `steps` must be given.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompositeSpec {
+    /// Shown to the client as the tool's description
+    pub description: String,
+    /// Arguments the tool accepts, by name
+    #[serde(default)]
+    pub parameters: BTreeMap<String, ParameterSpec>,
+    /// Arithmetic over the parameters, e.g. `weight / height ^ 2`
+    pub expression: Option<String>,
+    /// Tool calls run in order, each able to use the parameters and earlier results
+    #[serde(default)]
+    pub steps: Vec<StepSpec>,
+}
+
+/// A parameter: either just its description (a number), or a table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ParameterSpec {
+    /// A number described by the string
+    Description(String),
+    /// `{ type = "number" | "array", description = "..." }`
+    Full {
+        #[serde(rename = "type", default)]
+        kind: ParameterKind,
+        #[serde(default)]
+        description: Option<String>,
+    },
+}
+
+/// JSON type of a parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterKind {
+    /// A single number
+    #[default]
+    Number,
+    /// An array of numbers
+    Array,
+}
+
+/// One tool call in a pipeline
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StepSpec {
+    /// Name later steps use to refer to this step's result
+    pub id: String,
+    /// Tool to call
+    pub tool: String,
+    /// Arguments for the tool; `"${name}"` (or `{"$ref": "name"}`) stands for
+    /// a parameter and `"${id.result}"` for a field of an earlier step's result
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+}
+
+/// A tool defined in the configuration in terms of other tools.
+///
+/// Composite tools are either an arithmetic expression over their parameters,
+/// whose operators and functions are evaluated by calling the built-in tools
+/// (`+` is `add`, `^` is `power`, `sqrt(x)` is `sqrt`, ...), or a pipeline of
+/// tool calls. Either way every step runs through [`DefaultToolRegistry`], so
+/// the allow and deny lists and the call's deadline apply to it. Steps may
+/// use built-in and runtime-registered tools, but not other composite tools.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::tools::{DefaultToolRegistry, ToolContext, ToolRegistry};
+/// use serde_json::json;
+/// use std::sync::Arc;
+///
+/// let config = Config::from_toml(r#"
+///     [tools.composite.bmi]
+///     description = "Body-mass index"
+///     expression = "weight / height ^ 2"
+///     parameters = { weight = "Weight in kg", height = "Height in m" }
+/// "#).unwrap();
+/// let ctx = ToolContext::new().with_config(Arc::new(config));
+///
+/// let args = json!({ "weight": 81, "height": 1.8 });
+/// let result = DefaultToolRegistry.execute_tool_with_context("bmi", &args, &ctx).unwrap();
+/// assert!((result["result"].as_f64().unwrap() - 25.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompositeTool {
+    name: String,
+    description: String,
+    parameters: Vec<Parameter>,
+    body: Body,
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    name: String,
+    kind: ParameterKind,
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Body {
+    Expression(Expr),
+    Pipeline(Vec<StepSpec>),
+}
+
+impl CompositeTool {
+    /// Build a composite tool from its declaration.
+    ///
+    /// `tools` are the definitions of the tools it may call; the expression
+    /// or steps may only refer to those, to its own parameters and (in
+    /// pipelines) to earlier steps.
+    pub fn from_spec(name: &str, spec: CompositeSpec, tools: &[Value]) -> Result<Self, String> {
+        if tools.iter().any(|tool| tool["name"] == name) {
+            return Err("conflicts with an existing tool".to_string());
+        }
+        let parameters: Vec<Parameter> = spec
+            .parameters
+            .into_iter()
+            .map(|(name, spec)| match spec {
+                ParameterSpec::Description(description) => Parameter {
+                    name,
+                    kind: ParameterKind::Number,
+                    description: Some(description),
+                },
+                ParameterSpec::Full { kind, description } => Parameter {
+                    name,
+                    kind,
+                    description,
+                },
+            })
+            .collect();
+
+        let body = match (spec.expression, spec.steps.is_empty()) {
+            (Some(expression), true) => {
+                let expr = Parser::new(&expression, tools).parse()?;
+                expr.check_parameters(&parameters)?;
+                Body::Expression(expr)
+            }
+            (None, false) => {
+                check_steps(&spec.steps, &parameters, tools)?;
+                Body::Pipeline(spec.steps)
+            }
+            _ => return Err("needs either an expression or steps".to_string()),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            description: spec.description,
+            parameters,
+            body,
+        })
+    }
+
+    /// Tool name as exposed in `tools/list`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Tool definition (`name`, `description`, `inputSchema` and, for
+    /// expressions, `outputSchema`) generated from the declaration
+    pub fn definition(&self) -> Value {
+        let properties: Map<String, Value> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let mut schema = match parameter.kind {
+                    ParameterKind::Number => json!({ "type": "number" }),
+                    ParameterKind::Array => {
+                        json!({ "type": "array", "items": { "type": "number" } })
+                    }
+                };
+                if let Some(description) = &parameter.description {
+                    schema["description"] = Value::from(description.as_str());
+                }
+                (parameter.name.clone(), schema)
+            })
+            .collect();
+        let required: Vec<&str> = self.parameters.iter().map(|p| p.name.as_str()).collect();
+
+        let mut definition = json!({
+            "name": self.name,
+            "description": self.description,
+            "inputSchema": {
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            },
+        });
+        if let Body::Expression(_) = self.body {
+            definition["outputSchema"] = output_schema_for::<NumberResult>();
+        }
+        definition
+    }
+
+    /// Run the tool: check the arguments against the definition's
+    /// `inputSchema`, then evaluate the expression or run the steps in order
+    pub fn execute(&self, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+        validate_arguments(&self.name, arguments, &self.definition()["inputSchema"])?;
+        let scope = self
+            .parameters
+            .iter()
+            .filter_map(|parameter| {
+                let value = arguments.get(&parameter.name)?;
+                Some((parameter.name.clone(), value.clone()))
+            })
+            .collect();
+        // Progress belongs to the composite call as a whole
+        let step_ctx = ctx.nested();
+        match &self.body {
+            Body::Expression(expr) => {
+                let result = expr.evaluate(&scope, &step_ctx)?;
+                Ok(json!({ "result": result }))
+            }
+            Body::Pipeline(steps) => run_pipeline(steps, scope, &step_ctx),
+        }
+    }
+}
+
+/// Call `tool` for a composite tool, naming the step in any error
+fn call_step(step: &str, tool: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    ctx.check()?;
+    DefaultToolRegistry
+        .execute_tool_with_context(tool, arguments, ctx)
+        .map_err(|e| McpError {
+            message: format!("Step '{}' ({}) failed: {}", step, tool, e.message),
+            ..e
+        })
+}
+
+fn run_pipeline(
+    steps: &[StepSpec],
+    mut scope: Map<String, Value>,
+    ctx: &ToolContext,
+) -> McpResult<Value> {
+    let mut last = Value::Null;
+    for step in steps {
+        let arguments = references::substitute(&Value::Object(step.arguments.clone()), &scope)?;
+        last = call_step(&step.id, &step.tool, &arguments, ctx)?;
+        scope.insert(step.id.clone(), last.clone());
+    }
+    Ok(last)
+}
+
+/// Check that steps have unique ids, call known tools, and only refer to
+/// parameters and earlier steps
+fn check_steps(
+    steps: &[StepSpec],
+    parameters: &[Parameter],
+    tools: &[Value],
+) -> Result<(), String> {
+    let mut names: HashSet<&str> = parameters.iter().map(|p| p.name.as_str()).collect();
+    for step in steps {
+        if !tools.iter().any(|tool| tool["name"] == step.tool.as_str()) {
+            return Err(format!("step '{}': unknown tool '{}'", step.id, step.tool));
+        }
+        if step.tool == TOOL_BATCH {
+            return Err(format!(
+                "step '{}': {} cannot be a step",
+                step.id, TOOL_BATCH
+            ));
+        }
+        let arguments = Value::Object(step.arguments.clone());
+        if let Some(path) = references::references(&arguments)
+            .into_iter()
+            .find(|path| !names.contains(references::root(path)))
+        {
+            return Err(format!(
+                "step '{}': '{}' is not a parameter or an earlier step",
+                step.id, path
+            ));
+        }
+        if !names.insert(&step.id) {
+            return Err(format!("duplicate step or parameter name '{}'", step.id));
+        }
+    }
+    Ok(())
+}
+
+/// An arithmetic expression over a composite tool's parameters
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Parameter(String),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// A one-argument tool, with the name of the argument it takes
+    Function {
+        tool: String,
+        argument: String,
+        operand: Box<Expr>,
+    },
+}
+
+/// Binary operators and the tools that evaluate them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+}
+
+impl BinaryOp {
+    /// The tool that evaluates `a op b`, and its arguments
+    fn call(self, a: f64, b: f64) -> (&'static str, Value) {
+        match self {
+            Self::Add => ("add", json!({ "numbers": [a, b] })),
+            Self::Subtract => ("subtract", json!({ "a": a, "b": b })),
+            Self::Multiply => ("multiply", json!({ "numbers": [a, b] })),
+            Self::Divide => ("divide", json!({ "a": a, "b": b })),
+            Self::Modulo => ("modulo", json!({ "a": a, "b": b })),
+            Self::Power => ("power", json!({ "base": a, "exponent": b })),
+        }
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Subtract => '-',
+            Self::Multiply => '*',
+            Self::Divide => '/',
+            Self::Modulo => '%',
+            Self::Power => '^',
+        }
+    }
+}
+
+impl Expr {
+    /// Check that the expression only uses number parameters
+    fn check_parameters(&self, parameters: &[Parameter]) -> Result<(), String> {
+        match self {
+            Self::Number(_) => Ok(()),
+            Self::Parameter(name) => match parameters.iter().find(|p| &p.name == name) {
+                Some(parameter) if parameter.kind == ParameterKind::Number => Ok(()),
+                Some(_) => Err(format!("expression: parameter '{}' must be a number", name)),
+                None => Err(format!("expression: unknown parameter '{}'", name)),
+            },
+            Self::Negate(operand) | Self::Function { operand, .. } => {
+                operand.check_parameters(parameters)
+            }
+            Self::Binary(_, left, right) => {
+                left.check_parameters(parameters)?;
+                right.check_parameters(parameters)
+            }
+        }
+    }
+
+    fn evaluate(&self, scope: &Map<String, Value>, ctx: &ToolContext) -> McpResult<f64> {
+        match self {
+            Self::Number(value) => Ok(*value),
+            // Arguments are checked against the schema first, so this is a number
+            Self::Parameter(name) => Ok(scope.get(name).and_then(Value::as_f64).unwrap_or(0.0)),
+            Self::Negate(operand) => Ok(-operand.evaluate(scope, ctx)?),
+            Self::Binary(op, left, right) => {
+                let (tool, arguments) =
+                    op.call(left.evaluate(scope, ctx)?, right.evaluate(scope, ctx)?);
+                let step = op.symbol().to_string();
+                number_result(tool, call_step(&step, tool, &arguments, ctx)?)
+            }
+            Self::Function {
+                tool,
+                argument,
+                operand,
+            } => {
+                let arguments = json!({ argument.as_str(): operand.evaluate(scope, ctx)? });
+                number_result(tool, call_step(tool, tool, &arguments, ctx)?)
+            }
+        }
+    }
+}
+
+/// The `result` of a tool that returns a single number
+fn number_result(tool: &str, output: Value) -> McpResult<f64> {
+    output
+        .get("result")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| McpError::tool_error(format!("Tool '{}' did not return a number", tool)))
+}
+
+/// Recursive-descent parser for composite expressions.
+///
+/// ```text
+/// expr    := term (('+' | '-') term)*
+/// term    := unary (('*' | '/' | '%') unary)*
+/// unary   := '-' unary | power
+/// power   := primary ('^' unary)?
+/// primary := number | name | name '(' expr ')' | '(' expr ')'
+/// ```
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    tools: &'a [Value],
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, tools: &'a [Value]) -> Self {
+        Self {
+            text,
+            chars: text.char_indices().peekable(),
+            tools,
+        }
+    }
+
+    fn parse(mut self) -> Result<Expr, String> {
+        let expr = self.expr()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some((at, c)) => Err(self.unexpected(at, c)),
+        }
+    }
+
+    /// The next non-space character and its position
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek().is_some_and(|(_, c)| c == expected) {
+            self.chars.next();
+            return true;
+        }
+        false
+    }
+
+    fn unexpected(&self, at: usize, c: char) -> String {
+        format!("expression: unexpected '{}' at position {}", c, at + 1)
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        loop {
+            let op = if self.eat('+') {
+                BinaryOp::Add
+            } else if self.eat('-') {
+                BinaryOp::Subtract
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat('*') {
+                BinaryOp::Multiply
+            } else if self.eat('/') {
+                BinaryOp::Divide
+            } else if self.eat('%') {
+                BinaryOp::Modulo
+            } else {
+                return Ok(left);
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        let base = self.primary()?;
+        if self.eat('^') {
+            return Ok(Expr::Binary(
+                BinaryOp::Power,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            None => Err("expression: unexpected end".to_string()),
+            Some((_, '(')) => {
+                self.chars.next();
+                let expr = self.expr()?;
+                self.close()?;
+                Ok(expr)
+            }
+            Some((start, c)) if c.is_ascii_digit() || c == '.' => self.number(start),
+            Some((start, c)) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(start, |c| c.is_alphanumeric() || c == '_');
+                if self.eat('(') {
+                    let operand = self.expr()?;
+                    self.close()?;
+                    let argument = self.function_argument(name)?;
+                    Ok(Expr::Function {
+                        tool: name.to_string(),
+                        argument,
+                        operand: Box::new(operand),
+                    })
+                } else {
+                    Ok(Expr::Parameter(name.to_string()))
+                }
+            }
+            Some((at, c)) => Err(self.unexpected(at, c)),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some((_, ')')) => {
+                self.chars.next();
+                Ok(())
+            }
+            Some((at, c)) => Err(self.unexpected(at, c)),
+            None => Err("expression: missing ')'".to_string()),
+        }
+    }
+
+    fn number(&mut self, start: usize) -> Result<Expr, String> {
+        let mut end = start;
+        let mut exponent = false;
+        while let Some(&(at, c)) = self.chars.peek() {
+            let sign_of_exponent = exponent
+                && matches!(c, '+' | '-')
+                && matches!(self.text[..at].chars().last(), Some('e' | 'E'));
+            let starts_exponent = !exponent
+                && matches!(c, 'e' | 'E')
+                && self.text[at + 1..]
+                    .trim_start_matches(['+', '-'])
+                    .starts_with(|c: char| c.is_ascii_digit());
+            if !(c.is_ascii_digit() || c == '.' || sign_of_exponent || starts_exponent) {
+                break;
+            }
+            exponent |= starts_exponent;
+            end = at + c.len_utf8();
+            self.chars.next();
+        }
+        let literal = &self.text[start..end];
+        literal
+            .parse()
+            .map(Expr::Number)
+            .map_err(|_| format!("expression: invalid number '{}'", literal))
+    }
+
+    fn take_while(&mut self, start: usize, accept: impl Fn(char) -> bool) -> &'a str {
+        let mut end = start;
+        while let Some((at, c)) = self.chars.next_if(|&(_, c)| accept(c)) {
+            end = at + c.len_utf8();
+        }
+        &self.text[start..end]
+    }
+
+    /// The argument a tool used as a function takes: its only required property
+    fn function_argument(&self, tool: &str) -> Result<String, String> {
+        let definition = self
+            .tools
+            .iter()
+            .find(|definition| definition["name"] == tool)
+            .ok_or_else(|| format!("expression: unknown function '{}'", tool))?;
+        match definition["inputSchema"]["required"].as_array() {
+            Some(required) if required.len() == 1 => {
+                Ok(required[0].as_str().unwrap_or_default().to_string())
+            }
+            _ => Err(format!(
+                "expression: '{}' takes more than one argument and cannot be used as a function",
+                tool
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::get_all_tools;
+
+    fn parse(text: &str) -> Result<Expr, String> {
+        let tools = get_all_tools();
+        Parser::new(text, tools.as_array().unwrap()).parse()
+    }
+
+    fn tool(toml: &str) -> Result<CompositeTool, String> {
+        let spec: CompositeSpec = toml::from_str(toml).map_err(|e| e.to_string())?;
+        let tools = get_all_tools();
+        CompositeTool::from_spec("custom", spec, tools.as_array().unwrap())
+    }
+
+    fn number(value: f64) -> Box<Expr> {
+        Box::new(Expr::Number(value))
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        assert_eq!(
+            parse("1 + 2 * 3").unwrap(),
+            Expr::Binary(
+                BinaryOp::Add,
+                number(1.0),
+                Box::new(Expr::Binary(BinaryOp::Multiply, number(2.0), number(3.0)))
+            )
+        );
+        // `^` is right-associative and binds tighter than unary minus
+        assert_eq!(
+            parse("-2 ^ 3 ^ 2").unwrap(),
+            Expr::Negate(Box::new(Expr::Binary(
+                BinaryOp::Power,
+                number(2.0),
+                Box::new(Expr::Binary(BinaryOp::Power, number(3.0), number(2.0)))
+            )))
+        );
+        assert_eq!(parse("1.5e-3").unwrap(), Expr::Number(0.0015));
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_eq!(
+            parse("sqrt(x)").unwrap(),
+            Expr::Function {
+                tool: "sqrt".to_string(),
+                argument: "number".to_string(),
+                operand: Box::new(Expr::Parameter("x".to_string())),
+            }
+        );
+        assert!(parse("nope(1)").unwrap_err().contains("unknown function"));
+        assert!(parse("divide(1)").unwrap_err().contains("more than one"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("1 +").unwrap_err().contains("unexpected end"));
+        assert!(parse("(1 + 2").unwrap_err().contains("missing ')'"));
+        assert!(parse("1 $ 2").unwrap_err().contains("position 3"));
+    }
+
+    #[test]
+    fn test_expression_tool() {
+        let bmi = tool(
+            r#"
+            description = "Body-mass index"
+            expression = "weight / height ^ 2"
+            parameters = { weight = "Weight in kg", height = "Height in m" }
+            "#,
+        )
+        .unwrap();
+
+        let definition = bmi.definition();
+        assert_eq!(
+            definition["inputSchema"]["required"],
+            json!(["height", "weight"])
+        );
+        assert_eq!(
+            definition["inputSchema"]["properties"]["weight"]["description"],
+            "Weight in kg"
+        );
+
+        let ctx = ToolContext::new();
+        let result = bmi
+            .execute(&json!({ "weight": 81, "height": 1.8 }), &ctx)
+            .unwrap();
+        assert!((result["result"].as_f64().unwrap() - 25.0).abs() < 1e-9);
+
+        let err = bmi.execute(&json!({ "weight": 81 }), &ctx).unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains("height"));
+        assert_eq!(err.data.unwrap()["argument"], "height");
+
+        let err = bmi
+            .execute(&json!({ "weight": "81", "height": 1.8 }), &ctx)
+            .unwrap_err();
+        let data = err.data.unwrap();
+        assert_eq!(data["argument"], "weight");
+        assert_eq!(data["received"], "81");
+        assert!(data["expected"].is_string());
+
+        let err = bmi
+            .execute(&json!({ "weight": 81, "height": 0 }), &ctx)
+            .unwrap_err();
+        assert!(err.message.contains("Step '/' (divide) failed"));
+    }
+
+    #[test]
+    fn test_pipeline_tool() {
+        let spread = tool(
+            r#"
+            description = "Spread of values relative to their mean"
+            parameters = { values = { type = "array", description = "Values" } }
+
+            [[steps]]
+            id = "mean"
+            tool = "mean"
+            arguments = { numbers = "${values}" }
+
+            [[steps]]
+            id = "deviation"
+            tool = "std_dev"
+            arguments = { numbers = "${values}", sample = true }
+
+            [[steps]]
+            id = "ratio"
+            tool = "divide"
+            arguments = { a = "${deviation.result}", b = "${mean.result}" }
+            "#,
+        )
+        .unwrap();
+
+        assert!(spread.definition().get("outputSchema").is_none());
+        let result = spread
+            .execute(&json!({ "values": [2, 4, 6] }), &ToolContext::new())
+            .unwrap();
+        assert!((result["result"].as_f64().unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_declarations() {
+        let err =
+            tool("description = 'x'\nexpression = 'a + b'\nparameters = { a = 'A' }").unwrap_err();
+        assert!(err.contains("unknown parameter 'b'"));
+
+        let err = tool("description = 'x'").unwrap_err();
+        assert!(err.contains("either an expression or steps"));
+
+        let err = tool(
+            "description = 'x'\n[[steps]]\nid = 's'\ntool = 'add'\narguments = { numbers = '${later}' }",
+        )
+        .unwrap_err();
+        assert!(err.contains("not a parameter or an earlier step"));
+
+        let err = tool("description = 'x'\n[[steps]]\nid = 's'\ntool = 'nope'").unwrap_err();
+        assert!(err.contains("unknown tool 'nope'"));
+
+        let spec: CompositeSpec = toml::from_str("description = 'x'\nexpression = '1'").unwrap();
+        let tools = get_all_tools();
+        let err = CompositeTool::from_spec("add", spec, tools.as_array().unwrap()).unwrap_err();
+        assert!(err.contains("conflicts"));
+    }
+}
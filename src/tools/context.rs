@@ -0,0 +1,347 @@
+use crate::config::Config;
+use crate::error::{McpError, McpResult};
+use crate::session::Session;
+use crate::utils::limits::Limits;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// A progress update from a running tool, sent to the client as
+/// `notifications/progress`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// Work done so far; increases with every update
+    pub progress: f64,
+    /// Total amount of work, if known
+    pub total: Option<f64>,
+    /// What the tool is doing
+    pub message: Option<String>,
+}
+
+/// Receives progress updates; set only when the client asked for them
+#[derive(Clone)]
+struct ProgressReporter(Arc<dyn Fn(Progress) + Send + Sync>);
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressReporter")
+    }
+}
+
+/// Per-call execution context passed to every tool.
+///
+/// Carries everything a call may need besides its arguments: the server
+/// configuration and the [`Limits`] derived from it, the calling session, a
+/// tracing span to log under, the call's time budget, a cancellation token
+/// and, when the client sent a progress token, a progress reporter. Tools are
+/// synchronous, so cancellation is cooperative: long-running tools (such as
+/// `batch_operations`) call [`ToolContext::check`] between steps and stop with a
+/// `resource_limit` error once the deadline passes or the client cancels.
+///
+/// Clones share the same cancellation token.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::tools::ToolContext;
+/// use std::time::Duration;
+///
+/// let ctx = ToolContext::new().with_timeout(Duration::from_secs(5));
+/// assert!(ctx.check().is_ok());
+///
+/// ctx.cancel();
+/// assert_eq!(ctx.check().unwrap_err().code, -32002);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToolContext {
+    limits: Limits,
+    session: Option<Arc<Session>>,
+    span: tracing::Span,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    cancellation: CancellationToken,
+    progress: Option<ProgressReporter>,
+    depth: usize,
+    within_batch: bool,
+}
+
+impl Default for ToolContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolContext {
+    /// Create a context with the default configuration and no session, with
+    /// no deadline, that is never cancelled
+    pub fn new() -> Self {
+        Self {
+            limits: Limits::default(),
+            session: None,
+            span: tracing::Span::none(),
+            timeout: None,
+            deadline: None,
+            cancellation: CancellationToken::new(),
+            progress: None,
+            depth: 0,
+            within_batch: false,
+        }
+    }
+
+    /// Run the call under `config` (e.g. the server's) instead of the defaults
+    pub fn with_config(mut self, config: Arc<Config>) -> Self {
+        self.limits = Limits::new(config);
+        self
+    }
+
+    /// Attribute the call to `session`
+    pub fn with_session(mut self, session: Arc<Session>) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Log the call's events under `span`
+    pub fn with_span(mut self, span: tracing::Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// The configuration the call runs under
+    pub fn config(&self) -> &Config {
+        self.limits.config()
+    }
+
+    /// The resource limits the call must stay within
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// The session that made the call, if it came from a client
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_deref()
+    }
+
+    /// Span for the call's log events (disabled unless set)
+    pub fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
+    /// Limit the call to `timeout` from now; a zero duration means no limit
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        if timeout.is_zero() {
+            self.timeout = None;
+            self.deadline = None;
+        } else {
+            self.timeout = Some(timeout);
+            self.deadline = Some(Instant::now() + timeout);
+        }
+        self
+    }
+
+    /// Use `token` to cancel the call (e.g. a child of the request's token)
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Send progress updates for this call to `reporter`
+    pub fn with_progress(mut self, reporter: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(ProgressReporter(Arc::new(reporter)));
+        self
+    }
+
+    /// The same context with progress reporting turned off, for nested calls
+    /// whose progress is already covered by the caller's updates
+    pub fn without_progress(&self) -> Self {
+        Self {
+            progress: None,
+            ..self.clone()
+        }
+    }
+
+    /// Context for the calls a tool makes to other tools (batch operations,
+    /// composite steps): one level deeper and without progress reporting, which
+    /// the calling tool's own updates already cover
+    pub fn nested(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self.without_progress()
+        }
+    }
+
+    /// Context for a tool registered at runtime: one level deeper, so the calls
+    /// it makes back into the registry count as nested, with progress
+    /// reporting kept for the tool itself
+    pub(crate) fn for_runtime_tool(&self) -> Self {
+        Self {
+            depth: self.depth + 1,
+            ..self.clone()
+        }
+    }
+
+    /// How many tool calls this call is nested in; 0 for a client's call
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Mark the call as part of a `batch_operations` call, along with every
+    /// call nested inside it
+    pub(crate) fn within_batch(mut self) -> Self {
+        self.within_batch = true;
+        self
+    }
+
+    /// Whether the call is part of a `batch_operations` call, at any depth
+    pub fn is_within_batch(&self) -> bool {
+        self.within_batch
+    }
+
+    /// Whether anyone is listening for progress updates
+    pub fn reports_progress(&self) -> bool {
+        self.progress.is_some()
+    }
+
+    /// Report that `progress` out of `total` units of work are done.
+    ///
+    /// Does nothing unless the client asked for progress.
+    pub fn report_progress(&self, progress: f64, total: Option<f64>, message: Option<String>) {
+        if let Some(ProgressReporter(reporter)) = &self.progress {
+            reporter(Progress {
+                progress,
+                total,
+                message,
+            });
+        }
+    }
+
+    /// Time left before the deadline, if there is one
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Cancel the call; subsequent [`check`](Self::check)s fail
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether the call has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Fail with a `resource_limit` error if the call was cancelled or ran out of time
+    pub fn check(&self) -> McpResult<()> {
+        if self.is_cancelled() {
+            return Err(McpError::resource_limit("Operation cancelled"));
+        }
+        if let (Some(timeout), Some(deadline)) = (self.timeout, self.deadline) {
+            if Instant::now() >= deadline {
+                return Err(McpError::resource_limit(format!(
+                    "Operation exceeded timeout of {:?}",
+                    timeout
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve once the call is cancelled or its deadline passes
+    pub async fn done(&self) {
+        match self.deadline {
+            Some(deadline) => {
+                tokio::select! {
+                    _ = self.cancellation.cancelled() => {}
+                    _ = tokio::time::sleep_until(deadline.into()) => {}
+                }
+            }
+            None => self.cancellation.cancelled().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_limits_by_default() {
+        let ctx = ToolContext::new();
+        assert!(ctx.check().is_ok());
+        assert!(ctx.remaining().is_none());
+    }
+
+    #[test]
+    fn test_deadline_exceeded() {
+        let ctx = ToolContext::new().with_timeout(Duration::from_millis(10));
+        assert!(ctx.check().is_ok());
+
+        std::thread::sleep(Duration::from_millis(20));
+        let err = ctx.check().unwrap_err();
+        assert_eq!(err.code, -32002);
+        assert!(err.message.contains("timeout"));
+    }
+
+    #[test]
+    fn test_zero_timeout_means_unlimited() {
+        let ctx = ToolContext::new().with_timeout(Duration::ZERO);
+        assert!(ctx.remaining().is_none());
+        assert!(ctx.check().is_ok());
+    }
+
+    #[test]
+    fn test_clones_share_cancellation() {
+        let parent = CancellationToken::new();
+        let ctx = ToolContext::new().with_cancellation(parent.child_token());
+        let clone = ctx.clone();
+
+        parent.cancel();
+        assert!(clone.is_cancelled());
+        assert!(ctx.check().unwrap_err().message.contains("cancelled"));
+    }
+
+    #[test]
+    fn test_progress_reporting() {
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let ctx = ToolContext::new().with_progress({
+            let updates = updates.clone();
+            move |progress| updates.lock().unwrap().push(progress)
+        });
+        assert!(ctx.reports_progress());
+        assert!(!ctx.without_progress().reports_progress());
+
+        ctx.report_progress(1.0, Some(2.0), Some("first".to_string()));
+        ctx.without_progress().report_progress(2.0, Some(2.0), None);
+        ToolContext::new().report_progress(1.0, None, None);
+
+        let updates = updates.lock().unwrap();
+        assert_eq!(
+            *updates,
+            vec![Progress {
+                progress: 1.0,
+                total: Some(2.0),
+                message: Some("first".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nested_calls() {
+        let ctx = ToolContext::new().with_progress(|_| {});
+        assert_eq!(ctx.depth(), 0);
+
+        let nested = ctx.nested().within_batch().nested();
+        assert_eq!(nested.depth(), 2);
+        assert!(nested.is_within_batch());
+        assert!(!nested.reports_progress());
+        assert!(!ctx.is_within_batch());
+    }
+
+    #[tokio::test]
+    async fn test_done_on_deadline() {
+        let ctx = ToolContext::new().with_timeout(Duration::from_millis(10));
+        ctx.done().await;
+        assert!(ctx.check().is_err());
+    }
+}
@@ -1,188 +1,226 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, get_number_opt, result_json, result_value};
-use serde_json::Value;
+use crate::math::equations::{self as math, QuadraticRoots};
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "quadratic_formula",
-            "description": "Solve quadratic equation ax² + bx + c = 0 using the quadratic formula",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "Coefficient of x²"},
-                    "b": {"type": "number", "description": "Coefficient of x"},
-                    "c": {"type": "number", "description": "Constant term"}
-                },
-                "required": ["a", "b", "c"]
-            }
-        }),
-        serde_json::json!({
-            "name": "distance_formula",
-            "description": "Calculate distance between two points (x1, y1) and (x2, y2)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "x1": {"type": "number", "description": "X coordinate of first point"},
-                    "y1": {"type": "number", "description": "Y coordinate of first point"},
-                    "x2": {"type": "number", "description": "X coordinate of second point"},
-                    "y2": {"type": "number", "description": "Y coordinate of second point"}
-                },
-                "required": ["x1", "y1", "x2", "y2"]
-            }
-        }),
-        serde_json::json!({
-            "name": "pythagorean_theorem",
-            "description": "Calculate the third side of a right triangle using Pythagorean theorem (a² + b² = c²)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "Length of side a"},
-                    "b": {"type": "number", "description": "Length of side b"},
-                    "c": {"type": "number", "description": "Length of hypotenuse (leave 0 to calculate)"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": "slope",
-            "description": "Calculate the slope of a line between two points: m = (y2 - y1) / (x2 - x1)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "x1": {"type": "number", "description": "X coordinate of first point"},
-                    "y1": {"type": "number", "description": "Y coordinate of first point"},
-                    "x2": {"type": "number", "description": "X coordinate of second point"},
-                    "y2": {"type": "number", "description": "Y coordinate of second point"}
-                },
-                "required": ["x1", "y1", "x2", "y2"]
-            }
-        }),
-        serde_json::json!({
-            "name": "midpoint",
-            "description": "Calculate the midpoint between two points",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "x1": {"type": "number", "description": "X coordinate of first point"},
-                    "y1": {"type": "number", "description": "Y coordinate of first point"},
-                    "x2": {"type": "number", "description": "X coordinate of second point"},
-                    "y2": {"type": "number", "description": "Y coordinate of second point"}
-                },
-                "required": ["x1", "y1", "x2", "y2"]
-            }
-        }),
-    ]
+pub use crate::math::equations::Point;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct QuadraticInput {
+    /// Coefficient of x²
+    pub a: f64,
+    /// Coefficient of x
+    pub b: f64,
+    /// Constant term
+    pub c: f64,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "quadratic_formula" => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            let c = get_number(arguments, "c")?;
-            Ok(quadratic_formula(a, b, c)?)
-        }
-        "distance_formula" => {
-            let x1 = get_number(arguments, "x1")?;
-            let y1 = get_number(arguments, "y1")?;
-            let x2 = get_number(arguments, "x2")?;
-            let y2 = get_number(arguments, "y2")?;
-            Ok(result_json(distance_formula(x1, y1, x2, y2)?))
-        }
-        "pythagorean_theorem" => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            let c = get_number_opt(arguments, "c");
-            Ok(result_json(pythagorean_theorem(a, b, c)?))
-        }
-        "slope" => {
-            let x1 = get_number(arguments, "x1")?;
-            let y1 = get_number(arguments, "y1")?;
-            let x2 = get_number(arguments, "x2")?;
-            let y2 = get_number(arguments, "y2")?;
-            Ok(result_json(slope(x1, y1, x2, y2)?))
-        }
-        "midpoint" => {
-            let x1 = get_number(arguments, "x1")?;
-            let y1 = get_number(arguments, "y1")?;
-            let x2 = get_number(arguments, "x2")?;
-            let y2 = get_number(arguments, "y2")?;
-            Ok(result_value(midpoint(x1, y1, x2, y2)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown equations tool: {}",
-            name
-        ))),
+/// Arguments for tools taking two points (x1, y1) and (x2, y2)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TwoPointsInput {
+    /// X coordinate of first point
+    pub x1: f64,
+    /// Y coordinate of first point
+    pub y1: f64,
+    /// X coordinate of second point
+    pub x2: f64,
+    /// Y coordinate of second point
+    pub y2: f64,
+}
+
+impl TwoPointsInput {
+    fn points(&self) -> (Point, Point) {
+        (Point::new(self.x1, self.y1), Point::new(self.x2, self.y2))
     }
 }
 
-fn quadratic_formula(a: f64, b: f64, c: f64) -> McpResult<Value> {
-    if a == 0.0 {
-        return Err(crate::error::McpError::validation_error(
-            "Coefficient 'a' cannot be zero for quadratic equation",
-        ));
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PythagoreanInput {
+    /// Length of side a
+    pub a: f64,
+    /// Length of side b
+    pub b: f64,
+    /// Length of hypotenuse (leave 0 to calculate)
+    pub c: Option<f64>,
+}
+
+/// Kind of real roots found by the quadratic formula
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RootType {
+    Distinct,
+    Repeated,
+}
+
+/// Output of the `quadratic_formula` tool
+///
+/// `roots` is null (with an explanatory `message`) when the discriminant is negative.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct QuadraticResult {
+    /// The two real roots, if any
+    pub roots: Option<[f64; 2]>,
+    /// Discriminant b² - 4ac
+    pub discriminant: f64,
+    /// Whether the roots are distinct or repeated
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub root_type: Option<RootType>,
+    /// Explanation when there are no real roots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+pub struct QuadraticFormula;
+pub struct DistanceFormula;
+pub struct PythagoreanTheorem;
+pub struct Slope;
+pub struct Midpoint;
+
+impl MathTool for QuadraticFormula {
+    const NAME: &'static str = "quadratic_formula";
+    const DESCRIPTION: &'static str =
+        "Solve quadratic equation ax² + bx + c = 0 using the quadratic formula";
+    const FORMULA: &'static str = "x = (-b ± √(b² - 4ac)) / 2a";
+    type Input = QuadraticInput;
+    type Output = QuadraticResult;
+
+    fn example() -> Value {
+        json!({ "a": 1, "b": -3, "c": 2 })
     }
-    let discriminant = b * b - 4.0 * a * c;
-    if discriminant < 0.0 {
-        Ok(serde_json::json!({
-            "roots": null,
-            "discriminant": discriminant,
-            "message": "No real roots (complex roots exist)"
-        }))
-    } else if discriminant == 0.0 {
-        let root = -b / (2.0 * a);
-        Ok(serde_json::json!({
-            "roots": [root, root],
-            "discriminant": discriminant,
-            "type": "repeated"
-        }))
-    } else {
-        let sqrt_disc = discriminant.sqrt();
-        let root1 = (-b + sqrt_disc) / (2.0 * a);
-        let root2 = (-b - sqrt_disc) / (2.0 * a);
-        Ok(serde_json::json!({
-            "roots": [root1, root2],
-            "discriminant": discriminant,
-            "type": "distinct"
-        }))
+
+    fn run(input: QuadraticInput) -> McpResult<QuadraticResult> {
+        let discriminant = math::discriminant(input.a, input.b, input.c);
+        let (roots, root_type) = match math::quadratic_formula(input.a, input.b, input.c)? {
+            QuadraticRoots::Distinct(root1, root2) => ([root1, root2], RootType::Distinct),
+            QuadraticRoots::Repeated(root) => ([root, root], RootType::Repeated),
+            QuadraticRoots::Complex => {
+                return Ok(QuadraticResult {
+                    roots: None,
+                    discriminant,
+                    root_type: None,
+                    message: Some("No real roots (complex roots exist)".to_string()),
+                })
+            }
+        };
+        Ok(QuadraticResult {
+            roots: Some(roots),
+            discriminant,
+            root_type: Some(root_type),
+            message: None,
+        })
     }
 }
 
-fn distance_formula(x1: f64, y1: f64, x2: f64, y2: f64) -> McpResult<f64> {
-    Ok(((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt())
+impl MathTool for DistanceFormula {
+    const NAME: &'static str = "distance_formula";
+    const DESCRIPTION: &'static str = "Calculate distance between two points (x1, y1) and (x2, y2)";
+    const FORMULA: &'static str = "d = √((x2 - x1)² + (y2 - y1)²)";
+    type Input = TwoPointsInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "x1": 0, "y1": 0, "x2": 3, "y2": 4 })
+    }
+
+    fn run(input: TwoPointsInput) -> McpResult<NumberResult> {
+        let (p1, p2) = input.points();
+        Ok(math::distance(p1, p2).into())
+    }
 }
 
-fn pythagorean_theorem(a: f64, b: f64, c: Option<f64>) -> McpResult<f64> {
-    if let Some(c_val) = c {
-        if c_val == 0.0 {
-            Ok((a * a + b * b).sqrt())
-        } else if a == 0.0 {
-            Ok((c_val * c_val - b * b).sqrt())
-        } else if b == 0.0 {
-            Ok((c_val * c_val - a * a).sqrt())
-        } else {
-            Err(crate::error::McpError::validation_error(
+impl MathTool for PythagoreanTheorem {
+    const NAME: &'static str = "pythagorean_theorem";
+    const DESCRIPTION: &'static str =
+        "Calculate the third side of a right triangle using Pythagorean theorem (a² + b² = c²)";
+    const FORMULA: &'static str = "a² + b² = c²";
+    type Input = PythagoreanInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "a": 3, "b": 4 })
+    }
+
+    fn run(input: PythagoreanInput) -> McpResult<NumberResult> {
+        let (a, b) = (input.a, input.b);
+        match input.c {
+            None | Some(0.0) => Ok(math::hypotenuse(a, b).into()),
+            Some(c) if a == 0.0 => Ok(math::leg(c, b).into()),
+            Some(c) if b == 0.0 => Ok(math::leg(c, a).into()),
+            Some(_) => Err(crate::error::McpError::validation_error(
                 "Cannot determine which side to calculate",
-            ))
+            )),
         }
-    } else {
-        Ok((a * a + b * b).sqrt())
     }
 }
 
-fn slope(x1: f64, y1: f64, x2: f64, y2: f64) -> McpResult<f64> {
-    if (x2 - x1).abs() < 1e-10 {
-        return Err(crate::error::McpError::validation_error(
-            "Slope is undefined (vertical line)",
-        ));
+impl MathTool for Slope {
+    const NAME: &'static str = "slope";
+    const DESCRIPTION: &'static str =
+        "Calculate the slope of a line between two points: m = (y2 - y1) / (x2 - x1)";
+    const FORMULA: &'static str = "m = (y2 - y1) / (x2 - x1)";
+    type Input = TwoPointsInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "x1": 1, "y1": 2, "x2": 3, "y2": 8 })
+    }
+
+    fn run(input: TwoPointsInput) -> McpResult<NumberResult> {
+        let (p1, p2) = input.points();
+        math::slope(p1, p2).map(NumberResult::from)
+    }
+}
+
+impl MathTool for Midpoint {
+    const NAME: &'static str = "midpoint";
+    const DESCRIPTION: &'static str = "Calculate the midpoint between two points";
+    const FORMULA: &'static str = "M = ((x1 + x2) / 2, (y1 + y2) / 2)";
+    type Input = TwoPointsInput;
+    type Output = Point;
+
+    fn example() -> Value {
+        json!({ "x1": 0, "y1": 0, "x2": 4, "y2": 6 })
+    }
+
+    fn run(input: TwoPointsInput) -> McpResult<Point> {
+        let (p1, p2) = input.points();
+        Ok(math::midpoint(p1, p2))
     }
-    Ok((y2 - y1) / (x2 - x1))
 }
 
-fn midpoint(x1: f64, y1: f64, x2: f64, y2: f64) -> McpResult<Value> {
-    Ok(serde_json::json!({
-        "x": (x1 + x2) / 2.0,
-        "y": (y1 + y2) / 2.0
-    }))
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        QuadraticFormula::definition(),
+        DistanceFormula::definition(),
+        PythagoreanTheorem::definition(),
+        Slope::definition(),
+        Midpoint::definition(),
+    ]
+}
+
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        QuadraticFormula::documentation(),
+        DistanceFormula::documentation(),
+        PythagoreanTheorem::documentation(),
+        Slope::documentation(),
+        Midpoint::documentation(),
+    ]
+}
+
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        QuadraticFormula::NAME => QuadraticFormula::call_with_context(arguments, ctx),
+        DistanceFormula::NAME => DistanceFormula::call_with_context(arguments, ctx),
+        PythagoreanTheorem::NAME => PythagoreanTheorem::call_with_context(arguments, ctx),
+        Slope::NAME => Slope::call_with_context(arguments, ctx),
+        Midpoint::NAME => Midpoint::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown equations tool: {}",
+            name
+        ))),
+    }
 }
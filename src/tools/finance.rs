@@ -1,113 +1,164 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, get_number_opt, result_json, result_value};
-use serde_json::Value;
+use crate::math::finance as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use crate::utils::args::Percent;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "compound_interest",
-            "description": "Calculate compound interest: A = P(1 + r/n)^(nt)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "principal": {"type": "number", "description": "Principal amount (P)"},
-                    "rate": {"type": "number", "description": "Annual interest rate (as decimal, e.g., 0.05 for 5%)"},
-                    "time": {"type": "number", "description": "Time in years (t)"},
-                    "compounds_per_year": {"type": "number", "description": "Number of times compounded per year (n), default 1"}
-                },
-                "required": ["principal", "rate", "time"]
-            }
-        }),
-        serde_json::json!({
-            "name": "simple_interest",
-            "description": "Calculate simple interest: I = P × r × t",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "principal": {"type": "number", "description": "Principal amount (P)"},
-                    "rate": {"type": "number", "description": "Annual interest rate (as decimal)"},
-                    "time": {"type": "number", "description": "Time in years (t)"}
-                },
-                "required": ["principal", "rate", "time"]
-            }
-        }),
-        serde_json::json!({
-            "name": "percentage",
-            "description": "Calculate percentage: (part / whole) × 100 or find part/whole given percentage",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "part": {"type": "number", "description": "Part value (leave null if calculating)"},
-                    "whole": {"type": "number", "description": "Whole value"},
-                    "percent": {"type": "number", "description": "Percentage value (leave null if calculating)"}
-                },
-                "required": ["whole"]
-            }
-        }),
-    ]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CompoundInterestInput {
+    /// Principal amount (P)
+    pub principal: f64,
+    /// Annual interest rate (as decimal, e.g., 0.05 for 5%)
+    pub rate: f64,
+    /// Time in years (t)
+    pub time: f64,
+    /// Number of times compounded per year (n), default 1
+    pub compounds_per_year: Option<f64>,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "compound_interest" => {
-            let principal = get_number(arguments, "principal")?;
-            let rate = get_number(arguments, "rate")?;
-            let time = get_number(arguments, "time")?;
-            let compounds_per_year = get_number_opt(arguments, "compounds_per_year");
-            Ok(result_json(compound_interest(
-                principal,
-                rate,
-                time,
-                compounds_per_year,
-            )?))
-        }
-        "simple_interest" => {
-            let principal = get_number(arguments, "principal")?;
-            let rate = get_number(arguments, "rate")?;
-            let time = get_number(arguments, "time")?;
-            Ok(result_json(simple_interest(principal, rate, time)?))
-        }
-        "percentage" => {
-            let part = get_number_opt(arguments, "part");
-            let whole = get_number(arguments, "whole")?;
-            let percent = get_number_opt(arguments, "percent");
-            Ok(result_value(percentage(part, whole, percent)?))
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SimpleInterestInput {
+    /// Principal amount (P)
+    pub principal: f64,
+    /// Annual interest rate (as decimal)
+    pub rate: f64,
+    /// Time in years (t)
+    pub time: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PercentageInput {
+    /// Part value (omit if calculating)
+    pub part: Option<f64>,
+    /// Whole value
+    pub whole: f64,
+    /// Percentage value, e.g. 5 for 5% (omit if calculating)
+    pub percent: Option<Percent>,
+}
+
+/// Output of the `percentage` tool; the shape depends on which inputs were given
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PercentageResult {
+    /// `part` given: what percentage of `whole` it is
+    Percentage { percentage: f64 },
+    /// `percent` given: the corresponding part of `whole`
+    Part { part: f64 },
+    /// Both given: whether they agree
+    Check {
+        calculated_percentage: f64,
+        given_percentage: f64,
+        #[serde(rename = "match")]
+        matches: bool,
+    },
+}
+
+pub struct CompoundInterest;
+pub struct SimpleInterest;
+pub struct Percentage;
+
+impl MathTool for CompoundInterest {
+    const NAME: &'static str = "compound_interest";
+    const DESCRIPTION: &'static str = "Calculate compound interest: A = P(1 + r/n)^(nt)";
+    const FORMULA: &'static str = "A = P(1 + r/n)^(nt)";
+    type Input = CompoundInterestInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "principal": 1000, "rate": 0.05, "time": 10, "compounds_per_year": 12 })
+    }
+
+    fn run(input: CompoundInterestInput) -> McpResult<NumberResult> {
+        Ok(math::compound_interest(
+            input.principal,
+            input.rate,
+            input.time,
+            input.compounds_per_year.unwrap_or(1.0),
+        )
+        .into())
+    }
+}
+
+impl MathTool for SimpleInterest {
+    const NAME: &'static str = "simple_interest";
+    const DESCRIPTION: &'static str = "Calculate simple interest: I = P × r × t";
+    const FORMULA: &'static str = "I = P × r × t";
+    type Input = SimpleInterestInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "principal": 1000, "rate": 0.05, "time": 3 })
+    }
+
+    fn run(input: SimpleInterestInput) -> McpResult<NumberResult> {
+        Ok(math::simple_interest(input.principal, input.rate, input.time).into())
+    }
+}
+
+impl MathTool for Percentage {
+    const NAME: &'static str = "percentage";
+    const DESCRIPTION: &'static str =
+        "Calculate percentage: (part / whole) × 100 or find part/whole given percentage";
+    const FORMULA: &'static str = "percentage = (part / whole) × 100";
+    type Input = PercentageInput;
+    type Output = PercentageResult;
+
+    fn example() -> Value {
+        json!({ "part": 45, "whole": 60 })
+    }
+
+    fn run(input: PercentageInput) -> McpResult<PercentageResult> {
+        let whole = input.whole;
+        match (input.part, input.percent.map(|Percent(percent)| percent)) {
+            (Some(part), None) => Ok(PercentageResult::Percentage {
+                percentage: math::percentage(part, whole),
+            }),
+            (None, Some(percent)) => Ok(PercentageResult::Part {
+                part: math::percent_of(percent, whole),
+            }),
+            (Some(part), Some(percent)) => {
+                let calculated = math::percentage(part, whole);
+                Ok(PercentageResult::Check {
+                    calculated_percentage: calculated,
+                    given_percentage: percent,
+                    matches: (calculated - percent).abs() < 0.0001,
+                })
+            }
+            (None, None) => Err(crate::error::McpError::validation_error(
+                "Must provide either 'part' or 'percent'",
+            )),
         }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown finance tool: {}",
-            name
-        ))),
     }
 }
 
-fn compound_interest(
-    principal: f64,
-    rate: f64,
-    time: f64,
-    compounds_per_year: Option<f64>,
-) -> McpResult<f64> {
-    let n = compounds_per_year.unwrap_or(1.0);
-    Ok(principal * (1.0 + rate / n).powf(n * time))
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        CompoundInterest::definition(),
+        SimpleInterest::definition(),
+        Percentage::definition(),
+    ]
 }
 
-fn simple_interest(principal: f64, rate: f64, time: f64) -> McpResult<f64> {
-    Ok(principal * rate * time)
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        CompoundInterest::documentation(),
+        SimpleInterest::documentation(),
+        Percentage::documentation(),
+    ]
 }
 
-fn percentage(part: Option<f64>, whole: f64, percent: Option<f64>) -> McpResult<Value> {
-    match (part, percent) {
-        (Some(p), None) => Ok(serde_json::json!({ "percentage": (p / whole) * 100.0 })),
-        (None, Some(perc)) => Ok(serde_json::json!({ "part": (perc / 100.0) * whole })),
-        (Some(p), Some(perc)) => {
-            let calculated_percent = (p / whole) * 100.0;
-            Ok(serde_json::json!({
-                "calculated_percentage": calculated_percent,
-                "given_percentage": perc,
-                "match": (calculated_percent - perc).abs() < 0.0001
-            }))
-        }
-        (None, None) => Err(crate::error::McpError::validation_error(
-            "Must provide either 'part' or 'percent'",
-        )),
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        CompoundInterest::NAME => CompoundInterest::call_with_context(arguments, ctx),
+        SimpleInterest::NAME => SimpleInterest::call_with_context(arguments, ctx),
+        Percentage::NAME => Percentage::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown finance tool: {}",
+            name
+        ))),
     }
 }
@@ -1,187 +1,245 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, result_json};
-use serde_json::Value;
+use crate::math::geometry as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "area_circle",
-            "description": "Calculate the area of a circle",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "radius": {"type": "number", "description": "Radius of the circle"}
-                },
-                "required": ["radius"]
-            }
-        }),
-        serde_json::json!({
-            "name": "area_rectangle",
-            "description": "Calculate the area of a rectangle",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "length": {"type": "number", "description": "Length of rectangle"},
-                    "width": {"type": "number", "description": "Width of rectangle"}
-                },
-                "required": ["length", "width"]
-            }
-        }),
-        serde_json::json!({
-            "name": "area_triangle",
-            "description": "Calculate the area of a triangle",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "base": {"type": "number", "description": "Base of triangle"},
-                    "height": {"type": "number", "description": "Height of triangle"}
-                },
-                "required": ["base", "height"]
-            }
-        }),
-        serde_json::json!({
-            "name": "area_trapezoid",
-            "description": "Calculate the area of a trapezoid",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "base1": {"type": "number", "description": "First base length"},
-                    "base2": {"type": "number", "description": "Second base length"},
-                    "height": {"type": "number", "description": "Height of trapezoid"}
-                },
-                "required": ["base1", "base2", "height"]
-            }
-        }),
-        serde_json::json!({
-            "name": "volume_sphere",
-            "description": "Calculate the volume of a sphere",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "radius": {"type": "number", "description": "Radius of sphere"}
-                },
-                "required": ["radius"]
-            }
-        }),
-        serde_json::json!({
-            "name": "volume_cylinder",
-            "description": "Calculate the volume of a cylinder",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "radius": {"type": "number", "description": "Radius of base"},
-                    "height": {"type": "number", "description": "Height of cylinder"}
-                },
-                "required": ["radius", "height"]
-            }
-        }),
-        serde_json::json!({
-            "name": "volume_cone",
-            "description": "Calculate the volume of a cone",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "radius": {"type": "number", "description": "Radius of base"},
-                    "height": {"type": "number", "description": "Height of cone"}
-                },
-                "required": ["radius", "height"]
-            }
-        }),
-        serde_json::json!({
-            "name": "volume_rectangular_prism",
-            "description": "Calculate the volume of a rectangular prism (box)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "length": {"type": "number", "description": "Length"},
-                    "width": {"type": "number", "description": "Width"},
-                    "height": {"type": "number", "description": "Height"}
-                },
-                "required": ["length", "width", "height"]
-            }
-        }),
-    ]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CircleInput {
+    /// Radius of the circle
+    pub radius: f64,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "area_circle" => {
-            let radius = get_number(arguments, "radius")?;
-            Ok(result_json(area_circle(radius)?))
-        }
-        "area_rectangle" => {
-            let length = get_number(arguments, "length")?;
-            let width = get_number(arguments, "width")?;
-            Ok(result_json(area_rectangle(length, width)?))
-        }
-        "area_triangle" => {
-            let base = get_number(arguments, "base")?;
-            let height = get_number(arguments, "height")?;
-            Ok(result_json(area_triangle(base, height)?))
-        }
-        "area_trapezoid" => {
-            let base1 = get_number(arguments, "base1")?;
-            let base2 = get_number(arguments, "base2")?;
-            let height = get_number(arguments, "height")?;
-            Ok(result_json(area_trapezoid(base1, base2, height)?))
-        }
-        "volume_sphere" => {
-            let radius = get_number(arguments, "radius")?;
-            Ok(result_json(volume_sphere(radius)?))
-        }
-        "volume_cylinder" => {
-            let radius = get_number(arguments, "radius")?;
-            let height = get_number(arguments, "height")?;
-            Ok(result_json(volume_cylinder(radius, height)?))
-        }
-        "volume_cone" => {
-            let radius = get_number(arguments, "radius")?;
-            let height = get_number(arguments, "height")?;
-            Ok(result_json(volume_cone(radius, height)?))
-        }
-        "volume_rectangular_prism" => {
-            let length = get_number(arguments, "length")?;
-            let width = get_number(arguments, "width")?;
-            let height = get_number(arguments, "height")?;
-            Ok(result_json(volume_rectangular_prism(
-                length, width, height,
-            )?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown geometry tool: {}",
-            name
-        ))),
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RectangleInput {
+    /// Length of rectangle
+    pub length: f64,
+    /// Width of rectangle
+    pub width: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TriangleInput {
+    /// Base of triangle
+    pub base: f64,
+    /// Height of triangle
+    pub height: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TrapezoidInput {
+    /// First base length
+    pub base1: f64,
+    /// Second base length
+    pub base2: f64,
+    /// Height of trapezoid
+    pub height: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SphereInput {
+    /// Radius of sphere
+    pub radius: f64,
+}
+
+/// Arguments for solids with a circular base (`volume_cylinder`, `volume_cone`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SolidInput {
+    /// Radius of base
+    pub radius: f64,
+    /// Height of the solid
+    pub height: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PrismInput {
+    /// Length
+    pub length: f64,
+    /// Width
+    pub width: f64,
+    /// Height
+    pub height: f64,
+}
+
+pub struct AreaCircle;
+pub struct AreaRectangle;
+pub struct AreaTriangle;
+pub struct AreaTrapezoid;
+pub struct VolumeSphere;
+pub struct VolumeCylinder;
+pub struct VolumeCone;
+pub struct VolumeRectangularPrism;
+
+impl MathTool for AreaCircle {
+    const NAME: &'static str = "area_circle";
+    const DESCRIPTION: &'static str = "Calculate the area of a circle";
+    const FORMULA: &'static str = "A = πr²";
+    type Input = CircleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "radius": 3 })
+    }
+
+    fn run(input: CircleInput) -> McpResult<NumberResult> {
+        Ok(math::area_circle(input.radius).into())
     }
 }
 
-fn area_circle(radius: f64) -> McpResult<f64> {
-    Ok(std::f64::consts::PI * radius * radius)
+impl MathTool for AreaRectangle {
+    const NAME: &'static str = "area_rectangle";
+    const DESCRIPTION: &'static str = "Calculate the area of a rectangle";
+    const FORMULA: &'static str = "A = length × width";
+    type Input = RectangleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "length": 5, "width": 3 })
+    }
+
+    fn run(input: RectangleInput) -> McpResult<NumberResult> {
+        Ok(math::area_rectangle(input.length, input.width).into())
+    }
 }
 
-fn area_rectangle(length: f64, width: f64) -> McpResult<f64> {
-    Ok(length * width)
+impl MathTool for AreaTriangle {
+    const NAME: &'static str = "area_triangle";
+    const DESCRIPTION: &'static str = "Calculate the area of a triangle";
+    const FORMULA: &'static str = "A = ½ × base × height";
+    type Input = TriangleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "base": 6, "height": 4 })
+    }
+
+    fn run(input: TriangleInput) -> McpResult<NumberResult> {
+        Ok(math::area_triangle(input.base, input.height).into())
+    }
 }
 
-fn area_triangle(base: f64, height: f64) -> McpResult<f64> {
-    Ok(0.5 * base * height)
+impl MathTool for AreaTrapezoid {
+    const NAME: &'static str = "area_trapezoid";
+    const DESCRIPTION: &'static str = "Calculate the area of a trapezoid";
+    const FORMULA: &'static str = "A = ½ × (base1 + base2) × height";
+    type Input = TrapezoidInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "base1": 4, "base2": 6, "height": 3 })
+    }
+
+    fn run(input: TrapezoidInput) -> McpResult<NumberResult> {
+        Ok(math::area_trapezoid(input.base1, input.base2, input.height).into())
+    }
 }
 
-fn area_trapezoid(base1: f64, base2: f64, height: f64) -> McpResult<f64> {
-    Ok(0.5 * (base1 + base2) * height)
+impl MathTool for VolumeSphere {
+    const NAME: &'static str = "volume_sphere";
+    const DESCRIPTION: &'static str = "Calculate the volume of a sphere";
+    const FORMULA: &'static str = "V = ⁴⁄₃πr³";
+    type Input = SphereInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "radius": 2 })
+    }
+
+    fn run(input: SphereInput) -> McpResult<NumberResult> {
+        Ok(math::volume_sphere(input.radius).into())
+    }
 }
 
-fn volume_sphere(radius: f64) -> McpResult<f64> {
-    Ok((4.0 / 3.0) * std::f64::consts::PI * radius.powi(3))
+impl MathTool for VolumeCylinder {
+    const NAME: &'static str = "volume_cylinder";
+    const DESCRIPTION: &'static str = "Calculate the volume of a cylinder";
+    const FORMULA: &'static str = "V = πr²h";
+    type Input = SolidInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "radius": 2, "height": 5 })
+    }
+
+    fn run(input: SolidInput) -> McpResult<NumberResult> {
+        Ok(math::volume_cylinder(input.radius, input.height).into())
+    }
 }
 
-fn volume_cylinder(radius: f64, height: f64) -> McpResult<f64> {
-    Ok(std::f64::consts::PI * radius * radius * height)
+impl MathTool for VolumeCone {
+    const NAME: &'static str = "volume_cone";
+    const DESCRIPTION: &'static str = "Calculate the volume of a cone";
+    const FORMULA: &'static str = "V = ⅓πr²h";
+    type Input = SolidInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "radius": 3, "height": 4 })
+    }
+
+    fn run(input: SolidInput) -> McpResult<NumberResult> {
+        Ok(math::volume_cone(input.radius, input.height).into())
+    }
 }
 
-fn volume_cone(radius: f64, height: f64) -> McpResult<f64> {
-    Ok((1.0 / 3.0) * std::f64::consts::PI * radius * radius * height)
+impl MathTool for VolumeRectangularPrism {
+    const NAME: &'static str = "volume_rectangular_prism";
+    const DESCRIPTION: &'static str = "Calculate the volume of a rectangular prism (box)";
+    const FORMULA: &'static str = "V = length × width × height";
+    type Input = PrismInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "length": 2, "width": 3, "height": 4 })
+    }
+
+    fn run(input: PrismInput) -> McpResult<NumberResult> {
+        Ok(math::volume_rectangular_prism(input.length, input.width, input.height).into())
+    }
+}
+
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        AreaCircle::definition(),
+        AreaRectangle::definition(),
+        AreaTriangle::definition(),
+        AreaTrapezoid::definition(),
+        VolumeSphere::definition(),
+        VolumeCylinder::definition(),
+        VolumeCone::definition(),
+        VolumeRectangularPrism::definition(),
+    ]
+}
+
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        AreaCircle::documentation(),
+        AreaRectangle::documentation(),
+        AreaTriangle::documentation(),
+        AreaTrapezoid::documentation(),
+        VolumeSphere::documentation(),
+        VolumeCylinder::documentation(),
+        VolumeCone::documentation(),
+        VolumeRectangularPrism::documentation(),
+    ]
 }
 
-fn volume_rectangular_prism(length: f64, width: f64, height: f64) -> McpResult<f64> {
-    Ok(length * width * height)
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        AreaCircle::NAME => AreaCircle::call_with_context(arguments, ctx),
+        AreaRectangle::NAME => AreaRectangle::call_with_context(arguments, ctx),
+        AreaTriangle::NAME => AreaTriangle::call_with_context(arguments, ctx),
+        AreaTrapezoid::NAME => AreaTrapezoid::call_with_context(arguments, ctx),
+        VolumeSphere::NAME => VolumeSphere::call_with_context(arguments, ctx),
+        VolumeCylinder::NAME => VolumeCylinder::call_with_context(arguments, ctx),
+        VolumeCone::NAME => VolumeCone::call_with_context(arguments, ctx),
+        VolumeRectangularPrism::NAME => VolumeRectangularPrism::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown geometry tool: {}",
+            name
+        ))),
+    }
 }
@@ -2,15 +2,23 @@ pub mod advanced;
 pub mod algebra;
 pub mod basic_math;
 pub mod batch;
+pub mod cache;
 pub mod combinatorics;
+pub mod composite;
+pub mod context;
 pub mod equations;
 pub mod finance;
 pub mod geometry;
+pub mod references;
 pub mod registry;
 pub mod statistics;
 pub mod traits;
 pub mod trigonometry;
 
 // Re-export for convenience
-pub use registry::{execute_tool, get_all_tools, DefaultToolRegistry};
-pub use traits::ToolRegistry;
+pub use cache::CacheStats;
+pub use context::{Progress, ToolContext};
+pub use registry::{
+    execute_tool, get_all_tools, DefaultToolRegistry, RuntimeExecutor, ToolCategory, CATEGORIES,
+};
+pub use traits::{MathTool, NumberResult, ToolRegistry};
@@ -0,0 +1,114 @@
+use crate::error::{McpError, McpResult};
+use serde_json::{Map, Value};
+
+/// The path a value refers to, if it is a reference.
+///
+/// A reference is either the string `"${op1.result}"` or the object
+/// `{"$ref": "op1.result"}`. The path is a name followed by object keys or
+/// array indices, separated by dots.
+pub fn reference(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(text) => text.strip_prefix("${")?.strip_suffix('}'),
+        Value::Object(fields) if fields.len() == 1 => fields.get("$ref")?.as_str(),
+        _ => None,
+    }
+}
+
+/// The name a path starts with (`op1` in `op1.result`)
+pub fn root(path: &str) -> &str {
+    path.split('.').next().unwrap_or_default()
+}
+
+/// Every reference path in `value`, at any depth, in document order
+pub fn references(value: &Value) -> Vec<&str> {
+    let mut paths = Vec::new();
+    collect(value, &mut paths);
+    paths
+}
+
+fn collect<'a>(value: &'a Value, paths: &mut Vec<&'a str>) {
+    if let Some(path) = reference(value) {
+        paths.push(path);
+        return;
+    }
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect(item, paths)),
+        Value::Object(fields) => fields.values().for_each(|field| collect(field, paths)),
+        _ => {}
+    }
+}
+
+/// Replace every reference in `value` with the value it refers to in `scope`.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::tools::references::substitute;
+/// use serde_json::json;
+///
+/// let scope = json!({ "op1": { "result": 4.0 } });
+/// let arguments = json!({ "numbers": [{ "$ref": "op1.result" }, "${op1.result}", 1] });
+/// let resolved = substitute(&arguments, scope.as_object().unwrap()).unwrap();
+/// assert_eq!(resolved, json!({ "numbers": [4.0, 4.0, 1] }));
+/// ```
+pub fn substitute(value: &Value, scope: &Map<String, Value>) -> McpResult<Value> {
+    if let Some(path) = reference(value) {
+        return resolve(path, scope);
+    }
+    match value {
+        Value::Array(items) => items.iter().map(|item| substitute(item, scope)).collect(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, field)| Ok((key.clone(), substitute(field, scope)?)))
+            .collect::<McpResult<Map<_, _>>>()
+            .map(Value::Object),
+        _ => Ok(value.clone()),
+    }
+}
+
+/// Look up `name.field.0...` in `scope`
+pub fn resolve(path: &str, scope: &Map<String, Value>) -> McpResult<Value> {
+    let mut segments = path.split('.');
+    let mut value = scope.get(segments.next().unwrap_or_default());
+    for segment in segments {
+        value = value.and_then(|value| match value {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => value.get(segment),
+        });
+    }
+    value
+        .cloned()
+        .ok_or_else(|| McpError::tool_error(format!("Reference '{}' not found", path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_reference_forms() {
+        assert_eq!(reference(&json!("${a.result}")), Some("a.result"));
+        assert_eq!(reference(&json!({ "$ref": "a" })), Some("a"));
+        assert_eq!(reference(&json!("a.result")), None);
+        assert_eq!(reference(&json!({ "$ref": "a", "other": 1 })), None);
+        assert_eq!(root("a.result.0"), "a");
+    }
+
+    #[test]
+    fn test_references_at_any_depth() {
+        let value = json!({ "x": "${a}", "y": [1, { "$ref": "b.result" }], "z": { "w": "${c}" } });
+        let mut paths = references(&value);
+        paths.sort();
+        assert_eq!(paths, vec!["a", "b.result", "c"]);
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let scope = json!({ "q": { "roots": [1.5, -2.0] } });
+        let scope = scope.as_object().unwrap();
+        assert_eq!(resolve("q.roots.1", scope).unwrap(), json!(-2.0));
+        let err = resolve("q.missing", scope).unwrap_err();
+        assert!(err.message.contains("'q.missing' not found"));
+    }
+}
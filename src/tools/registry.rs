@@ -1,151 +1,510 @@
+use crate::config::Config;
 use crate::error::{McpError, McpResult};
-use crate::tools::traits::ToolRegistry;
+use crate::metrics;
+use crate::tools::cache::{CacheStats, ResultCache};
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, ToolRegistry};
+use crate::utils::args::coerce_numbers;
+use crate::utils::validation::validate_arguments;
 use once_cell::sync::Lazy;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tokio::sync::watch;
 
 use super::{
     advanced, algebra, basic_math, batch, combinatorics, equations, finance, geometry, statistics,
     trigonometry,
 };
 
-/// Tool executor function type
-type ToolExecutor = fn(&str, &Value) -> McpResult<Value>;
+/// Executes the tools of one category: `(tool name, arguments, context)`
+pub type ToolExecutor = fn(&str, &Value, &ToolContext) -> McpResult<Value>;
+
+/// Executes a tool registered at runtime: `(arguments, context)`
+pub type RuntimeExecutor = Arc<dyn Fn(&Value, &ToolContext) -> McpResult<Value> + Send + Sync>;
 
 /// Static tool registry with HashMap for O(1) lookup
-static TOOL_REGISTRY: Lazy<HashMap<&'static str, ToolExecutor>> = Lazy::new(|| {
-    let mut registry = HashMap::new();
-
-    // Register basic math tools using const strings from modules
-    registry.insert(basic_math::TOOL_ADD, basic_math::execute as ToolExecutor);
-    registry.insert(
-        basic_math::TOOL_SUBTRACT,
-        basic_math::execute as ToolExecutor,
-    );
-    registry.insert(
-        basic_math::TOOL_MULTIPLY,
-        basic_math::execute as ToolExecutor,
-    );
-    registry.insert(basic_math::TOOL_DIVIDE, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_POWER, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_SQRT, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_ABS, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_ROUND, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_FLOOR, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_CEIL, basic_math::execute as ToolExecutor);
-    registry.insert(basic_math::TOOL_MODULO, basic_math::execute as ToolExecutor);
-
-    // Register batch operations tool
-    registry.insert(batch::TOOL_BATCH, batch::execute as ToolExecutor);
-
-    // Register other tool categories (they still use the old approach temporarily)
-    register_tools_legacy(
-        &mut registry,
-        algebra::get_tool_definitions(),
-        algebra::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        statistics::get_tool_definitions(),
-        statistics::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        geometry::get_tool_definitions(),
-        geometry::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        equations::get_tool_definitions(),
-        equations::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        trigonometry::get_tool_definitions(),
-        trigonometry::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        finance::get_tool_definitions(),
-        finance::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        combinatorics::get_tool_definitions(),
-        combinatorics::execute,
-    );
-    register_tools_legacy(
-        &mut registry,
-        advanced::get_tool_definitions(),
-        advanced::execute,
-    );
+static TOOL_REGISTRY: Lazy<HashMap<String, ToolExecutor>> = Lazy::new(|| {
+    CATEGORIES
+        .iter()
+        .flat_map(|category| {
+            (category.definitions)()
+                .into_iter()
+                .filter_map(move |definition| {
+                    let name = definition.get("name")?.as_str()?.to_string();
+                    Some((name, category.execute))
+                })
+        })
+        .collect()
+});
+
+/// A group of related tools, backed by one module
+pub struct ToolCategory {
+    /// Category name (the module name, e.g. `finance`)
+    pub name: &'static str,
+    /// What the tools in this category cover
+    pub description: &'static str,
+    /// The module's `get_tool_definitions`
+    pub definitions: fn() -> Vec<Value>,
+    /// The module's `get_tool_documentation`
+    pub documentation: fn() -> Vec<Value>,
+    /// The module's `execute`
+    pub execute: ToolExecutor,
+}
+
+/// All tool categories, in `tools/list` order
+pub static CATEGORIES: &[ToolCategory] = &[
+    ToolCategory {
+        name: "basic_math",
+        description: "Arithmetic, powers, roots, rounding and remainders",
+        definitions: basic_math::get_tool_definitions,
+        documentation: basic_math::get_tool_documentation,
+        execute: basic_math::execute,
+    },
+    ToolCategory {
+        name: "batch",
+        description: "Run several tool calls in a single request",
+        definitions: batch::get_tool_definitions,
+        documentation: batch::get_tool_documentation,
+        execute: batch::execute,
+    },
+    ToolCategory {
+        name: "algebra",
+        description: "Greatest common divisor, least common multiple and factorials",
+        definitions: algebra::get_tool_definitions,
+        documentation: algebra::get_tool_documentation,
+        execute: algebra::execute,
+    },
+    ToolCategory {
+        name: "statistics",
+        description: "Descriptive statistics over lists of numbers",
+        definitions: statistics::get_tool_definitions,
+        documentation: statistics::get_tool_documentation,
+        execute: statistics::execute,
+    },
+    ToolCategory {
+        name: "geometry",
+        description: "Areas of plane figures and volumes of solids",
+        definitions: geometry::get_tool_definitions,
+        documentation: geometry::get_tool_documentation,
+        execute: geometry::execute,
+    },
+    ToolCategory {
+        name: "equations",
+        description: "Quadratic equations and coordinate geometry",
+        definitions: equations::get_tool_definitions,
+        documentation: equations::get_tool_documentation,
+        execute: equations::execute,
+    },
+    ToolCategory {
+        name: "trigonometry",
+        description: "Trigonometric functions, their inverses, triangle laws and angle conversion",
+        definitions: trigonometry::get_tool_definitions,
+        documentation: trigonometry::get_tool_documentation,
+        execute: trigonometry::execute,
+    },
+    ToolCategory {
+        name: "finance",
+        description: "Interest and percentage calculations",
+        definitions: finance::get_tool_definitions,
+        documentation: finance::get_tool_documentation,
+        execute: finance::execute,
+    },
+    ToolCategory {
+        name: "combinatorics",
+        description: "Permutations and combinations",
+        definitions: combinatorics::get_tool_definitions,
+        documentation: combinatorics::get_tool_documentation,
+        execute: combinatorics::execute,
+    },
+    ToolCategory {
+        name: "advanced",
+        description: "Exponential growth and logarithms",
+        definitions: advanced::get_tool_definitions,
+        documentation: advanced::get_tool_documentation,
+        execute: advanced::execute,
+    },
+];
+
+/// Look up a tool category by name
+pub fn category(name: &str) -> Option<&'static ToolCategory> {
+    CATEGORIES.iter().find(|category| category.name == name)
+}
 
-    registry
+/// Category name of every tool
+static TOOL_CATEGORIES: Lazy<HashMap<String, &'static ToolCategory>> = Lazy::new(|| {
+    CATEGORIES
+        .iter()
+        .flat_map(|category| {
+            (category.definitions)()
+                .into_iter()
+                .filter_map(move |definition| {
+                    let name = definition.get("name")?.as_str()?.to_string();
+                    Some((name, category))
+                })
+        })
+        .collect()
 });
 
+/// Look up the category a tool belongs to
+pub fn tool_category(tool: &str) -> Option<&'static ToolCategory> {
+    TOOL_CATEGORIES.get(tool).copied()
+}
+
 /// Static tool definitions cache - now using Arc to avoid cloning
 static TOOL_DEFINITIONS: Lazy<Arc<Value>> = Lazy::new(|| {
-    let mut all_tools = Vec::new();
-
-    all_tools.extend(basic_math::get_tool_definitions());
-    all_tools.extend(batch::get_tool_definitions());
-    all_tools.extend(algebra::get_tool_definitions());
-    all_tools.extend(statistics::get_tool_definitions());
-    all_tools.extend(geometry::get_tool_definitions());
-    all_tools.extend(equations::get_tool_definitions());
-    all_tools.extend(trigonometry::get_tool_definitions());
-    all_tools.extend(finance::get_tool_definitions());
-    all_tools.extend(combinatorics::get_tool_definitions());
-    all_tools.extend(advanced::get_tool_definitions());
+    let all_tools: Vec<Value> = CATEGORIES
+        .iter()
+        .flat_map(|category| (category.definitions)())
+        .collect();
 
     Arc::new(serde_json::json!(all_tools))
 });
 
-/// Legacy registration for modules not yet converted to const strings
-/// TODO: Remove this once all modules use const strings
-fn register_tools_legacy(
-    registry: &mut HashMap<&'static str, ToolExecutor>,
-    definitions: Vec<Value>,
-    executor: ToolExecutor,
-) {
-    for tool_def in definitions {
-        if let Some(name) = tool_def.get("name").and_then(|n| n.as_str()) {
-            // Temporarily leak memory for legacy tools - will be removed
-            let name_static = Box::leak(name.to_string().into_boxed_str());
-            registry.insert(name_static, executor);
-        }
-    }
+/// Input schema of every built-in tool
+static TOOL_SCHEMAS: Lazy<HashMap<String, Arc<Value>>> = Lazy::new(|| {
+    TOOL_DEFINITIONS
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|definition| {
+            let name = definition.get("name")?.as_str()?.to_string();
+            Some((name, Arc::new(definition.get("inputSchema")?.clone())))
+        })
+        .collect()
+});
+
+/// Built-in tools whose results vary between identical calls
+/// (`batch_operations` reports timings); their operations are cached instead
+const UNCACHEABLE_TOOLS: &[&str] = &[batch::TOOL_BATCH];
+
+/// Results of earlier calls, shared by every session in the process
+static RESULT_CACHE: Lazy<ResultCache> = Lazy::new(ResultCache::new);
+
+/// A tool added by an embedding application
+struct RuntimeTool {
+    name: String,
+    definition: Value,
+    input_schema: Arc<Value>,
+    execute: RuntimeExecutor,
 }
 
+/// Tools registered at runtime, in registration order
+static RUNTIME_TOOLS: Lazy<RwLock<Vec<RuntimeTool>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Bumped whenever the set of runtime tools changes
+static TOOL_LIST_VERSION: Lazy<watch::Sender<u64>> = Lazy::new(|| watch::channel(0).0);
+
 /// Default tool registry implementation.
 ///
-/// Uses a static HashMap for O(1) tool lookup and cached tool definitions.
-/// This is the recommended registry for production use.
+/// Uses a static HashMap for O(1) lookup of the built-in tools and cached tool
+/// definitions. This is the recommended registry for production use.
+///
+/// Applications embedding the server can add their own tools at startup with
+/// [`register`](Self::register). Registered tools are shared by every
+/// `DefaultToolRegistry` (and so every session) in the process, are listed
+/// after the built-in tools, and obey the same allow and deny lists.
+///
+/// When the call's configuration enables the result cache (`[cache]`),
+/// successful results are kept in a process-wide LRU cache keyed on the tool
+/// name and canonicalized arguments, and identical calls are answered from
+/// it. Composite tools, `batch_operations` itself and the tools in
+/// `cache.exclude` always run.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::tools::{DefaultToolRegistry, ToolRegistry};
+/// use serde_json::json;
+///
+/// let registry = DefaultToolRegistry;
+/// let definition = json!({
+///     "name": "double",
+///     "description": "Double a number",
+///     "inputSchema": { "type": "object", "properties": { "x": { "type": "number" } } }
+/// });
+/// registry
+///     .register(definition, |args, _ctx| Ok(json!({ "result": args["x"].as_f64().unwrap_or(0.0) * 2.0 })))
+///     .unwrap();
+///
+/// let result = registry.execute_tool("double", &json!({ "x": 21 })).unwrap();
+/// assert_eq!(result["result"], 42.0);
+/// assert!(registry.unregister("double"));
+/// ```
 pub struct DefaultToolRegistry;
 
+impl DefaultToolRegistry {
+    /// Add a tool with the given definition (`name`, `description`,
+    /// `inputSchema` and optionally `outputSchema`), run by `executor`.
+    ///
+    /// Fails with `invalid_params` if the definition has no name or no object
+    /// `inputSchema`, or if a tool with that name already exists. Connected
+    /// clients are sent `notifications/tools/list_changed`.
+    ///
+    /// Tools the executor calls with its context count as nested calls, so
+    /// they are charged against their own rate limits.
+    pub fn register<F>(&self, definition: Value, executor: F) -> McpResult<()>
+    where
+        F: Fn(&Value, &ToolContext) -> McpResult<Value> + Send + Sync + 'static,
+    {
+        let name = definition
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| McpError::invalid_params("Tool definition needs a non-empty 'name'"))?
+            .to_string();
+        if !definition.get("inputSchema").is_some_and(Value::is_object) {
+            return Err(McpError::invalid_params(format!(
+                "Tool definition for '{}' needs an object 'inputSchema'",
+                name
+            )));
+        }
+
+        let mut tools = RUNTIME_TOOLS.write().unwrap_or_else(|e| e.into_inner());
+        if TOOL_REGISTRY.contains_key(&name) || tools.iter().any(|tool| tool.name == name) {
+            return Err(McpError::invalid_params(format!(
+                "Tool '{}' is already registered",
+                name
+            )));
+        }
+        tools.push(RuntimeTool {
+            name,
+            input_schema: Arc::new(definition["inputSchema"].clone()),
+            definition,
+            execute: Arc::new(executor),
+        });
+        drop(tools);
+        tools_changed();
+        Ok(())
+    }
+
+    /// Add a [`MathTool`], using its generated definition
+    pub fn register_tool<T: MathTool>(&self) -> McpResult<()> {
+        self.register(T::definition(), |arguments, ctx| {
+            T::call_with_context(arguments, ctx)
+        })
+    }
+
+    /// Remove a tool added with [`register`](Self::register).
+    ///
+    /// Returns whether it was registered; built-in tools cannot be removed.
+    /// Connected clients are sent `notifications/tools/list_changed`.
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut tools = RUNTIME_TOOLS.write().unwrap_or_else(|e| e.into_inner());
+        let before = tools.len();
+        tools.retain(|tool| tool.name != name);
+        let removed = tools.len() != before;
+        drop(tools);
+        if removed {
+            // A tool registered later under the same name may compute differently
+            RESULT_CACHE.remove_tool(name);
+            tools_changed();
+        }
+        removed
+    }
+
+    /// Hit and miss counters and current size of the result cache
+    pub fn cache_stats(&self) -> CacheStats {
+        RESULT_CACHE.stats()
+    }
+
+    /// Drop every cached result
+    pub fn clear_cache(&self) {
+        RESULT_CACHE.clear();
+    }
+
+    /// Watch for changes to the tool list; the value is bumped on every change
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        TOOL_LIST_VERSION.subscribe()
+    }
+}
+
+/// Tell subscribers the tool list changed
+fn tools_changed() {
+    TOOL_LIST_VERSION.send_modify(|version| *version += 1);
+}
+
+/// The input schema and executor of a tool registered at runtime
+fn runtime_tool(name: &str) -> Option<(Arc<Value>, RuntimeExecutor)> {
+    RUNTIME_TOOLS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|tool| tool.name == name)
+        .map(|tool| (tool.input_schema.clone(), tool.execute.clone()))
+}
+
 impl ToolRegistry for DefaultToolRegistry {
     fn get_all_tools(&self) -> Value {
-        // Clone the Arc (cheap ref count increment) and dereference to get Value
-        (**TOOL_DEFINITIONS).clone()
+        get_all_tools()
+    }
+
+    fn execute_tool_with_context(
+        &self,
+        name: &str,
+        arguments: &Value,
+        ctx: &ToolContext,
+    ) -> McpResult<Value> {
+        let start = Instant::now();
+        let result = execute(name, arguments, ctx);
+        // Unknown names are not recorded, so clients cannot grow the metrics
+        if is_known(name, ctx) {
+            metrics::global().record(name, start.elapsed(), &result);
+        }
+        result
+    }
+}
+
+/// Check the tool is enabled and within its rate limit, coerce lenient
+/// numbers, then run it
+fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    // Unknown names fall through to dispatch and fail as unknown tools
+    if is_known(name, ctx) && !ctx.config().tool_enabled(name) {
+        return Err(McpError::tool_error(format!(
+            "Tool '{}' is disabled on this server",
+            name
+        )));
+    }
+    // Also reached through composite and runtime tools, which a batch's
+    // own check of its operations cannot see into
+    if name == batch::TOOL_BATCH && ctx.is_within_batch() {
+        return Err(McpError::invalid_params(format!(
+            "{} cannot be nested",
+            batch::TOOL_BATCH
+        )));
+    }
+    // The service charges a client's call; calls made from inside another
+    // tool are charged against their own tool's limit here
+    if ctx.depth() > 0 {
+        if let Some(session) = ctx.session() {
+            session.check_tool_rate_limit(name, ctx.config().tool_cost(name))?;
+        }
     }
+    if ctx.config().lenient_numbers {
+        if let Some(schema) = input_schema(name, ctx) {
+            let mut arguments = arguments.clone();
+            let interpretations = coerce_numbers(&mut arguments, &schema);
+            if !interpretations.is_empty() {
+                let mut result = dispatch_cached(name, &arguments, ctx)?;
+                if let Some(result) = result.as_object_mut() {
+                    let interpretations = serde_json::to_value(interpretations)?;
+                    result.insert("interpretations".to_string(), interpretations);
+                }
+                return Ok(result);
+            }
+        }
+    }
+    dispatch_cached(name, arguments, ctx)
+}
 
-    fn execute_tool(&self, name: &str, arguments: &Value) -> McpResult<Value> {
-        TOOL_REGISTRY
-            .get(name)
-            .ok_or_else(|| McpError::tool_error(format!("Unknown tool: {}", name)))
-            .and_then(|executor| executor(name, arguments))
+/// Whether `name` is a built-in, composite or runtime tool
+pub(crate) fn is_known(name: &str, ctx: &ToolContext) -> bool {
+    TOOL_REGISTRY.contains_key(name)
+        || ctx.config().composite_tool(name).is_some()
+        || runtime_tool(name).is_some()
+}
+
+/// [`dispatch`], answered from the result cache when the configuration
+/// enables it and the tool's results may be cached
+fn dispatch_cached(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    let config = ctx.config();
+    let cacheable = config.cache_enabled
+        && !UNCACHEABLE_TOOLS.contains(&name)
+        && !config.cache_exclude.contains(name)
+        && config.composite_tool(name).is_none();
+    if !cacheable {
+        return dispatch(name, arguments, ctx);
     }
+
+    // A result only stands for calls checked against the same limits
+    let key = serde_json::json!({
+        "arguments": arguments,
+        "limits": [config.max_array_size, config.max_decimal_places],
+    });
+    if let Some(result) = RESULT_CACHE.get(name, &key) {
+        return Ok(result);
+    }
+    let result = dispatch(name, arguments, ctx)?;
+    RESULT_CACHE.insert(
+        name,
+        &key,
+        result.clone(),
+        config.cache_max_entries,
+        config.cache_max_bytes,
+    );
+    Ok(result)
+}
+
+/// Check `arguments` against the tool's inputSchema, then run it
+fn dispatch(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    if let Some(executor) = TOOL_REGISTRY.get(name) {
+        if let Some(schema) = TOOL_SCHEMAS.get(name) {
+            validate_arguments(name, arguments, schema)?;
+        }
+        return executor(name, arguments, ctx);
+    }
+    if let Some(composite) = ctx.config().composite_tool(name) {
+        // Checks the arguments against its own inputSchema
+        return composite.execute(arguments, ctx);
+    }
+    // Cloned out so the lock is not held while the tool runs
+    let (schema, executor) = runtime_tool(name)
+        .ok_or_else(|| McpError::tool_error(format!("Unknown tool: {}", name)))?;
+    validate_arguments(name, arguments, &schema)?;
+    executor(arguments, &ctx.for_runtime_tool())
+}
+
+/// The inputSchema of a built-in, composite or runtime tool
+fn input_schema(name: &str, ctx: &ToolContext) -> Option<Arc<Value>> {
+    if let Some(schema) = TOOL_SCHEMAS.get(name) {
+        return Some(Arc::clone(schema));
+    }
+    if let Some(composite) = ctx.config().composite_tool(name) {
+        return Some(Arc::new(composite.definition()["inputSchema"].take()));
+    }
+    runtime_tool(name).map(|(schema, _)| schema)
 }
 
 /// Get all tool definitions (cached, returns Arc-wrapped Value for efficiency)
+///
+/// The cache is shared as long as no tools have been registered at runtime.
 pub fn get_all_tools_arc() -> Arc<Value> {
-    Arc::clone(&TOOL_DEFINITIONS)
+    let runtime = RUNTIME_TOOLS.read().unwrap_or_else(|e| e.into_inner());
+    if runtime.is_empty() {
+        return Arc::clone(&TOOL_DEFINITIONS);
+    }
+    let all_tools: Vec<Value> = TOOL_DEFINITIONS
+        .as_array()
+        .into_iter()
+        .flatten()
+        .cloned()
+        .chain(runtime.iter().map(|tool| tool.definition.clone()))
+        .collect();
+    Arc::new(Value::Array(all_tools))
+}
+
+/// Get the definitions of the tools `config` enables, in `tools/list` order:
+/// built-in tools, tools registered at runtime, then `config`'s composite tools
+pub fn get_enabled_tools(config: &Config) -> Vec<Value> {
+    let composites = config.composite_tools.iter().map(|tool| tool.definition());
+    get_all_tools_arc()
+        .as_array()
+        .into_iter()
+        .flatten()
+        .cloned()
+        .chain(composites)
+        .filter(|tool| {
+            tool.get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|name| config.tool_enabled(name))
+        })
+        .collect()
 }
 
 /// Get all tool definitions (clones from Arc)
 pub fn get_all_tools() -> Value {
-    (**TOOL_DEFINITIONS).clone()
+    (*get_all_tools_arc()).clone()
 }
 
 /// Execute a tool (O(1) lookup)
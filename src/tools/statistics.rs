@@ -1,294 +1,315 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_bool_opt, get_number_array, result_json, result_value};
-use serde_json::Value;
-use std::collections::HashMap;
+use crate::math::statistics::{self as math, Sample};
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use crate::utils::args::check_number_array;
+use crate::utils::limits::Limits;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "mean",
-            "description": "Calculate the arithmetic mean (average) of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "median",
-            "description": "Calculate the median of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "mode",
-            "description": "Find the mode (most frequently occurring value) of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "std_dev",
-            "description": "Calculate the standard deviation of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    },
-                    "sample": {
-                        "type": "boolean",
-                        "description": "If true, calculate sample standard deviation (n-1), otherwise population (n)"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "variance",
-            "description": "Calculate the variance of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    },
-                    "sample": {
-                        "type": "boolean",
-                        "description": "If true, calculate sample variance (n-1), otherwise population (n)"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "min",
-            "description": "Find the minimum value in a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "max",
-            "description": "Find the maximum value in a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "sum",
-            "description": "Calculate the sum of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-        serde_json::json!({
-            "name": "product",
-            "description": "Calculate the product of a list of numbers",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "numbers": {
-                        "type": "array",
-                        "items": {"type": "number"},
-                        "description": "Array of numbers"
-                    }
-                },
-                "required": ["numbers"]
-            }
-        }),
-    ]
+pub use crate::math::statistics::PROGRESS_CHUNK;
+
+/// Arguments for tools that summarize a list of numbers
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NumbersInput {
+    /// Array of numbers
+    pub numbers: Vec<f64>,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "mean" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(mean(numbers)?))
-        }
-        "median" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(median(numbers)?))
-        }
-        "mode" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_value(mode(numbers)?))
-        }
-        "variance" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            let sample = get_bool_opt(arguments, "sample");
-            Ok(result_json(variance(numbers, sample)?))
-        }
-        "std_dev" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            let sample = get_bool_opt(arguments, "sample");
-            Ok(result_json(std_dev(numbers, sample)?))
-        }
-        "min" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(min(numbers)?))
-        }
-        "max" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(max(numbers)?))
-        }
-        "sum" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(sum(numbers)?))
-        }
-        "product" => {
-            let numbers = get_number_array(arguments, "numbers")?;
-            Ok(result_json(product(numbers)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown statistics tool: {}",
-            name
-        ))),
+/// Arguments for dispersion tools (`variance`, `std_dev`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DispersionInput {
+    /// Array of numbers
+    pub numbers: Vec<f64>,
+    /// If true, calculate the sample statistic (n-1), otherwise population (n)
+    pub sample: Option<bool>,
+}
+
+/// Output of the `mode` tool
+///
+/// `mode` is null (with an explanatory `message`) when every value is unique.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ModeResult {
+    /// Most frequent value(s)
+    pub mode: Option<Vec<f64>>,
+    /// Number of occurrences of each mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<usize>,
+    /// Explanation when there is no mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+pub struct Mean;
+pub struct Median;
+pub struct Mode;
+pub struct StdDev;
+pub struct Variance;
+pub struct Min;
+pub struct Max;
+pub struct Sum;
+pub struct Product;
+
+impl MathTool for Mean {
+    const NAME: &'static str = "mean";
+    const DESCRIPTION: &'static str =
+        "Calculate the arithmetic mean (average) of a list of numbers";
+    const FORMULA: &'static str = "x̄ = (Σ xᵢ) / n";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [2, 4, 4, 4, 5, 5, 7, 9] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        Self::run_with_context(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: NumbersInput, ctx: &ToolContext) -> McpResult<NumberResult> {
+        math::mean_with_context(&input.numbers, ctx).map(NumberResult::from)
+    }
+}
+
+impl MathTool for Median {
+    const NAME: &'static str = "median";
+    const DESCRIPTION: &'static str = "Calculate the median of a list of numbers";
+    const FORMULA: &'static str =
+        "middle value of the sorted list (mean of the two middle values when n is even)";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [3, 1, 4, 1, 5] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        math::median(&input.numbers).map(NumberResult::from)
+    }
+}
+
+impl MathTool for Mode {
+    const NAME: &'static str = "mode";
+    const DESCRIPTION: &'static str =
+        "Find the mode (most frequently occurring value) of a list of numbers";
+    const FORMULA: &'static str = "most frequent value(s)";
+    type Input = NumbersInput;
+    type Output = ModeResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [1, 2, 2, 3, 3, 3] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<ModeResult> {
+        Ok(match math::mode(&input.numbers)? {
+            Some(modes) => ModeResult {
+                mode: Some(modes.values),
+                frequency: Some(modes.frequency),
+                message: None,
+            },
+            None => ModeResult {
+                mode: None,
+                frequency: None,
+                message: Some("No mode - all values are unique".to_string()),
+            },
+        })
     }
 }
 
-fn mean(numbers: Vec<f64>) -> McpResult<f64> {
-    if numbers.is_empty() {
-        return Err(crate::error::McpError::validation_error(
-            "Cannot calculate mean of empty array",
-        ));
+impl MathTool for StdDev {
+    const NAME: &'static str = "std_dev";
+    const DESCRIPTION: &'static str = "Calculate the standard deviation of a list of numbers";
+    const FORMULA: &'static str = "σ = √variance";
+    type Input = DispersionInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [2, 4, 4, 4, 5, 5, 7, 9] })
+    }
+
+    fn validate(input: &DispersionInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: DispersionInput) -> McpResult<NumberResult> {
+        Self::run_with_context(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: DispersionInput, ctx: &ToolContext) -> McpResult<NumberResult> {
+        let sample = Sample::from(input.sample.unwrap_or(false));
+        math::std_dev_with_context(&input.numbers, sample, ctx).map(NumberResult::from)
     }
-    Ok(numbers.iter().sum::<f64>() / numbers.len() as f64)
 }
 
-fn median(numbers: Vec<f64>) -> McpResult<f64> {
-    if numbers.is_empty() {
-        return Err(crate::error::McpError::validation_error(
-            "Cannot calculate median of empty array",
-        ));
+impl MathTool for Variance {
+    const NAME: &'static str = "variance";
+    const DESCRIPTION: &'static str = "Calculate the variance of a list of numbers";
+    const FORMULA: &'static str = "σ² = Σ (xᵢ - x̄)² / n (population) or / (n - 1) (sample)";
+    type Input = DispersionInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [2, 4, 4, 4, 5, 5, 7, 9], "sample": false })
+    }
+
+    fn validate(input: &DispersionInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
     }
-    let mut sorted = numbers;
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let len = sorted.len();
-    if len.is_multiple_of(2) {
-        Ok((sorted[len / 2 - 1] + sorted[len / 2]) / 2.0)
-    } else {
-        Ok(sorted[len / 2])
+
+    fn run(input: DispersionInput) -> McpResult<NumberResult> {
+        Self::run_with_context(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: DispersionInput, ctx: &ToolContext) -> McpResult<NumberResult> {
+        let sample = Sample::from(input.sample.unwrap_or(false));
+        math::variance_with_context(&input.numbers, sample, ctx).map(NumberResult::from)
     }
 }
 
-fn mode(numbers: Vec<f64>) -> McpResult<Value> {
-    if numbers.is_empty() {
-        return Err(crate::error::McpError::validation_error(
-            "Cannot calculate mode of empty array",
-        ));
-    }
-    let mut frequency: HashMap<String, usize> = HashMap::new();
-    for num in &numbers {
-        let key = format!("{:.10}", num);
-        *frequency.entry(key).or_insert(0) += 1;
-    }
-    let max_freq = frequency.values().max().copied().unwrap_or(0);
-    let modes: Vec<f64> = frequency
-        .iter()
-        .filter(|(_, &freq)| freq == max_freq)
-        .filter_map(|(key, _)| key.parse::<f64>().ok())
-        .collect();
-
-    if modes.len() == numbers.len() {
-        Ok(serde_json::json!({"mode": null, "message": "No mode - all values are unique"}))
-    } else {
-        Ok(serde_json::json!({"mode": modes, "frequency": max_freq}))
+impl MathTool for Min {
+    const NAME: &'static str = "min";
+    const DESCRIPTION: &'static str = "Find the minimum value in a list of numbers";
+    const FORMULA: &'static str = "min(x₁, …, xₙ)";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [7, 2, 9, 4] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        math::min(&input.numbers).map(NumberResult::from)
     }
 }
 
-fn variance(numbers: Vec<f64>, sample: Option<bool>) -> McpResult<f64> {
-    if numbers.is_empty() {
-        return Err(crate::error::McpError::validation_error(
-            "Cannot calculate variance of empty array",
-        ));
-    }
-    let mean_val = mean(numbers.clone())?;
-    let n = numbers.len() as f64;
-    let divisor = if sample.unwrap_or(false) && n > 1.0 {
-        n - 1.0
-    } else {
-        n
-    };
-    let sum_squared_diff: f64 = numbers.iter().map(|x| (x - mean_val).powi(2)).sum();
-    Ok(sum_squared_diff / divisor)
+impl MathTool for Max {
+    const NAME: &'static str = "max";
+    const DESCRIPTION: &'static str = "Find the maximum value in a list of numbers";
+    const FORMULA: &'static str = "max(x₁, …, xₙ)";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [7, 2, 9, 4] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        math::max(&input.numbers).map(NumberResult::from)
+    }
 }
 
-fn std_dev(numbers: Vec<f64>, sample: Option<bool>) -> McpResult<f64> {
-    Ok(variance(numbers, sample)?.sqrt())
+impl MathTool for Sum {
+    const NAME: &'static str = "sum";
+    const DESCRIPTION: &'static str = "Calculate the sum of a list of numbers";
+    const FORMULA: &'static str = "Σ xᵢ";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [1, 2, 3, 4] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        Self::run_with_context(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: NumbersInput, ctx: &ToolContext) -> McpResult<NumberResult> {
+        math::sum_with_context(&input.numbers, ctx).map(NumberResult::from)
+    }
 }
 
-fn min(numbers: Vec<f64>) -> McpResult<f64> {
-    numbers
-        .iter()
-        .min_by(|a, b| a.partial_cmp(b).unwrap())
-        .copied()
-        .ok_or_else(|| crate::error::McpError::validation_error("Cannot find min of empty array"))
+impl MathTool for Product {
+    const NAME: &'static str = "product";
+    const DESCRIPTION: &'static str = "Calculate the product of a list of numbers";
+    const FORMULA: &'static str = "Π xᵢ";
+    type Input = NumbersInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "numbers": [1, 2, 3, 4] })
+    }
+
+    fn validate(input: &NumbersInput, limits: &Limits) -> McpResult<()> {
+        check_number_array(&input.numbers, "numbers", limits)
+    }
+
+    fn run(input: NumbersInput) -> McpResult<NumberResult> {
+        Self::run_with_context(input, &ToolContext::default())
+    }
+
+    fn run_with_context(input: NumbersInput, ctx: &ToolContext) -> McpResult<NumberResult> {
+        math::product_with_context(&input.numbers, ctx).map(NumberResult::from)
+    }
 }
 
-fn max(numbers: Vec<f64>) -> McpResult<f64> {
-    numbers
-        .iter()
-        .max_by(|a, b| a.partial_cmp(b).unwrap())
-        .copied()
-        .ok_or_else(|| crate::error::McpError::validation_error("Cannot find max of empty array"))
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        Mean::definition(),
+        Median::definition(),
+        Mode::definition(),
+        StdDev::definition(),
+        Variance::definition(),
+        Min::definition(),
+        Max::definition(),
+        Sum::definition(),
+        Product::definition(),
+    ]
 }
 
-fn sum(numbers: Vec<f64>) -> McpResult<f64> {
-    Ok(numbers.iter().sum())
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        Mean::documentation(),
+        Median::documentation(),
+        Mode::documentation(),
+        StdDev::documentation(),
+        Variance::documentation(),
+        Min::documentation(),
+        Max::documentation(),
+        Sum::documentation(),
+        Product::documentation(),
+    ]
 }
 
-fn product(numbers: Vec<f64>) -> McpResult<f64> {
-    Ok(numbers.iter().product())
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        Mean::NAME => Mean::call_with_context(arguments, ctx),
+        Median::NAME => Median::call_with_context(arguments, ctx),
+        Mode::NAME => Mode::call_with_context(arguments, ctx),
+        Variance::NAME => Variance::call_with_context(arguments, ctx),
+        StdDev::NAME => StdDev::call_with_context(arguments, ctx),
+        Min::NAME => Min::call_with_context(arguments, ctx),
+        Max::NAME => Max::call_with_context(arguments, ctx),
+        Sum::NAME => Sum::call_with_context(arguments, ctx),
+        Product::NAME => Product::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown statistics tool: {}",
+            name
+        ))),
+    }
 }
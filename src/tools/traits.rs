@@ -1,4 +1,11 @@
-use crate::error::McpResult;
+use crate::error::{McpError, McpResult};
+use crate::tools::context::ToolContext;
+use crate::utils::limits::Limits;
+use crate::utils::validation::find_mismatch;
+use schemars::gen::SchemaSettings;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Trait for tool registry operations.
@@ -35,5 +42,245 @@ pub trait ToolRegistry {
     ///
     /// A `McpResult` containing the tool's result as a JSON `Value`, or an error
     /// if the tool is not found or execution fails.
-    fn execute_tool(&self, name: &str, arguments: &Value) -> McpResult<Value>;
+    fn execute_tool(&self, name: &str, arguments: &Value) -> McpResult<Value> {
+        self.execute_tool_with_context(name, arguments, &ToolContext::default())
+    }
+
+    /// Execute a tool by name, honoring the deadline and cancellation in `ctx`.
+    ///
+    /// Fails with a `resource_limit` error if `ctx` is cancelled or times out
+    /// before or during execution.
+    fn execute_tool_with_context(
+        &self,
+        name: &str,
+        arguments: &Value,
+        ctx: &ToolContext,
+    ) -> McpResult<Value>;
+}
+
+/// A single math tool with typed input and output.
+///
+/// The tool's `inputSchema` is generated from `Input`, and arguments are parsed
+/// into that same type before `run` is called, so the advertised schema and the
+/// accepted arguments cannot drift apart.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::tools::basic_math::Add;
+/// use rust_math_mcp::tools::MathTool;
+/// use serde_json::json;
+///
+/// let definition = Add::definition();
+/// assert_eq!(definition["inputSchema"]["required"], json!(["numbers"]));
+///
+/// let result = Add::call(&json!({ "numbers": [1, 2, 3] })).unwrap();
+/// assert_eq!(result["result"], 6.0);
+/// ```
+pub trait MathTool {
+    /// Tool name as exposed in `tools/list`
+    const NAME: &'static str;
+    /// Human-readable description of what the tool computes
+    const DESCRIPTION: &'static str;
+    /// Formula the tool implements, shown in its documentation (empty if none)
+    const FORMULA: &'static str = "";
+
+    /// Arguments accepted by the tool
+    type Input: DeserializeOwned + JsonSchema;
+    /// Value produced on success
+    type Output: Serialize + JsonSchema;
+
+    /// Representative arguments, run to produce the documented example
+    fn example() -> Value;
+
+    /// Check parsed arguments against the call's limits (e.g. array sizes)
+    /// before the tool runs; accepts everything by default
+    fn validate(_input: &Self::Input, _limits: &Limits) -> McpResult<()> {
+        Ok(())
+    }
+
+    /// Run the tool on already-parsed arguments
+    fn run(input: Self::Input) -> McpResult<Self::Output>;
+
+    /// Run the tool with access to the call's context.
+    ///
+    /// Tools that loop over many steps override this to check `ctx` between
+    /// steps; the default just runs [`MathTool::run`].
+    fn run_with_context(input: Self::Input, _ctx: &ToolContext) -> McpResult<Self::Output> {
+        Self::run(input)
+    }
+
+    /// Build the tool definition (`name`, `description`, `inputSchema`, `outputSchema`)
+    fn definition() -> Value {
+        serde_json::json!({
+            "name": Self::NAME,
+            "description": Self::DESCRIPTION,
+            "inputSchema": schema_for::<Self::Input>(),
+            "outputSchema": output_schema_for::<Self::Output>(),
+        })
+    }
+
+    /// Result of [`MathTool::example`], shown in the documentation.
+    ///
+    /// Computed by actually running the example, so it can never disagree with
+    /// the implementation. Tools whose example calls other tools through the
+    /// registry return a fixed value instead, so that reading documentation
+    /// never shows up in the metrics or the result cache.
+    fn example_result() -> Value {
+        Self::call(&Self::example()).unwrap_or_else(|e| serde_json::json!({ "error": e.message }))
+    }
+
+    /// Build extended documentation: the definition plus formula and a worked example
+    fn documentation() -> Value {
+        let mut doc = Self::definition();
+        let arguments = Self::example();
+        let result = Self::example_result();
+
+        if !Self::FORMULA.is_empty() {
+            doc["formula"] = Value::from(Self::FORMULA);
+        }
+        doc["example"] = serde_json::json!({ "arguments": arguments, "result": result });
+        doc
+    }
+
+    /// Parse JSON arguments, run the tool and serialize its output
+    fn call(arguments: &Value) -> McpResult<Value> {
+        Self::call_with_context(arguments, &ToolContext::default())
+    }
+
+    /// Like [`MathTool::call`], but stops early if `ctx` is cancelled or out of time
+    fn call_with_context(arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+        let input: Self::Input = serde_json::from_value(arguments.clone()).map_err(|e| {
+            let message = format!("Invalid arguments for {}: {}", Self::NAME, e);
+            // The parser does not say where the bad value is, the schema does
+            match find_mismatch(arguments, &schema_for::<Self::Input>()) {
+                Some(argument) => argument.invalid_params(message),
+                None => McpError::invalid_params(message),
+            }
+        })?;
+        Self::validate(&input, ctx.limits())?;
+        ctx.check()?;
+        let output = Self::run_with_context(input, ctx)?;
+        serde_json::to_value(output)
+            .map_err(|e| McpError::internal_error(format!("Failed to serialize result: {}", e)))
+    }
+}
+
+/// Generate a self-contained JSON schema for `T`.
+///
+/// Sub-schemas are inlined and the root `$schema`, `title` and `description`
+/// keywords are dropped, leaving the plain object schema MCP clients expect.
+/// Objects with declared properties get `"additionalProperties": false`, so
+/// unknown arguments are rejected rather than ignored.
+pub fn schema_for<T: JsonSchema>() -> Value {
+    let mut schema = generate_schema::<T>(false);
+    deny_unknown_properties(&mut schema);
+    schema
+}
+
+fn deny_unknown_properties(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if object.contains_key("properties") && !object.contains_key("additionalProperties") {
+                object.insert("additionalProperties".to_string(), Value::Bool(false));
+            }
+            object.values_mut().for_each(deny_unknown_properties);
+        }
+        Value::Array(items) => items.iter_mut().for_each(deny_unknown_properties),
+        _ => {}
+    }
+}
+
+/// Generate a JSON schema for a tool's output.
+///
+/// Unlike input schemas, `Option` fields that are always serialized are marked
+/// nullable, since a result may contain `null` (e.g. `mode` with no repeats).
+/// Untagged enum outputs get `"type": "object"` at the root, which MCP requires
+/// of every `outputSchema`.
+pub fn output_schema_for<T: JsonSchema>() -> Value {
+    let mut schema = generate_schema::<T>(true);
+    if let Some(object) = schema.as_object_mut() {
+        if !object.contains_key("type") {
+            object.insert("type".to_string(), Value::from("object"));
+        }
+    }
+    schema
+}
+
+fn generate_schema<T: JsonSchema>(nullable_options: bool) -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.option_add_null_type = nullable_options;
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<T>())
+        .unwrap_or_else(|_| serde_json::json!({ "type": "object" }));
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("title");
+        object.remove("description");
+    }
+    schema
+}
+
+/// Output of tools that produce a single number: `{"result": <number>}`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NumberResult {
+    /// Computed value
+    pub result: f64,
+}
+
+impl From<f64> for NumberResult {
+    fn from(result: f64) -> Self {
+        Self { result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::basic_math::{Round, Subtract};
+    use serde_json::json;
+
+    #[test]
+    fn test_definition_from_types() {
+        let definition = Subtract::definition();
+
+        assert_eq!(definition["name"], "subtract");
+        let schema = &definition["inputSchema"];
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["a", "b"]));
+        assert_eq!(
+            schema["properties"]["b"]["description"],
+            "Number to subtract"
+        );
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("title").is_none());
+    }
+
+    #[test]
+    fn test_optional_fields_not_required() {
+        let schema = &Round::definition()["inputSchema"];
+
+        assert_eq!(schema["required"], json!(["number"]));
+        assert_eq!(schema["properties"]["decimals"]["type"], "number");
+    }
+
+    #[test]
+    fn test_call_parses_arguments() {
+        let result = Subtract::call(&json!({ "a": 10, "b": 4 })).unwrap();
+        assert_eq!(result["result"], 6.0);
+    }
+
+    #[test]
+    fn test_call_rejects_invalid_arguments() {
+        let missing = Subtract::call(&json!({ "a": 10 })).unwrap_err();
+        assert_eq!(missing.code, -32602);
+        assert!(missing.message.contains("subtract"));
+
+        let wrong_type = Subtract::call(&json!({ "a": 10, "b": "four" })).unwrap_err();
+        assert_eq!(wrong_type.code, -32602);
+    }
 }
@@ -1,280 +1,347 @@
 use crate::error::McpResult;
-use crate::utils::args::{get_number, get_number_opt, result_json, result_value};
-use serde_json::Value;
+use crate::math::trigonometry as math;
+use crate::tools::context::ToolContext;
+use crate::tools::traits::{MathTool, NumberResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-pub fn get_tool_definitions() -> Vec<Value> {
-    vec![
-        serde_json::json!({
-            "name": "sin",
-            "description": "Calculate sine of an angle (in radians)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "angle": {"type": "number", "description": "Angle in radians"}
-                },
-                "required": ["angle"]
-            }
-        }),
-        serde_json::json!({
-            "name": "cos",
-            "description": "Calculate cosine of an angle (in radians)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "angle": {"type": "number", "description": "Angle in radians"}
-                },
-                "required": ["angle"]
-            }
-        }),
-        serde_json::json!({
-            "name": "tan",
-            "description": "Calculate tangent of an angle (in radians)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "angle": {"type": "number", "description": "Angle in radians"}
-                },
-                "required": ["angle"]
-            }
-        }),
-        serde_json::json!({
-            "name": "asin",
-            "description": "Calculate arcsine (inverse sine) in radians",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "value": {"type": "number", "description": "Value between -1 and 1"}
-                },
-                "required": ["value"]
-            }
-        }),
-        serde_json::json!({
-            "name": "acos",
-            "description": "Calculate arccosine (inverse cosine) in radians",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "value": {"type": "number", "description": "Value between -1 and 1"}
-                },
-                "required": ["value"]
-            }
-        }),
-        serde_json::json!({
-            "name": "atan",
-            "description": "Calculate arctangent (inverse tangent) in radians",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "value": {"type": "number", "description": "Value"}
-                },
-                "required": ["value"]
-            }
-        }),
-        serde_json::json!({
-            "name": "law_of_cosines",
-            "description": "Calculate side or angle using Law of Cosines: c² = a² + b² - 2ab cos(C)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "a": {"type": "number", "description": "Side a"},
-                    "b": {"type": "number", "description": "Side b"},
-                    "c": {"type": "number", "description": "Side c (leave 0 to calculate)"},
-                    "angle_c": {"type": "number", "description": "Angle C in radians (required if calculating c)"}
-                },
-                "required": ["a", "b"]
-            }
-        }),
-        serde_json::json!({
-            "name": "law_of_sines",
-            "description": "Calculate side or angle using Law of Sines: a/sin(A) = b/sin(B) = c/sin(C)",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "side_a": {"type": "number", "description": "Side a (leave 0 if calculating)"},
-                    "angle_a": {"type": "number", "description": "Angle A in radians"},
-                    "side_b": {"type": "number", "description": "Side b (leave 0 if calculating)"},
-                    "angle_b": {"type": "number", "description": "Angle B in radians"}
-                },
-                "required": ["angle_a", "angle_b"]
-            }
-        }),
-        serde_json::json!({
-            "name": "degrees_to_radians",
-            "description": "Convert degrees to radians",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "degrees": {"type": "number", "description": "Angle in degrees"}
-                },
-                "required": ["degrees"]
-            }
-        }),
-        serde_json::json!({
-            "name": "radians_to_degrees",
-            "description": "Convert radians to degrees",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "radians": {"type": "number", "description": "Angle in radians"}
-                },
-                "required": ["radians"]
-            }
-        }),
-    ]
+/// Arguments for the trigonometric functions (`sin`, `cos`, `tan`)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AngleInput {
+    /// Angle in radians
+    pub angle: f64,
 }
 
-pub fn execute(name: &str, arguments: &Value) -> McpResult<Value> {
-    match name {
-        "sin" => {
-            let angle = get_number(arguments, "angle")?;
-            Ok(result_json(sin(angle)?))
-        }
-        "cos" => {
-            let angle = get_number(arguments, "angle")?;
-            Ok(result_json(cos(angle)?))
-        }
-        "tan" => {
-            let angle = get_number(arguments, "angle")?;
-            Ok(result_json(tan(angle)?))
-        }
-        "asin" => {
-            let value = get_number(arguments, "value")?;
-            Ok(result_json(asin(value)?))
-        }
-        "acos" => {
-            let value = get_number(arguments, "value")?;
-            Ok(result_json(acos(value)?))
-        }
-        "atan" => {
-            let value = get_number(arguments, "value")?;
-            Ok(result_json(atan(value)?))
-        }
-        "law_of_cosines" => {
-            let a = get_number(arguments, "a")?;
-            let b = get_number(arguments, "b")?;
-            let c = get_number_opt(arguments, "c");
-            let angle_c = get_number_opt(arguments, "angle_c");
-            Ok(result_value(law_of_cosines(a, b, c, angle_c)?))
-        }
-        "law_of_sines" => {
-            let side_a = get_number_opt(arguments, "side_a");
-            let angle_a = get_number(arguments, "angle_a")?;
-            let side_b = get_number_opt(arguments, "side_b");
-            let angle_b = get_number(arguments, "angle_b")?;
-            Ok(result_value(law_of_sines(
-                side_a, angle_a, side_b, angle_b,
-            )?))
-        }
-        "degrees_to_radians" => {
-            let degrees = get_number(arguments, "degrees")?;
-            Ok(result_json(degrees_to_radians(degrees)?))
-        }
-        "radians_to_degrees" => {
-            let radians = get_number(arguments, "radians")?;
-            Ok(result_json(radians_to_degrees(radians)?))
-        }
-        _ => Err(crate::error::McpError::tool_error(format!(
-            "Unknown trigonometry tool: {}",
-            name
-        ))),
+/// Arguments for `asin` and `acos`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UnitValueInput {
+    /// Value between -1 and 1
+    pub value: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ValueInput {
+    /// Value
+    pub value: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LawOfCosinesInput {
+    /// Side a
+    pub a: f64,
+    /// Side b
+    pub b: f64,
+    /// Side c (leave 0 to calculate)
+    pub c: Option<f64>,
+    /// Angle C in radians (required if calculating c)
+    pub angle_c: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LawOfSinesInput {
+    /// Side a (omit if calculating)
+    pub side_a: Option<f64>,
+    /// Angle A in radians
+    pub angle_a: f64,
+    /// Side b (omit if calculating)
+    pub side_b: Option<f64>,
+    /// Angle B in radians
+    pub angle_b: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DegreesInput {
+    /// Angle in degrees
+    pub degrees: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RadiansInput {
+    /// Angle in radians
+    pub radians: f64,
+}
+
+/// Output of the `law_of_cosines` tool
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LawOfCosinesResult {
+    /// Side c computed from a, b and angle C
+    Side { side_c: f64 },
+    /// Angle C (radians) computed from the three sides
+    Angle { angle_c: f64 },
+}
+
+/// Output of the `law_of_sines` tool
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LawOfSinesResult {
+    /// Side b computed from side a
+    SideB { side_b: f64 },
+    /// Side a computed from side b
+    SideA { side_a: f64 },
+    /// Both sides given: the two ratios and whether they agree
+    Check {
+        ratio_a: f64,
+        ratio_b: f64,
+        #[serde(rename = "match")]
+        matches: bool,
+    },
+}
+
+pub struct Sin;
+pub struct Cos;
+pub struct Tan;
+pub struct Asin;
+pub struct Acos;
+pub struct Atan;
+pub struct LawOfCosines;
+pub struct LawOfSines;
+pub struct DegreesToRadians;
+pub struct RadiansToDegrees;
+
+impl MathTool for Sin {
+    const NAME: &'static str = "sin";
+    const DESCRIPTION: &'static str = "Calculate sine of an angle (in radians)";
+    const FORMULA: &'static str = "sin(θ), θ in radians";
+    type Input = AngleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "angle": std::f64::consts::FRAC_PI_6 })
+    }
+
+    fn run(input: AngleInput) -> McpResult<NumberResult> {
+        Ok(math::sin(input.angle).into())
     }
 }
 
-fn sin(angle: f64) -> McpResult<f64> {
-    Ok(angle.sin())
+impl MathTool for Cos {
+    const NAME: &'static str = "cos";
+    const DESCRIPTION: &'static str = "Calculate cosine of an angle (in radians)";
+    const FORMULA: &'static str = "cos(θ), θ in radians";
+    type Input = AngleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "angle": std::f64::consts::FRAC_PI_3 })
+    }
+
+    fn run(input: AngleInput) -> McpResult<NumberResult> {
+        Ok(math::cos(input.angle).into())
+    }
 }
 
-fn cos(angle: f64) -> McpResult<f64> {
-    Ok(angle.cos())
+impl MathTool for Tan {
+    const NAME: &'static str = "tan";
+    const DESCRIPTION: &'static str = "Calculate tangent of an angle (in radians)";
+    const FORMULA: &'static str = "tan(θ) = sin(θ) / cos(θ), θ in radians";
+    type Input = AngleInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "angle": std::f64::consts::FRAC_PI_4 })
+    }
+
+    fn run(input: AngleInput) -> McpResult<NumberResult> {
+        Ok(math::tan(input.angle).into())
+    }
 }
 
-fn tan(angle: f64) -> McpResult<f64> {
-    Ok(angle.tan())
+impl MathTool for Asin {
+    const NAME: &'static str = "asin";
+    const DESCRIPTION: &'static str = "Calculate arcsine (inverse sine) in radians";
+    const FORMULA: &'static str = "θ = arcsin(x), -1 ≤ x ≤ 1";
+    type Input = UnitValueInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "value": 0.5 })
+    }
+
+    fn run(input: UnitValueInput) -> McpResult<NumberResult> {
+        math::asin(input.value).map(NumberResult::from)
+    }
 }
 
-fn asin(value: f64) -> McpResult<f64> {
-    if !(-1.0..=1.0).contains(&value) {
-        return Err(crate::error::McpError::validation_error(
-            "Value must be between -1 and 1 for arcsine",
-        ));
+impl MathTool for Acos {
+    const NAME: &'static str = "acos";
+    const DESCRIPTION: &'static str = "Calculate arccosine (inverse cosine) in radians";
+    const FORMULA: &'static str = "θ = arccos(x), -1 ≤ x ≤ 1";
+    type Input = UnitValueInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "value": 0.5 })
+    }
+
+    fn run(input: UnitValueInput) -> McpResult<NumberResult> {
+        math::acos(input.value).map(NumberResult::from)
     }
-    Ok(value.asin())
 }
 
-fn acos(value: f64) -> McpResult<f64> {
-    if !(-1.0..=1.0).contains(&value) {
-        return Err(crate::error::McpError::validation_error(
-            "Value must be between -1 and 1 for arccosine",
-        ));
+impl MathTool for Atan {
+    const NAME: &'static str = "atan";
+    const DESCRIPTION: &'static str = "Calculate arctangent (inverse tangent) in radians";
+    const FORMULA: &'static str = "θ = arctan(x)";
+    type Input = ValueInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "value": 1 })
+    }
+
+    fn run(input: ValueInput) -> McpResult<NumberResult> {
+        Ok(math::atan(input.value).into())
     }
-    Ok(value.acos())
 }
 
-fn atan(value: f64) -> McpResult<f64> {
-    Ok(value.atan())
+impl MathTool for LawOfCosines {
+    const NAME: &'static str = "law_of_cosines";
+    const DESCRIPTION: &'static str =
+        "Calculate side or angle using Law of Cosines: c² = a² + b² - 2ab cos(C)";
+    const FORMULA: &'static str = "c² = a² + b² - 2ab cos(C)";
+    type Input = LawOfCosinesInput;
+    type Output = LawOfCosinesResult;
+
+    fn example() -> Value {
+        json!({ "a": 3, "b": 4, "c": 0, "angle_c": std::f64::consts::FRAC_PI_2 })
+    }
+
+    fn run(input: LawOfCosinesInput) -> McpResult<LawOfCosinesResult> {
+        let (a, b) = (input.a, input.b);
+        match (input.c, input.angle_c) {
+            (Some(0.0), Some(angle_c)) => Ok(LawOfCosinesResult::Side {
+                side_c: math::law_of_cosines_side(a, b, angle_c),
+            }),
+            (Some(0.0), None) => Err(crate::error::McpError::validation_error(
+                "Angle C is required to calculate side c",
+            )),
+            (Some(c), _) => Ok(LawOfCosinesResult::Angle {
+                angle_c: math::law_of_cosines_angle(a, b, c)?,
+            }),
+            (None, _) => Err(crate::error::McpError::validation_error(
+                "Must provide side c or set it to 0 to calculate",
+            )),
+        }
+    }
 }
 
-fn law_of_cosines(a: f64, b: f64, c: Option<f64>, angle_c: Option<f64>) -> McpResult<Value> {
-    if let Some(c_val) = c {
-        if c_val == 0.0 {
-            if let Some(angle) = angle_c {
-                let c_calc = (a * a + b * b - 2.0 * a * b * angle.cos()).sqrt();
-                Ok(serde_json::json!({ "side_c": c_calc }))
-            } else {
-                Err(crate::error::McpError::validation_error(
-                    "Angle C is required to calculate side c",
-                ))
-            }
-        } else {
-            let cos_c = (a * a + b * b - c_val * c_val) / (2.0 * a * b);
-            if cos_c.abs() > 1.0 {
-                return Err(crate::error::McpError::validation_error(
-                    "Invalid triangle: sides do not satisfy triangle inequality",
-                ));
+impl MathTool for LawOfSines {
+    const NAME: &'static str = "law_of_sines";
+    const DESCRIPTION: &'static str =
+        "Calculate side or angle using Law of Sines: a/sin(A) = b/sin(B) = c/sin(C)";
+    const FORMULA: &'static str = "a / sin(A) = b / sin(B)";
+    type Input = LawOfSinesInput;
+    type Output = LawOfSinesResult;
+
+    fn example() -> Value {
+        json!({ "side_a": 10, "angle_a": std::f64::consts::FRAC_PI_6, "angle_b": std::f64::consts::FRAC_PI_2 })
+    }
+
+    fn run(input: LawOfSinesInput) -> McpResult<LawOfSinesResult> {
+        let (angle_a, angle_b) = (input.angle_a, input.angle_b);
+        match (input.side_a, input.side_b) {
+            (Some(a), None) => Ok(LawOfSinesResult::SideB {
+                side_b: math::law_of_sines_side(a, angle_a, angle_b),
+            }),
+            (None, Some(b)) => Ok(LawOfSinesResult::SideA {
+                side_a: math::law_of_sines_side(b, angle_b, angle_a),
+            }),
+            (Some(a), Some(b)) => {
+                let ratio_a = math::sine_ratio(a, angle_a);
+                let ratio_b = math::sine_ratio(b, angle_b);
+                Ok(LawOfSinesResult::Check {
+                    ratio_a,
+                    ratio_b,
+                    matches: (ratio_a - ratio_b).abs() < 1e-10,
+                })
             }
-            Ok(serde_json::json!({ "angle_c": cos_c.acos() }))
+            (None, None) => Err(crate::error::McpError::validation_error(
+                "Must provide at least one side",
+            )),
         }
-    } else {
-        Err(crate::error::McpError::validation_error(
-            "Must provide side c or set it to 0 to calculate",
-        ))
     }
 }
 
-fn law_of_sines(
-    side_a: Option<f64>,
-    angle_a: f64,
-    side_b: Option<f64>,
-    angle_b: f64,
-) -> McpResult<Value> {
-    match (side_a, side_b) {
-        (Some(a), None) => {
-            let b = a * angle_b.sin() / angle_a.sin();
-            Ok(serde_json::json!({ "side_b": b }))
-        }
-        (None, Some(b)) => {
-            let a = b * angle_a.sin() / angle_b.sin();
-            Ok(serde_json::json!({ "side_a": a }))
-        }
-        (Some(a), Some(b)) => {
-            let ratio_a = a / angle_a.sin();
-            let ratio_b = b / angle_b.sin();
-            Ok(serde_json::json!({
-                "ratio_a": ratio_a,
-                "ratio_b": ratio_b,
-                "match": (ratio_a - ratio_b).abs() < 1e-10
-            }))
-        }
-        (None, None) => Err(crate::error::McpError::validation_error(
-            "Must provide at least one side",
-        )),
+impl MathTool for DegreesToRadians {
+    const NAME: &'static str = "degrees_to_radians";
+    const DESCRIPTION: &'static str = "Convert degrees to radians";
+    const FORMULA: &'static str = "radians = degrees × π / 180";
+    type Input = DegreesInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "degrees": 180 })
+    }
+
+    fn run(input: DegreesInput) -> McpResult<NumberResult> {
+        Ok(math::degrees_to_radians(input.degrees).into())
     }
 }
 
-fn degrees_to_radians(degrees: f64) -> McpResult<f64> {
-    Ok(degrees * std::f64::consts::PI / 180.0)
+impl MathTool for RadiansToDegrees {
+    const NAME: &'static str = "radians_to_degrees";
+    const DESCRIPTION: &'static str = "Convert radians to degrees";
+    const FORMULA: &'static str = "degrees = radians × 180 / π";
+    type Input = RadiansInput;
+    type Output = NumberResult;
+
+    fn example() -> Value {
+        json!({ "radians": std::f64::consts::PI })
+    }
+
+    fn run(input: RadiansInput) -> McpResult<NumberResult> {
+        Ok(math::radians_to_degrees(input.radians).into())
+    }
 }
 
-fn radians_to_degrees(radians: f64) -> McpResult<f64> {
-    Ok(radians * 180.0 / std::f64::consts::PI)
+pub fn get_tool_definitions() -> Vec<Value> {
+    vec![
+        Sin::definition(),
+        Cos::definition(),
+        Tan::definition(),
+        Asin::definition(),
+        Acos::definition(),
+        Atan::definition(),
+        LawOfCosines::definition(),
+        LawOfSines::definition(),
+        DegreesToRadians::definition(),
+        RadiansToDegrees::definition(),
+    ]
+}
+
+/// Extended documentation (formula and worked example) for each tool in this module
+pub fn get_tool_documentation() -> Vec<Value> {
+    vec![
+        Sin::documentation(),
+        Cos::documentation(),
+        Tan::documentation(),
+        Asin::documentation(),
+        Acos::documentation(),
+        Atan::documentation(),
+        LawOfCosines::documentation(),
+        LawOfSines::documentation(),
+        DegreesToRadians::documentation(),
+        RadiansToDegrees::documentation(),
+    ]
+}
+
+pub fn execute(name: &str, arguments: &Value, ctx: &ToolContext) -> McpResult<Value> {
+    match name {
+        Sin::NAME => Sin::call_with_context(arguments, ctx),
+        Cos::NAME => Cos::call_with_context(arguments, ctx),
+        Tan::NAME => Tan::call_with_context(arguments, ctx),
+        Asin::NAME => Asin::call_with_context(arguments, ctx),
+        Acos::NAME => Acos::call_with_context(arguments, ctx),
+        Atan::NAME => Atan::call_with_context(arguments, ctx),
+        LawOfCosines::NAME => LawOfCosines::call_with_context(arguments, ctx),
+        LawOfSines::NAME => LawOfSines::call_with_context(arguments, ctx),
+        DegreesToRadians::NAME => DegreesToRadians::call_with_context(arguments, ctx),
+        RadiansToDegrees::NAME => RadiansToDegrees::call_with_context(arguments, ctx),
+        _ => Err(crate::error::McpError::tool_error(format!(
+            "Unknown trigonometry tool: {}",
+            name
+        ))),
+    }
 }
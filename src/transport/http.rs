@@ -0,0 +1,80 @@
+use crate::config::Config;
+use crate::metrics;
+use crate::service::MathService;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::info;
+
+/// Path the MCP endpoint is mounted at
+pub const MCP_PATH: &str = "/mcp";
+/// Path of the Prometheus metrics page
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Build the HTTP router serving MCP streamable HTTP at [`MCP_PATH`] and the
+/// tool metrics at [`METRICS_PATH`].
+///
+/// Each client session (one `initialize` handshake) gets its own
+/// `MathService`, built from the shared configuration.
+pub fn router(config: Arc<Config>) -> Router {
+    let service = StreamableHttpService::new(
+        move || Ok(MathService::with_config(config.clone())),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    Router::new()
+        .route(METRICS_PATH, get(render_metrics))
+        .nest_service(MCP_PATH, service)
+}
+
+async fn render_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, metrics::PROMETHEUS_CONTENT_TYPE)],
+        metrics::global().render_prometheus(),
+    )
+}
+
+/// Serve MCP over HTTP on an already-bound listener until `shutdown` resolves.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::transport::http;
+/// use std::sync::Arc;
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let config = Arc::new(Config::new());
+/// let listener = tokio::net::TcpListener::bind(config.http_bind).await?;
+/// http::serve(listener, config, async {
+///     let _ = tokio::signal::ctrl_c().await;
+/// })
+/// .await
+/// # }
+/// ```
+pub async fn serve<F>(
+    listener: TcpListener,
+    config: Arc<Config>,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    info!(
+        "Serving MCP over HTTP at http://{}{}",
+        listener.local_addr()?,
+        MCP_PATH
+    );
+
+    axum::serve(listener, router(config))
+        .with_graceful_shutdown(shutdown)
+        .await
+}
@@ -0,0 +1,206 @@
+use crate::error::McpError;
+use crate::protocol::methods;
+use rmcp::{
+    model::{
+        ClientJsonRpcMessage, ClientNotification, ClientRequest, ErrorData,
+        InitializedNotification, JsonRpcNotification, JsonRpcRequest, JsonRpcVersion2_0, RequestId,
+        ServerJsonRpcMessage,
+    },
+    service::RoleServer,
+    transport::Transport,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Methods whose decode failures are reported as bad params rather than unknown methods
+const KNOWN_METHODS: &[&str] = &[
+    methods::INITIALIZE,
+    methods::TOOLS_LIST,
+    methods::TOOLS_CALL,
+    methods::RESOURCES_LIST,
+    methods::RESOURCES_READ,
+    methods::PROMPTS_LIST,
+    methods::PROMPTS_GET,
+];
+
+/// Newline-delimited JSON-RPC transport that tolerates messages rmcp cannot decode.
+///
+/// rmcp's stock line transport ends the session on the first line it fails to
+/// deserialize, so a single request for an unknown method would take the whole
+/// server down. This transport answers such requests with a JSON-RPC error
+/// (`-32601` for an unknown method, `-32602` for bad params) and keeps
+/// reading. Lines that are not JSON get a `-32700` parse error; undecodable
+/// notifications are logged and skipped.
+///
+/// rmcp also refuses requests until the client sends
+/// `notifications/initialized`, which some clients skip; when the next message
+/// after `initialize` is anything else, the notification is filled in first.
+pub struct LineTransport<R, W> {
+    lines: Lines<BufReader<R>>,
+    writer: Arc<Mutex<W>>,
+    awaiting_initialized: bool,
+    pending: Option<ClientJsonRpcMessage>,
+}
+
+impl<R, W> LineTransport<R, W>
+where
+    R: AsyncRead + Send + Unpin,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    /// Create a transport reading requests from `read` and writing responses to `write`
+    pub fn new(read: R, write: W) -> Self {
+        Self {
+            lines: BufReader::new(read).lines(),
+            writer: Arc::new(Mutex::new(write)),
+            awaiting_initialized: false,
+            pending: None,
+        }
+    }
+}
+
+/// Create a line transport over the process's stdin and stdout
+pub fn stdio() -> LineTransport<tokio::io::Stdin, tokio::io::Stdout> {
+    LineTransport::new(tokio::io::stdin(), tokio::io::stdout())
+}
+
+/// Write `message` to `writer` as one line of JSON
+async fn write_line<W>(writer: &Mutex<W>, message: &impl Serialize) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    let mut writer = writer.lock().await;
+    writer.write_all(&line).await?;
+    writer.flush().await
+}
+
+impl<R, W> Transport<RoleServer> for LineTransport<R, W>
+where
+    R: AsyncRead + Send + Unpin,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    type Error = std::io::Error;
+
+    fn send(
+        &mut self,
+        item: ServerJsonRpcMessage,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'static {
+        let writer = Arc::clone(&self.writer);
+        async move { write_line(&writer, &item).await }
+    }
+
+    async fn receive(&mut self) -> Option<ClientJsonRpcMessage> {
+        if let Some(message) = self.pending.take() {
+            return Some(message);
+        }
+        loop {
+            let line = match self.lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(e) => {
+                    warn!("Error reading from stream: {}", e);
+                    return None;
+                }
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<ClientJsonRpcMessage>(line) {
+                Ok(message) => return Some(self.after_initialize(message)),
+                Err(e) => self.reject(line, e).await,
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.writer.lock().await.shutdown().await
+    }
+}
+
+impl<R, W> LineTransport<R, W>
+where
+    R: AsyncRead + Send + Unpin,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    /// Pass `message` on, putting `notifications/initialized` in front of it
+    /// when it follows `initialize` without one
+    fn after_initialize(&mut self, message: ClientJsonRpcMessage) -> ClientJsonRpcMessage {
+        if !std::mem::take(&mut self.awaiting_initialized) {
+            self.awaiting_initialized = matches!(
+                &message,
+                ClientJsonRpcMessage::Request(JsonRpcRequest {
+                    request: ClientRequest::InitializeRequest(_),
+                    ..
+                })
+            );
+            return message;
+        }
+        if let ClientJsonRpcMessage::Notification(JsonRpcNotification {
+            notification: ClientNotification::InitializedNotification(_),
+            ..
+        }) = &message
+        {
+            return message;
+        }
+        debug!("Client skipped notifications/initialized");
+        self.pending = Some(message);
+        ClientJsonRpcMessage::Notification(JsonRpcNotification {
+            jsonrpc: JsonRpcVersion2_0,
+            notification: ClientNotification::InitializedNotification(
+                InitializedNotification::default(),
+            ),
+        })
+    }
+
+    /// Respond to a line that is not a valid client message.
+    ///
+    /// Lines that are not JSON and requests (anything with an `id` and a
+    /// `method`) always get an error response so the client is never left
+    /// waiting; everything else is dropped.
+    async fn reject(&mut self, line: &str, error: serde_json::Error) {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            debug!("Malformed JSON message: {}", error);
+            // The request's id is unknown, so the response has a null id
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": ErrorData::from(McpError::parse_error(format!("Parse error: {}", error))),
+            });
+            if let Err(e) = write_line(&self.writer, &response).await {
+                warn!("Failed to send parse error response: {}", e);
+            }
+            return;
+        };
+
+        let method = value.get("method").and_then(|m| m.as_str());
+        let id = value
+            .get("id")
+            .and_then(|id| serde_json::from_value::<RequestId>(id.clone()).ok());
+
+        match (method, id) {
+            (Some(method), Some(id)) => {
+                let error = if KNOWN_METHODS.contains(&method) {
+                    debug!(method = %method, error = %error, "Invalid params");
+                    McpError::invalid_params(format!("Invalid params for {}: {}", method, error))
+                } else {
+                    debug!(method = %method, "Unknown method");
+                    McpError::method_not_found(method)
+                };
+                let response = ServerJsonRpcMessage::error(error.into(), id);
+                if let Err(e) = write_line(&self.writer, &response).await {
+                    warn!("Failed to send error response: {}", e);
+                }
+            }
+            _ => debug!("Ignoring undecodable message: {}", error),
+        }
+    }
+}
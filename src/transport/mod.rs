@@ -0,0 +1,15 @@
+//! Transports the MCP server can be exposed over.
+//!
+//! - [`stdio`]: newline-delimited JSON-RPC over stdin/stdout, for clients that
+//!   spawn the server as a child process (Claude Desktop).
+//! - [`http`]: MCP streamable HTTP, for a single long-running server shared by
+//!   several clients.
+//! - [`socket`]: newline-delimited JSON-RPC over TCP or Unix socket
+//!   connections, one session per connection, for sidecar processes on the
+//!   same host.
+
+pub mod http;
+mod line;
+pub mod socket;
+
+pub use line::{stdio, LineTransport};
@@ -0,0 +1,95 @@
+use super::LineTransport;
+use crate::config::Config;
+use crate::service::MathService;
+use rmcp::ServiceExt;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tracing::{debug, info, warn};
+
+#[cfg(unix)]
+use tokio::net::UnixListener;
+
+/// Accept TCP connections until `shutdown` resolves, serving each one as its own session.
+///
+/// Connections speak the same newline-delimited JSON-RPC as stdio.
+pub async fn serve_tcp<F>(
+    listener: TcpListener,
+    config: Arc<Config>,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()>,
+{
+    info!("Serving MCP over TCP at {}", listener.local_addr()?);
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    let (read, write) = stream.into_split();
+                    spawn_session(read, write, config.clone(), peer.to_string());
+                }
+                Err(e) => warn!("Failed to accept TCP connection: {}", e),
+            },
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Accept Unix socket connections until `shutdown` resolves, serving each one as its own session.
+///
+/// Connections speak the same newline-delimited JSON-RPC as stdio.
+#[cfg(unix)]
+pub async fn serve_unix<F>(
+    listener: UnixListener,
+    config: Arc<Config>,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()>,
+{
+    info!(
+        "Serving MCP over Unix socket at {:?}",
+        listener.local_addr()?
+    );
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    let (read, write) = stream.into_split();
+                    spawn_session(read, write, config.clone(), "unix".to_string());
+                }
+                Err(e) => warn!("Failed to accept Unix socket connection: {}", e),
+            },
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Run a new `MathService` session over one connection in the background
+fn spawn_session<R, W>(read: R, write: W, config: Arc<Config>, peer: String)
+where
+    R: AsyncRead + Send + Unpin + 'static,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    tokio::spawn(async move {
+        let service = MathService::with_config(config);
+        let session = service.session().id();
+        debug!(session, peer = %peer, "Session started");
+
+        match service.serve(LineTransport::new(read, write)).await {
+            Ok(running) => {
+                if let Err(e) = running.waiting().await {
+                    warn!(session, "Session task failed: {}", e);
+                }
+            }
+            Err(e) => debug!(session, peer = %peer, "Session ended during initialization: {}", e),
+        }
+        debug!(session, peer = %peer, "Session closed");
+    });
+}
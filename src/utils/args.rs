@@ -1,5 +1,32 @@
-use crate::error::{McpError, McpResult};
+use crate::error::McpResult;
+use crate::utils::limits::Limits;
+use crate::utils::validation::{join_path, within, InvalidArgument};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::f64::consts::{E, PI, TAU};
+
+/// Schema keyword giving the unit of a number argument
+pub const UNIT_KEYWORD: &str = "x-unit";
+
+/// [`UNIT_KEYWORD`] of arguments given in percent, e.g. 5 for 5%
+pub const PERCENT_UNIT: &str = "percent";
+
+/// The golden ratio, (1 + √5) / 2
+const PHI: f64 = 1.618_033_988_749_895;
+
+/// Constants a lenient number may name, matched case-insensitively
+const CONSTANTS: &[(&str, f64)] = &[
+    ("pi", PI),
+    ("π", PI),
+    ("tau", TAU),
+    ("τ", TAU),
+    ("phi", PHI),
+    ("φ", PHI),
+    ("e", E),
+];
 
 /// Extract a required number argument from JSON.
 ///
@@ -25,15 +52,16 @@ use serde_json::Value;
 /// ```
 pub fn get_number(arguments: &Value, key: &str) -> McpResult<f64> {
     let value = arguments[key].as_f64().ok_or_else(|| {
-        McpError::invalid_params(format!("Invalid argument: {} must be a number", key))
+        expected(arguments.get(key), key, "a number")
+            .invalid_params(format!("Invalid argument: {} must be a number", key))
     })?;
 
     // Validate the number is finite
     if !value.is_finite() {
-        return Err(McpError::validation_error(format!(
-            "Invalid argument: {} must be a finite number",
-            key
-        )));
+        return Err(InvalidArgument::new("a finite number")
+            .at(key)
+            .received(value.to_string())
+            .validation_error(format!("Invalid argument: {} must be a finite number", key)));
     }
 
     Ok(value)
@@ -77,41 +105,323 @@ pub fn get_number_opt(arguments: &Value, key: &str) -> Option<f64> {
 /// use rust_math_mcp::utils::args::get_number_array;
 /// use serde_json::json;
 ///
+/// use rust_math_mcp::utils::limits::Limits;
+///
 /// let args = json!({ "numbers": [1.0, 2.0, 3.0] });
-/// let numbers = get_number_array(&args, "numbers").unwrap(); // vec![1.0, 2.0, 3.0]
+/// let numbers = get_number_array(&args, "numbers", &Limits::default()).unwrap(); // vec![1.0, 2.0, 3.0]
 /// ```
-pub fn get_number_array(arguments: &Value, key: &str) -> McpResult<Vec<f64>> {
-    use crate::config::Config;
-    use crate::utils::validation::validate_array_size;
-
+pub fn get_number_array(arguments: &Value, key: &str, limits: &Limits) -> McpResult<Vec<f64>> {
     let arr = arguments[key].as_array().ok_or_else(|| {
-        McpError::invalid_params(format!("Invalid arguments: {} must be an array", key))
+        expected(arguments.get(key), key, "an array of numbers")
+            .invalid_params(format!("Invalid arguments: {} must be an array", key))
     })?;
 
-    // Validate array size
-    let config = Config::new();
-    validate_array_size(arr.len(), &config)?;
-
     let numbers: Vec<f64> = arr.iter().filter_map(|v| v.as_f64()).collect();
 
-    if numbers.len() != arr.len() {
-        return Err(McpError::invalid_params(format!(
-            "Invalid arguments: {} must be an array of numbers",
-            key
-        )));
+    if let Some((idx, item)) = arr.iter().enumerate().find(|(_, v)| !v.is_number()) {
+        return Err(
+            expected(Some(item), &format!("{}[{}]", key, idx), "a number").invalid_params(format!(
+                "Invalid arguments: {} must be an array of numbers",
+                key
+            )),
+        );
     }
 
+    check_number_array(&numbers, key, limits)?;
+
+    Ok(numbers)
+}
+
+/// Validate an already-parsed array of numbers.
+///
+/// Checks the array size against `limits` and that every element is finite.
+/// Typed tool inputs call this for their array fields, since parsing alone
+/// does not enforce server limits.
+///
+/// # Arguments
+///
+/// * `numbers` - The parsed numbers
+/// * `key` - Argument name used in error messages
+/// * `limits` - Limits of the current call
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::args::check_number_array;
+/// use rust_math_mcp::utils::limits::Limits;
+///
+/// let limits = Limits::default();
+/// assert!(check_number_array(&[1.0, 2.0], "numbers", &limits).is_ok());
+/// assert!(check_number_array(&[1.0, f64::NAN], "numbers", &limits).is_err());
+/// ```
+pub fn check_number_array(numbers: &[f64], key: &str, limits: &Limits) -> McpResult<()> {
+    // Validate array size
+    limits
+        .check_array_size(numbers.len())
+        .map_err(|e| within(e, key))?;
+
     // Validate all numbers are finite
     for (idx, num) in numbers.iter().enumerate() {
         if !num.is_finite() {
-            return Err(McpError::validation_error(format!(
-                "Invalid argument: {}[{}] must be a finite number",
-                key, idx
-            )));
+            return Err(InvalidArgument::new("a finite number")
+                .at(format!("{}[{}]", key, idx))
+                .received(num.to_string())
+                .validation_error(format!(
+                    "Invalid argument: {}[{}] must be a finite number",
+                    key, idx
+                )));
         }
     }
 
-    Ok(numbers)
+    Ok(())
+}
+
+/// Describe the argument at `path` as not being `what`, suggesting how to fix
+/// the common mistakes of omitting it or quoting a number
+fn expected(received: Option<&Value>, path: &str, what: &str) -> InvalidArgument {
+    let argument = InvalidArgument::new(what).at(path);
+    match received {
+        None => argument.suggestion(format!("Add the required argument '{}'", path)),
+        Some(Value::String(text)) if text.trim().parse::<f64>().is_ok() => argument
+            .received(text.as_str())
+            .suggestion("Pass the number without quotes"),
+        Some(value) => argument.received(value.clone()),
+    }
+}
+
+/// A number argument given in percent (5 for 5%) rather than as a fraction.
+///
+/// Its schema is a number marked with [`UNIT_KEYWORD`], so lenient parsing
+/// reads `"5%"` for it as 5 instead of 0.05.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct Percent(pub f64);
+
+impl JsonSchema for Percent {
+    fn schema_name() -> String {
+        "Percent".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = f64::json_schema(gen).into_object();
+        schema
+            .extensions
+            .insert(UNIT_KEYWORD.to_string(), Value::from(PERCENT_UNIT));
+        schema.into()
+    }
+}
+
+/// How a number given as a string was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberFormat {
+    /// A plain number, e.g. `"3.5"` or `"1e-3"`
+    NumericString,
+    /// A ratio of two numbers, e.g. `"1/3"`
+    Fraction,
+    /// A percentage, e.g. `"5%"` for 0.05, or 5 for an argument in percent
+    Percentage,
+    /// A named constant with an optional factor or divisor, e.g. `"2pi"` or `"pi/4"`
+    Constant,
+}
+
+/// How a string argument was read as a number, reported back to the client
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Interpretation {
+    /// Path to the argument, e.g. `numbers[1]`
+    pub argument: String,
+    /// The string received
+    pub input: String,
+    /// The number it was read as
+    pub value: f64,
+    /// How it was written
+    pub format: NumberFormat,
+}
+
+/// Read a number written as text: a numeric string (`"3.5"`, `"1e-3"`), a
+/// fraction (`"1/3"`), a percentage (`"5%"`) or a named constant (`pi`, `e`,
+/// `tau`, `phi`) with an optional factor and divisor (`"2pi"`, `"pi/4"`).
+///
+/// Returns `None` for anything else, including results that are not finite.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::args::{parse_number, NumberFormat};
+///
+/// assert_eq!(parse_number("1/4"), Some((0.25, NumberFormat::Fraction)));
+/// assert_eq!(parse_number("5%"), Some((0.05, NumberFormat::Percentage)));
+/// assert_eq!(parse_number("2pi"), Some((std::f64::consts::TAU, NumberFormat::Constant)));
+/// assert_eq!(parse_number("1/0"), None);
+/// ```
+pub fn parse_number(text: &str) -> Option<(f64, NumberFormat)> {
+    parse_number_in(text, false)
+}
+
+/// [`parse_number`] for an argument that is itself in percent when
+/// `in_percent`, so that `"5%"` is read as 5
+fn parse_number_in(text: &str, in_percent: bool) -> Option<(f64, NumberFormat)> {
+    let text = text.trim();
+    let (value, format) = if let Some(percent) = text.strip_suffix('%') {
+        let percent = percent.trim().parse::<f64>().ok()?;
+        let value = if in_percent { percent } else { percent / 100.0 };
+        (value, NumberFormat::Percentage)
+    } else if let Some((numerator, denominator)) = text.split_once('/') {
+        let (numerator, named_above) = term(numerator)?;
+        let (denominator, named_below) = term(denominator)?;
+        if denominator == 0.0 {
+            return None;
+        }
+        let format = if named_above || named_below {
+            NumberFormat::Constant
+        } else {
+            NumberFormat::Fraction
+        };
+        (numerator / denominator, format)
+    } else {
+        match term(text)? {
+            (value, true) => (value, NumberFormat::Constant),
+            (value, false) => (value, NumberFormat::NumericString),
+        }
+    };
+    value.is_finite().then_some((value, format))
+}
+
+/// A number, or a constant with an optional factor (`2pi`, `-e`, `3*tau`);
+/// the flag says whether a constant was named
+fn term(text: &str) -> Option<(f64, bool)> {
+    let text = text.trim();
+    if let Ok(value) = text.parse::<f64>() {
+        return Some((value, false));
+    }
+    let lower = text.to_lowercase();
+    let (factor, constant) = CONSTANTS
+        .iter()
+        .find_map(|&(name, value)| Some((lower.strip_suffix(name)?, value)))?;
+    let factor = match factor.trim().trim_end_matches('*').trim() {
+        "" | "+" => 1.0,
+        "-" => -1.0,
+        factor => factor.parse::<f64>().ok()?,
+    };
+    Some((factor * constant, true))
+}
+
+/// Extract a required number argument, also accepting the strings
+/// [`parse_number`] reads; the [`Interpretation`] says how a string was read.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::args::get_number_lenient;
+/// use serde_json::json;
+///
+/// let (rate, interpretation) = get_number_lenient(&json!({ "rate": "5%" }), "rate").unwrap();
+/// assert_eq!(rate, 0.05);
+/// assert_eq!(interpretation.unwrap().input, "5%");
+/// ```
+pub fn get_number_lenient(
+    arguments: &Value,
+    key: &str,
+) -> McpResult<(f64, Option<Interpretation>)> {
+    let Some(text) = arguments[key].as_str() else {
+        return get_number(arguments, key).map(|value| (value, None));
+    };
+    let (value, format) = parse_number(text).ok_or_else(|| {
+        InvalidArgument::new("a number, fraction, percentage or constant such as pi")
+            .at(key)
+            .received(text)
+            .invalid_params(format!("Invalid argument: {} must be a number", key))
+    })?;
+    let interpretation = Interpretation {
+        argument: key.to_string(),
+        input: text.to_string(),
+        value,
+        format,
+    };
+    Ok((value, Some(interpretation)))
+}
+
+/// Replace the strings in `arguments` that `schema` says should be numbers
+/// with the numbers [`parse_number`] reads them as, returning how each was
+/// read. Strings it cannot read are left for validation to reject.
+///
+/// Arguments whose schema has the [`PERCENT_UNIT`] unit keep percentages in
+/// percent, so `"5%"` becomes 5 there.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::args::coerce_numbers;
+/// use serde_json::json;
+///
+/// let schema = json!({ "properties": { "numbers": { "type": "array", "items": { "type": "number" } } } });
+/// let mut arguments = json!({ "numbers": [1, "1/2", "pi"] });
+/// let interpretations = coerce_numbers(&mut arguments, &schema);
+/// assert_eq!(arguments["numbers"][1], 0.5);
+/// assert_eq!(interpretations[1].argument, "numbers[2]");
+/// ```
+pub fn coerce_numbers(arguments: &mut Value, schema: &Value) -> Vec<Interpretation> {
+    let mut interpretations = Vec::new();
+    coerce(arguments, schema, "", &mut interpretations);
+    interpretations
+}
+
+fn coerce(value: &mut Value, schema: &Value, path: &str, found: &mut Vec<Interpretation>) {
+    match value {
+        Value::String(text) if wants_number(schema) => {
+            let in_percent = schema.get(UNIT_KEYWORD) == Some(&Value::from(PERCENT_UNIT));
+            if let Some((number, format)) = parse_number_in(text, in_percent) {
+                found.push(Interpretation {
+                    argument: path.to_string(),
+                    input: std::mem::take(text),
+                    value: number,
+                    format,
+                });
+                *value = Value::from(number);
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter_mut().enumerate() {
+                    let path = join_path(path, &format!("[{}]", index));
+                    coerce(item, item_schema, &path, found);
+                }
+            }
+        }
+        Value::Object(fields) => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return;
+            };
+            for (name, field) in fields.iter_mut() {
+                if let Some(property) = properties.get(name) {
+                    coerce(field, property, &join_path(path, name), found);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `schema` takes a number but not a string
+fn wants_number(schema: &Value) -> bool {
+    let alternatives = schema.get("anyOf").or_else(|| schema.get("oneOf"));
+    let schemas: Vec<&Value> = match alternatives.and_then(Value::as_array) {
+        Some(alternatives) => alternatives.iter().collect(),
+        None => vec![schema],
+    };
+    let types: Vec<&str> = schemas
+        .iter()
+        .filter_map(|schema| schema.get("type"))
+        .flat_map(|types| match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            types => types.as_str().into_iter().collect::<Vec<_>>(),
+        })
+        .collect();
+    types.iter().any(|t| *t == "number" || *t == "integer") && !types.contains(&"string")
 }
 
 /// Extract an optional boolean argument from JSON
@@ -155,21 +465,120 @@ mod tests {
     #[test]
     fn test_get_number_array() {
         let args = json!({ "numbers": [1.0, 2.0, 3.0] });
-        let result = get_number_array(&args, "numbers").unwrap();
+        let result = get_number_array(&args, "numbers", &Limits::default()).unwrap();
         assert_eq!(result, vec![1.0, 2.0, 3.0]);
     }
 
     #[test]
     fn test_get_number_array_empty() {
         let args = json!({ "numbers": [] });
-        let result = get_number_array(&args, "numbers").unwrap();
+        let result = get_number_array(&args, "numbers", &Limits::default()).unwrap();
         assert_eq!(result, Vec::<f64>::new());
     }
 
     #[test]
     fn test_get_number_array_invalid() {
         let args = json!({ "numbers": [1.0, "not a number", 3.0] });
-        assert!(get_number_array(&args, "numbers").is_err());
+        assert!(get_number_array(&args, "numbers", &Limits::default()).is_err());
+    }
+
+    #[test]
+    fn test_get_number_array_error_data() {
+        let args = json!({ "numbers": [1.0, 2.0, "3"] });
+        let err = get_number_array(&args, "numbers", &Limits::default()).unwrap_err();
+        let data = err.data.unwrap();
+        assert_eq!(data["argument"], "numbers[2]");
+        assert_eq!(data["expected"], "a number");
+        assert_eq!(data["received"], "3");
+        assert_eq!(data["suggestion"], "Pass the number without quotes");
+
+        let err = get_number(&json!({}), "radius").unwrap_err();
+        assert_eq!(err.data.unwrap()["argument"], "radius");
+    }
+
+    #[test]
+    fn test_check_number_array_size_error_data() {
+        let limits = Limits::new(crate::config::Config {
+            max_array_size: 2,
+            ..Default::default()
+        });
+        let err = check_number_array(&[1.0, 2.0, 3.0], "numbers", &limits).unwrap_err();
+        let data = err.data.unwrap();
+        assert_eq!(data["argument"], "numbers");
+        assert_eq!(data["received"], 3);
+    }
+
+    #[test]
+    fn test_parse_number_formats() {
+        assert_eq!(
+            parse_number(" 3.5 "),
+            Some((3.5, NumberFormat::NumericString))
+        );
+        assert_eq!(
+            parse_number("1e-3"),
+            Some((0.001, NumberFormat::NumericString))
+        );
+        assert_eq!(parse_number("-1/4"), Some((-0.25, NumberFormat::Fraction)));
+        assert_eq!(
+            parse_number("12.5%"),
+            Some((0.125, NumberFormat::Percentage))
+        );
+        assert_eq!(parse_number("PI"), Some((PI, NumberFormat::Constant)));
+        assert_eq!(
+            parse_number("pi/2"),
+            Some((PI / 2.0, NumberFormat::Constant))
+        );
+        assert_eq!(
+            parse_number("-2*e"),
+            Some((-2.0 * E, NumberFormat::Constant))
+        );
+        assert_eq!(parse_number("tau"), Some((TAU, NumberFormat::Constant)));
+        assert_eq!(parse_number("φ"), Some((PHI, NumberFormat::Constant)));
+        assert_eq!(parse_number("inf"), None);
+        assert_eq!(parse_number("two"), None);
+        assert_eq!(parse_number("1/2/3"), None);
+    }
+
+    #[test]
+    fn test_get_number_lenient() {
+        let args = json!({ "a": 2.0, "b": "1/3", "c": "lots" });
+        assert_eq!(get_number_lenient(&args, "a").unwrap(), (2.0, None));
+        let (value, interpretation) = get_number_lenient(&args, "b").unwrap();
+        assert_eq!(value, 1.0 / 3.0);
+        assert_eq!(interpretation.unwrap().format, NumberFormat::Fraction);
+        let err = get_number_lenient(&args, "c").unwrap_err();
+        assert_eq!(err.data.unwrap()["argument"], "c");
+    }
+
+    #[test]
+    fn test_coerce_numbers_follows_schema() {
+        let schema = json!({ "properties": {
+            "x": { "type": "number" },
+            "label": { "type": "string" },
+            "either": { "anyOf": [{ "type": "number" }, { "type": "string" }] }
+        } });
+        let mut args = json!({ "x": "2pi", "label": "3", "either": "4", "other": "5" });
+        let interpretations = coerce_numbers(&mut args, &schema);
+        assert_eq!(
+            args,
+            json!({ "x": TAU, "label": "3", "either": "4", "other": "5" })
+        );
+        assert_eq!(interpretations.len(), 1);
+        assert_eq!(interpretations[0].input, "2pi");
+    }
+
+    #[test]
+    fn test_coerce_percent_arguments() {
+        let schema = json!({ "properties": {
+            "rate": { "type": "number" },
+            "percent": { "type": "number", "x-unit": "percent" }
+        } });
+        let mut args = json!({ "rate": "5%", "percent": "5%" });
+        coerce_numbers(&mut args, &schema);
+        assert_eq!(args, json!({ "rate": 0.05, "percent": 5.0 }));
+
+        let schema = crate::tools::traits::schema_for::<Option<Percent>>();
+        assert_eq!(schema[UNIT_KEYWORD], PERCENT_UNIT);
     }
 
     #[test]
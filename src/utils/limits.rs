@@ -1,20 +1,42 @@
 use crate::config::Config;
 use crate::error::{McpError, McpResult};
-use crate::utils::validation::validate_array_size;
+use crate::utils::validation::{validate_array_size, InvalidArgument};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Resource limits and constraints manager.
 ///
 /// Provides centralized management of resource limits including array sizes,
-/// timeouts, and other constraints to prevent resource exhaustion.
+/// timeouts, and other constraints to prevent resource exhaustion. Every tool
+/// call gets one through its [`crate::tools::ToolContext`]; clones share the
+/// same configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::utils::limits::Limits;
+///
+/// let limits = Limits::new(Config { max_array_size: 3, ..Config::default() });
+/// assert!(limits.check_array_size(3).is_ok());
+/// assert_eq!(limits.check_array_size(4).unwrap_err().code, -32002);
+/// ```
+#[derive(Debug, Clone)]
 pub struct Limits {
-    config: Config,
+    config: Arc<Config>,
 }
 
 impl Limits {
     /// Create a new Limits instance
-    pub fn new(config: Config) -> Self {
-        Self { config }
+    pub fn new(config: impl Into<Arc<Config>>) -> Self {
+        Self {
+            config: config.into(),
+        }
+    }
+
+    /// The configuration the limits come from
+    pub fn config(&self) -> &Arc<Config> {
+        &self.config
     }
 
     /// Validate array size against limits
@@ -22,7 +44,45 @@ impl Limits {
         validate_array_size(size, &self.config)
     }
 
-    /// Check if operation should timeout (placeholder for future async implementation)
+    /// Validate the number of operations in a batch against the limit
+    pub fn check_batch_size(&self, size: usize) -> McpResult<()> {
+        if size > self.config.max_batch_size {
+            return Err(InvalidArgument::new(format!(
+                "at most {} operations",
+                self.config.max_batch_size
+            ))
+            .at("operations")
+            .received(size)
+            .suggestion("Split the operations across several batches")
+            .invalid_params(format!(
+                "Batch size exceeds maximum of {} operations",
+                self.config.max_batch_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate a number of decimal places to round to against the limit.
+    ///
+    /// Negative places (rounding to tens, hundreds, ...) are allowed.
+    pub fn check_decimal_places(&self, places: i32) -> McpResult<()> {
+        if places > self.config.max_decimal_places {
+            return Err(InvalidArgument::new(format!(
+                "at most {} decimal places",
+                self.config.max_decimal_places
+            ))
+            .received(places)
+            .validation_error(format!(
+                "Decimal places {} exceeds maximum of {}",
+                places, self.config.max_decimal_places
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check if an operation started at `start` has run longer than `max_duration`.
+    ///
+    /// Tool calls get their deadline from [`crate::tools::ToolContext`] instead.
     pub fn check_timeout(&self, start: Instant, max_duration: Duration) -> McpResult<()> {
         if start.elapsed() > max_duration {
             return Err(McpError::resource_limit(format!(
@@ -38,6 +98,11 @@ impl Limits {
         self.config.max_array_size
     }
 
+    /// Get maximum number of operations in a batch
+    pub fn max_batch_size(&self) -> usize {
+        self.config.max_batch_size
+    }
+
     /// Get maximum decimal places
     pub fn max_decimal_places(&self) -> i32 {
         self.config.max_decimal_places
@@ -1,4 +1,5 @@
 pub mod args;
 pub mod limits;
+pub mod output;
 pub mod rate_limiter;
 pub mod validation;
@@ -0,0 +1,49 @@
+use crate::config::Config;
+use serde_json::Value;
+
+/// Apply the `[output]` settings in `config` to a tool result: round every
+/// floating-point number to `output_decimal_places`, when set.
+///
+/// The server and the command line both call this, so they print the same
+/// numbers.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::config::Config;
+/// use rust_math_mcp::utils::output::apply_output_settings;
+/// use serde_json::json;
+///
+/// let config = Config {
+///     output_decimal_places: Some(2),
+///     ..Config::new()
+/// };
+/// let mut result = json!({ "result": 3.14159, "count": 3 });
+/// apply_output_settings(&mut result, &config);
+/// assert_eq!(result, json!({ "result": 3.14, "count": 3 }));
+/// ```
+pub fn apply_output_settings(result: &mut Value, config: &Config) {
+    if let Some(places) = config.output_decimal_places {
+        round_numbers(result, places);
+    }
+}
+
+/// Round every floating-point number in `value` to `places` decimal places
+fn round_numbers(value: &mut Value, places: u32) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let factor = 10f64.powi(places as i32);
+            let rounded = number.as_f64().map(|x| (x * factor).round() / factor);
+            if let Some(rounded) = rounded.and_then(serde_json::Number::from_f64) {
+                *number = rounded;
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| round_numbers(item, places)),
+        Value::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| round_numbers(field, places)),
+        _ => {}
+    }
+}
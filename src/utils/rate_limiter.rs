@@ -74,27 +74,76 @@ impl RateLimiter {
     /// assert!(!limiter.check_rate_limit()); // 3rd request: rate limited
     /// ```
     pub fn check_rate_limit(&self) -> bool {
-        let mut state = self.state.lock().unwrap_or_else(|poisoned| {
-            // Recover from poisoned mutex by taking ownership of the inner data
-            // This prevents cascading failures if a thread panics while holding the lock
-            poisoned.into_inner()
-        });
+        self.try_acquire(1.0).is_ok()
+    }
 
-        // Refill tokens based on elapsed time
-        let now = Instant::now();
-        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
-        let tokens_to_add = elapsed * state.refill_rate;
-
-        state.tokens = (state.tokens + tokens_to_add).min(state.max_tokens);
-        state.last_refill = now;
-
-        // Check if we have a token available
-        if state.tokens >= 1.0 {
-            state.tokens -= 1.0;
-            true
-        } else {
-            false
+    /// Take `cost` tokens, or report how long until that many are available.
+    ///
+    /// Costs above the burst capacity are capped at it, so an expensive
+    /// operation can still run once the bucket is full.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_math_mcp::utils::rate_limiter::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let limiter = RateLimiter::new(10, Duration::from_secs(1));
+    ///
+    /// assert!(limiter.try_acquire(8.0).is_ok());
+    /// let retry_after = limiter.try_acquire(5.0).unwrap_err();
+    /// assert!(retry_after <= Duration::from_millis(300));
+    /// ```
+    pub fn try_acquire(&self, cost: f64) -> Result<(), Duration> {
+        Self::try_acquire_all(&[self], cost).map_err(|(_, retry_after)| retry_after)
+    }
+
+    /// Take `cost` tokens from every limiter in `limiters`, or from none of them.
+    ///
+    /// On failure, returns the index of the first limiter without enough
+    /// tokens and how long until it has them. The limiters are locked in
+    /// order, so callers sharing limiters should pass them in the same order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_math_mcp::utils::rate_limiter::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let tool = RateLimiter::new(5, Duration::from_secs(1));
+    /// let session = RateLimiter::new(1, Duration::from_secs(1));
+    ///
+    /// assert!(RateLimiter::try_acquire_all(&[&tool, &session], 1.0).is_ok());
+    /// let (index, _) = RateLimiter::try_acquire_all(&[&tool, &session], 1.0).unwrap_err();
+    /// assert_eq!(index, 1);
+    /// assert_eq!(tool.available_tokens().floor(), 4.0); // Left untouched
+    /// ```
+    pub fn try_acquire_all(limiters: &[&RateLimiter], cost: f64) -> Result<(), (usize, Duration)> {
+        let mut states: Vec<_> = limiters
+            .iter()
+            .map(|limiter| {
+                // Recover from poisoned mutex by taking ownership of the inner data
+                // This prevents cascading failures if a thread panics while holding the lock
+                limiter
+                    .state
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+            })
+            .collect();
+
+        // Check every bucket before taking tokens from any
+        for (index, state) in states.iter_mut().enumerate() {
+            state.refill();
+            let cost = cost.min(state.max_tokens);
+            if state.tokens < cost {
+                let missing = cost - state.tokens;
+                return Err((index, Duration::from_secs_f64(missing / state.refill_rate)));
+            }
         }
+        for state in &mut states {
+            state.tokens -= cost.min(state.max_tokens);
+        }
+        Ok(())
     }
 
     /// Get the current number of available tokens.
@@ -106,18 +155,21 @@ impl RateLimiter {
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-        // Refill tokens based on elapsed time
-        let now = Instant::now();
-        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
-        let tokens_to_add = elapsed * state.refill_rate;
-
-        state.tokens = (state.tokens + tokens_to_add).min(state.max_tokens);
-        state.last_refill = now;
-
+        state.refill();
         state.tokens
     }
 }
 
+impl RateLimiterState {
+    /// Add the tokens earned since the last refill, up to the burst capacity
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.max_tokens);
+        self.last_refill = now;
+    }
+}
+
 impl Clone for RateLimiter {
     fn clone(&self) -> Self {
         Self {
@@ -153,6 +205,36 @@ mod tests {
         assert!(limiter.check_rate_limit()); // Should be OK after refill
     }
 
+    #[test]
+    fn test_rate_limiter_cost() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(1));
+
+        assert!(limiter.try_acquire(6.0).is_ok());
+        let retry_after = limiter.try_acquire(6.0).unwrap_err();
+        assert!(retry_after > Duration::from_millis(150));
+        assert!(retry_after <= Duration::from_millis(200));
+
+        // A cost above capacity is capped rather than refused forever
+        let limiter = RateLimiter::new(5, Duration::from_secs(1));
+        assert!(limiter.try_acquire(50.0).is_ok());
+        assert!(limiter.available_tokens() < 1.0);
+    }
+
+    #[test]
+    fn test_rate_limiter_acquire_all() {
+        let first = RateLimiter::new(3, Duration::from_secs(1));
+        let second = RateLimiter::new(1, Duration::from_secs(1));
+
+        assert!(RateLimiter::try_acquire_all(&[&first, &second], 1.0).is_ok());
+        let (index, retry_after) =
+            RateLimiter::try_acquire_all(&[&first, &second], 1.0).unwrap_err();
+        assert_eq!(index, 1);
+        assert!(retry_after > Duration::ZERO);
+
+        // The refused call took nothing from the first bucket
+        assert_eq!(first.available_tokens().floor(), 2.0);
+    }
+
     #[test]
     fn test_rate_limiter_available_tokens() {
         let limiter = RateLimiter::new(5, Duration::from_secs(1));
@@ -1,5 +1,412 @@
 use crate::config::Config;
 use crate::error::{McpError, McpResult};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Structured description of an argument that failed validation, sent as the
+/// error's `data` so clients can point at the offending value.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::validation::InvalidArgument;
+///
+/// let error = InvalidArgument::new("a finite number")
+///     .at("numbers[2]")
+///     .received("NaN")
+///     .validation_error("numbers[2] must be a finite number");
+/// let data = error.data.unwrap();
+/// assert_eq!(data["argument"], "numbers[2]");
+/// assert_eq!(data["expected"], "a finite number");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvalidArgument {
+    /// Path to the argument, e.g. `operations[3].arguments.numbers[7]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument: Option<String>,
+    /// What the argument should have been
+    pub expected: String,
+    /// The value received (absent if the argument is missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub received: Option<Value>,
+    /// How to fix the argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl InvalidArgument {
+    /// Describe an argument that should have been `expected`
+    pub fn new(expected: impl Into<String>) -> Self {
+        Self {
+            argument: None,
+            expected: expected.into(),
+            received: None,
+            suggestion: None,
+        }
+    }
+
+    /// Set the path to the argument
+    pub fn at(mut self, path: impl Into<String>) -> Self {
+        self.argument = Some(path.into());
+        self
+    }
+
+    /// Set the value received
+    pub fn received(mut self, value: impl Into<Value>) -> Self {
+        self.received = Some(value.into());
+        self
+    }
+
+    /// Set a hint on how to fix the argument
+    pub fn suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Build an error with `code` and `message`, carrying this description as `data`
+    pub fn error(self, code: i32, message: impl Into<String>) -> McpError {
+        let data = serde_json::to_value(self).unwrap_or_default();
+        McpError::with_data(code, message, data)
+    }
+
+    /// Invalid params error (-32602) carrying this description
+    pub fn invalid_params(self, message: impl Into<String>) -> McpError {
+        self.error(-32602, message)
+    }
+
+    /// Validation error (-32001) carrying this description
+    pub fn validation_error(self, message: impl Into<String>) -> McpError {
+        self.error(-32001, message)
+    }
+
+    /// Resource limit error (-32002) carrying this description
+    pub fn resource_limit(self, message: impl Into<String>) -> McpError {
+        self.error(-32002, message)
+    }
+}
+
+/// Place an argument error under `path`.
+///
+/// If `error` describes an [`InvalidArgument`], its `argument` becomes
+/// relative to `path` (`numbers[7]` under `operations[3].arguments` becomes
+/// `operations[3].arguments.numbers[7]`), or `path` itself if it had none.
+/// Other errors are returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::validation::{within, InvalidArgument};
+///
+/// let error = InvalidArgument::new("a number").at("numbers[7]").invalid_params("Not a number");
+/// let error = within(error, "operations[3].arguments");
+/// assert_eq!(error.data.unwrap()["argument"], "operations[3].arguments.numbers[7]");
+/// ```
+pub fn within(mut error: McpError, path: &str) -> McpError {
+    let Some(data) = error.data.as_mut().and_then(Value::as_object_mut) else {
+        return error;
+    };
+    if !data.contains_key("expected") {
+        return error;
+    }
+    let argument = match data.get("argument").and_then(Value::as_str) {
+        Some(inner) => join_path(path, inner),
+        None => path.to_string(),
+    };
+    data.insert("argument".to_string(), Value::from(argument));
+    error
+}
+
+/// Join two argument paths: `a.b` and `c[0]` give `a.b.c[0]`, `a` and `[1]` give `a[1]`
+pub fn join_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else if child.is_empty() || child.starts_with('[') {
+        format!("{}{}", parent, child)
+    } else {
+        format!("{}.{}", parent, child)
+    }
+}
+
+/// Check a tool's `arguments` against its input `schema`.
+///
+/// Supports the keywords tool schemas use: `type`, `enum`, `required`,
+/// `properties`, `additionalProperties`, `items`, `minItems`/`maxItems`,
+/// `minimum`/`maximum` (and their exclusive forms) and `anyOf`/`oneOf`. A
+/// `null` optional argument counts as omitted. The first problem found fails
+/// with `invalid_params`, whose `data` describes the argument.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_math_mcp::utils::validation::validate_arguments;
+/// use serde_json::json;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "radius": { "type": "number", "minimum": 0 } },
+///     "required": ["radius"],
+///     "additionalProperties": false
+/// });
+/// assert!(validate_arguments("area_circle", &json!({ "radius": 2 }), &schema).is_ok());
+///
+/// let error = validate_arguments("area_circle", &json!({ "radius": -2 }), &schema).unwrap_err();
+/// assert_eq!(error.message, "Invalid arguments for area_circle: radius must be at least 0, got -2");
+/// assert_eq!(error.data.unwrap()["argument"], "radius");
+///
+/// let error = validate_arguments("area_circle", &json!({ "radius": 2, "r": 2 }), &schema).unwrap_err();
+/// assert_eq!(error.message, "Invalid arguments for area_circle: unknown argument 'r'");
+/// ```
+pub fn validate_arguments(tool: &str, arguments: &Value, schema: &Value) -> McpResult<()> {
+    match mismatch(arguments, schema, "") {
+        Some(found) => Err(found
+            .argument
+            .invalid_params(format!("Invalid arguments for {}: {}", tool, found.problem))),
+        None => Ok(()),
+    }
+}
+
+/// Find the first place where `arguments` does not match a tool's input
+/// `schema`, as [`validate_arguments`] checks it.
+///
+/// Used to locate the argument behind a parse failure, since the parser's own
+/// messages do not say where the bad value is.
+pub fn find_mismatch(arguments: &Value, schema: &Value) -> Option<InvalidArgument> {
+    mismatch(arguments, schema, "").map(|found| found.argument)
+}
+
+/// A value that does not match its schema
+struct Mismatch {
+    argument: InvalidArgument,
+    /// What is wrong, for the error message, e.g. `n must be a number, got "5"`
+    problem: String,
+}
+
+fn mismatch(value: &Value, schema: &Value, path: &str) -> Option<Mismatch> {
+    let name = if path.is_empty() { "arguments" } else { path };
+    let wrong = |expected: String| {
+        let problem = match value {
+            Value::Array(_) | Value::Object(_) => format!("{} must be {}", name, expected),
+            _ => format!("{} must be {}, got {}", name, expected, value),
+        };
+        let mut argument = InvalidArgument::new(expected).received(value.clone());
+        if !path.is_empty() {
+            argument = argument.at(path);
+        }
+        Mismatch { argument, problem }
+    };
+
+    let alternatives = schema.get("anyOf").or_else(|| schema.get("oneOf"));
+    if let Some(alternatives) = alternatives.and_then(Value::as_array) {
+        let mut mismatches = Vec::new();
+        for alternative in alternatives {
+            match mismatch(value, alternative, path) {
+                None => return None,
+                Some(found) => mismatches.push(found),
+            }
+        }
+        // Report the nested problem if only one alternative has the right type
+        let mut typed: Vec<Mismatch> = alternatives
+            .iter()
+            .zip(mismatches)
+            .filter(|(alternative, _)| matches_type(value, alternative))
+            .map(|(_, found)| found)
+            .collect();
+        if typed.len() == 1 {
+            return typed.pop();
+        }
+        let expected: Vec<String> = alternatives.iter().map(describe).collect();
+        return Some(wrong(expected.join(" or ")));
+    }
+
+    if !matches_type(value, schema) {
+        let found = wrong(describe(schema));
+        let numeric_string = value
+            .as_str()
+            .is_some_and(|s| s.trim().parse::<f64>().is_ok());
+        return Some(if numeric_string && allows_type(schema, "number") {
+            Mismatch {
+                argument: found.argument.suggestion("Pass the number without quotes"),
+                ..found
+            }
+        } else {
+            found
+        });
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            return Some(wrong(describe(schema)));
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let number = number.as_f64()?;
+            let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+            let limits = [
+                ("at least", bound("minimum").filter(|&min| number < min)),
+                ("at most", bound("maximum").filter(|&max| number > max)),
+                (
+                    "greater than",
+                    bound("exclusiveMinimum").filter(|&min| number <= min),
+                ),
+                (
+                    "less than",
+                    bound("exclusiveMaximum").filter(|&max| number >= max),
+                ),
+            ];
+            limits
+                .into_iter()
+                .find_map(|(relation, limit)| Some(wrong(format!("{} {}", relation, limit?))))
+        }
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required = schema.get("required").and_then(Value::as_array);
+            let required: Vec<&str> = required
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            for &name in &required {
+                if fields.get(name).is_none() {
+                    let property = properties.and_then(|p| p.get(name)).unwrap_or(&Value::Null);
+                    let path = join_path(path, name);
+                    return Some(Mismatch {
+                        problem: format!("missing required argument '{}'", path),
+                        argument: InvalidArgument::new(describe(property))
+                            .at(path)
+                            .suggestion(format!("Add the required argument '{}'", name)),
+                    });
+                }
+            }
+            for (name, field) in fields {
+                let property = properties.and_then(|p| p.get(name));
+                let found = match (property, schema.get("additionalProperties")) {
+                    // An optional argument given as null is treated as omitted
+                    (Some(_), _) if field.is_null() && !required.contains(&name.as_str()) => None,
+                    (Some(property), _) => mismatch(field, property, &join_path(path, name)),
+                    (None, Some(Value::Bool(false))) => {
+                        let known: Vec<&str> = properties
+                            .into_iter()
+                            .flat_map(|p| p.keys())
+                            .map(String::as_str)
+                            .collect();
+                        Some(unknown_argument(
+                            &join_path(path, name),
+                            name,
+                            field,
+                            &known,
+                        ))
+                    }
+                    (None, Some(extra)) if extra.is_object() => {
+                        mismatch(field, extra, &join_path(path, name))
+                    }
+                    (None, _) => None,
+                };
+                if found.is_some() {
+                    return found;
+                }
+            }
+            None
+        }
+        Value::Array(items) => {
+            let count = |keyword: &str| schema.get(keyword).and_then(Value::as_u64);
+            if let Some(min) = count("minItems").filter(|&min| (items.len() as u64) < min) {
+                return Some(wrong(format!("an array of at least {} items", min)));
+            }
+            if let Some(max) = count("maxItems").filter(|&max| (items.len() as u64) > max) {
+                return Some(wrong(format!("an array of at most {} items", max)));
+            }
+            let item_schema = schema.get("items")?;
+            items.iter().enumerate().find_map(|(index, item)| {
+                mismatch(item, item_schema, &join_path(path, &format!("[{}]", index)))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// An argument the schema does not declare, with a hint if it looks like a
+/// misspelling of one it does
+fn unknown_argument(path: &str, name: &str, value: &Value, known: &[&str]) -> Mismatch {
+    let normalize = |name: &str| name.to_lowercase().replace(['_', '-'], "");
+    let similar = known.iter().find(|k| normalize(k) == normalize(name));
+    let expected = if known.is_empty() {
+        "no arguments".to_string()
+    } else {
+        format!("one of the arguments {}", known.join(", "))
+    };
+    let suggestion = match similar {
+        Some(similar) => format!("Did you mean '{}'?", similar),
+        None => format!("Remove '{}'", name),
+    };
+    Mismatch {
+        problem: format!("unknown argument '{}'", path),
+        argument: InvalidArgument::new(expected)
+            .at(path)
+            .received(value.clone())
+            .suggestion(suggestion),
+    }
+}
+
+/// The JSON types `schema` allows, if it restricts them
+fn schema_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(name) => Some(vec![name.as_str()]),
+        Value::Array(names) => Some(names.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn allows_type(schema: &Value, name: &str) -> bool {
+    schema_types(schema).is_some_and(|types| types.contains(&name))
+}
+
+fn matches_type(value: &Value, schema: &Value) -> bool {
+    let Some(types) = schema_types(schema) else {
+        return true;
+    };
+    types.iter().any(|name| match *name {
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    })
+}
+
+/// Describe what `schema` accepts, e.g. `a number` or `one of "a", "b"`
+fn describe(schema: &Value) -> String {
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        let options: Vec<String> = options.iter().map(Value::to_string).collect();
+        return format!("one of {}", options.join(", "));
+    }
+    let Some(types) = schema_types(schema) else {
+        return "a value".to_string();
+    };
+    let names: Vec<&str> = types
+        .iter()
+        .map(|name| match *name {
+            "number" => "a number",
+            "integer" => "an integer",
+            "string" => "a string",
+            "boolean" => "a boolean",
+            "array" => "an array",
+            "object" => "an object",
+            "null" => "null",
+            other => other,
+        })
+        .collect();
+    match (names.as_slice(), schema.pointer("/items/type")) {
+        (["an array"], Some(Value::String(item))) if item == "number" => {
+            "an array of numbers".to_string()
+        }
+        _ => names.join(" or "),
+    }
+}
 
 /// Validate array size against configuration limits.
 ///
@@ -13,7 +420,13 @@ use crate::error::{McpError, McpResult};
 /// `Ok(())` if size is within limits, error otherwise.
 pub fn validate_array_size(size: usize, config: &Config) -> McpResult<()> {
     if size > config.max_array_size {
-        return Err(McpError::resource_limit(format!(
+        return Err(InvalidArgument::new(format!(
+            "an array of at most {} items",
+            config.max_array_size
+        ))
+        .received(size)
+        .suggestion("Split the input into smaller arrays")
+        .resource_limit(format!(
             "Array size {} exceeds maximum allowed size of {}",
             size, config.max_array_size
         )));
@@ -29,26 +442,10 @@ pub fn validate_array_size(size: usize, config: &Config) -> McpResult<()> {
 /// * `name` - Name of the value for error messages
 pub fn validate_finite(value: f64, name: &str) -> McpResult<()> {
     if !value.is_finite() {
-        return Err(McpError::validation_error(format!(
-            "{} must be a finite number, got: {}",
-            name, value
-        )));
-    }
-    Ok(())
-}
-
-/// Validate decimal places for rounding operations
-pub fn validate_decimal_places(places: i32, config: &Config) -> McpResult<()> {
-    if places < 0 {
-        return Err(McpError::validation_error(
-            "Decimal places must be non-negative",
-        ));
-    }
-    if places > config.max_decimal_places {
-        return Err(McpError::validation_error(format!(
-            "Decimal places {} exceeds maximum of {}",
-            places, config.max_decimal_places
-        )));
+        return Err(InvalidArgument::new("a finite number")
+            .at(name)
+            .received(value.to_string())
+            .validation_error(format!("{} must be a finite number, got: {}", name, value)));
     }
     Ok(())
 }
@@ -56,10 +453,10 @@ pub fn validate_decimal_places(places: i32, config: &Config) -> McpResult<()> {
 /// Validate that a number is positive
 pub fn validate_positive(value: f64, name: &str) -> McpResult<()> {
     if value <= 0.0 {
-        return Err(McpError::validation_error(format!(
-            "{} must be positive, got: {}",
-            name, value
-        )));
+        return Err(InvalidArgument::new("a positive number")
+            .at(name)
+            .received(value)
+            .validation_error(format!("{} must be positive, got: {}", name, value)));
     }
     Ok(())
 }
@@ -67,10 +464,10 @@ pub fn validate_positive(value: f64, name: &str) -> McpResult<()> {
 /// Validate that a number is non-negative
 pub fn validate_non_negative(value: f64, name: &str) -> McpResult<()> {
     if value < 0.0 {
-        return Err(McpError::validation_error(format!(
-            "{} must be non-negative, got: {}",
-            name, value
-        )));
+        return Err(InvalidArgument::new("a non-negative number")
+            .at(name)
+            .received(value)
+            .validation_error(format!("{} must be non-negative, got: {}", name, value)));
     }
     Ok(())
 }
@@ -78,19 +475,116 @@ pub fn validate_non_negative(value: f64, name: &str) -> McpResult<()> {
 /// Validate integer value (within reasonable range)
 pub fn validate_integer(value: f64, name: &str) -> McpResult<i64> {
     if value.fract() != 0.0 {
-        return Err(McpError::validation_error(format!(
-            "{} must be an integer, got: {}",
-            name, value
-        )));
+        return Err(InvalidArgument::new("an integer")
+            .at(name)
+            .received(value)
+            .suggestion(format!("Round {} to {}", name, value.round()))
+            .validation_error(format!("{} must be an integer, got: {}", name, value)));
     }
 
     // Check if value is within i64 range
     if value < i64::MIN as f64 || value > i64::MAX as f64 {
-        return Err(McpError::validation_error(format!(
-            "{} is out of range for integer operations",
-            name
-        )));
+        return Err(InvalidArgument::new("an integer within the 64-bit range")
+            .at(name)
+            .received(value)
+            .validation_error(format!("{} is out of range for integer operations", name)));
     }
 
     Ok(value as i64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["numbers"],
+            "properties": {
+                "numbers": { "type": "array", "items": { "type": "number" } },
+                "method": { "type": "string", "enum": ["fast", "exact"] },
+                "decimals": { "type": ["integer", "null"] }
+            }
+        })
+    }
+
+    #[test]
+    fn test_find_mismatch_paths() {
+        let found = find_mismatch(&json!({ "numbers": [1, 2, "3"] }), &schema()).unwrap();
+        assert_eq!(found.argument.as_deref(), Some("numbers[2]"));
+        assert_eq!(found.expected, "a number");
+        assert_eq!(found.received, Some(json!("3")));
+        assert!(found.suggestion.is_some());
+
+        let found = find_mismatch(&json!({ "numbers": [], "method": "slow" }), &schema()).unwrap();
+        assert_eq!(found.argument.as_deref(), Some("method"));
+        assert_eq!(found.expected, r#"one of "fast", "exact""#);
+
+        let found = find_mismatch(&json!({ "decimals": 2 }), &schema()).unwrap();
+        assert_eq!(found.argument.as_deref(), Some("numbers"));
+        assert_eq!(found.expected, "an array of numbers");
+        assert_eq!(found.received, None);
+
+        assert!(find_mismatch(&json!({ "numbers": [1.5], "decimals": null }), &schema()).is_none());
+    }
+
+    #[test]
+    fn test_validate_arguments_bounds_and_unknown_keys() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "value": { "type": "number", "minimum": -1, "exclusiveMaximum": 1 },
+                "items": { "type": "array", "maxItems": 2 }
+            },
+            "additionalProperties": false
+        });
+        assert!(validate_arguments("t", &json!({ "value": -1, "items": [1, 2] }), &schema).is_ok());
+
+        let err = validate_arguments("t", &json!({ "value": 1 }), &schema).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid arguments for t: value must be less than 1, got 1"
+        );
+        assert_eq!(err.code, -32602);
+
+        let err = validate_arguments("t", &json!({ "items": [1, 2, 3] }), &schema).unwrap_err();
+        assert_eq!(err.data.unwrap()["expected"], "an array of at most 2 items");
+
+        let err = validate_arguments("t", &json!({ "Value": 0 }), &schema).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid arguments for t: unknown argument 'Value'"
+        );
+        assert_eq!(err.data.unwrap()["suggestion"], "Did you mean 'value'?");
+
+        let err = validate_arguments("t", &json!([1]), &schema).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Invalid arguments for t: arguments must be an object"
+        );
+    }
+
+    #[test]
+    fn test_within_nests_argument_paths() {
+        let error = InvalidArgument::new("a number")
+            .at("[2]")
+            .invalid_params("bad");
+        let error = within(within(error, "numbers"), "operations[0].arguments");
+        let data = error.data.unwrap();
+        assert_eq!(data["argument"], "operations[0].arguments.numbers[2]");
+
+        let error = within(McpError::validation_error("Division by zero"), "a");
+        assert!(error.data.is_none());
+    }
+
+    #[test]
+    fn test_validation_errors_carry_data() {
+        let data = validate_integer(2.5, "n").unwrap_err().data.unwrap();
+        assert_eq!(data["argument"], "n");
+        assert_eq!(data["expected"], "an integer");
+        assert_eq!(data["received"], 2.5);
+        assert_eq!(data["suggestion"], "Round n to 3");
+    }
+}
@@ -272,6 +272,9 @@ fn test_tools_call_quadratic_formula() {
     let roots = result_data["roots"].as_array().unwrap();
     assert_eq!(roots.len(), 2);
 
+    // The same result is available as typed JSON without re-parsing text
+    assert_eq!(response["result"]["structuredContent"], result_data);
+
     server.terminate();
 }
 
@@ -388,15 +391,13 @@ fn test_unknown_method() {
 
     let response = server.read_response();
 
-    // Should return error in result
+    // Should return a JSON-RPC error
     assert_eq!(response["jsonrpc"], "2.0");
     assert_eq!(response["id"], 6);
-    assert!(response["result"].is_object());
-    assert_eq!(response["result"]["isError"], true);
-
-    let content = &response["result"]["content"];
-    let text = content[0]["text"].as_str().unwrap();
-    assert!(text.contains("Method not found"));
+    assert!(response.get("result").is_none());
+    assert_eq!(response["error"]["code"], -32601);
+    let message = response["error"]["message"].as_str().unwrap();
+    assert!(message.contains("Method not found"));
 
     server.terminate();
 }
@@ -732,7 +733,7 @@ fn test_batch_operations_complex_workflow() {
                             "principal": 1000.0,
                             "rate": 5.0,
                             "time": 10.0,
-                            "n": 12.0
+                            "compounds_per_year": 12.0
                         }
                     }
                 ]
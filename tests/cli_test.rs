@@ -0,0 +1,172 @@
+// Tests for the command line mode: `call`, `list` and the REPL
+
+use rust_math_mcp::cli::{self, History, OutputFormat};
+use rust_math_mcp::config::Config;
+use serde_json::Value;
+use std::process::{Command, Output};
+use std::sync::Arc;
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust-math-mcp"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_call_prints_json_result() {
+    let output = run(&[
+        "call",
+        "mean",
+        "--args",
+        r#"{"numbers":[1,2,3]}"#,
+        "--format",
+        "json",
+    ]);
+    assert!(output.status.success());
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["result"], 2.0);
+}
+
+#[test]
+fn test_call_errors_match_the_server() {
+    let output = run(&["call", "divide", "--args", r#"{"a":1,"b":"0"}"#]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("Error: Invalid arguments for divide"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("argument: b"), "{}", stderr);
+
+    let output = run(&["call", "transmute", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["code"], -32000);
+    assert_eq!(error["error"]["message"], "Unknown tool: transmute");
+}
+
+#[test]
+fn test_call_honours_server_flags() {
+    let output = run(&[
+        "--disable-tool",
+        "sqrt",
+        "call",
+        "sqrt",
+        "--args",
+        r#"{"number":4}"#,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("disabled"));
+}
+
+#[test]
+fn test_call_rounds_like_the_server() {
+    let output = run(&[
+        "--decimal-places",
+        "2",
+        "call",
+        "mean",
+        "--args",
+        r#"{"numbers":[1,2,2]}"#,
+        "--format",
+        "json",
+    ]);
+    assert!(output.status.success());
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["result"], 1.67);
+}
+
+#[test]
+fn test_list_by_category() {
+    let output = run(&["list", "--category", "combinatorics"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "combinatorics");
+    assert!(lines[1].trim_start().starts_with("permutation "));
+    assert!(lines[2].trim_start().starts_with("combination "));
+    assert_eq!(lines.len(), 3);
+
+    let config = Config::new();
+    let tools = cli::list(&config, None).unwrap();
+    assert!(tools.iter().all(|tool| tool["category"].is_string()));
+    assert!(cli::list(&config, Some("alchemy")).is_err());
+}
+
+#[test]
+fn test_repl() {
+    let config = Arc::new(Config::new());
+    let input = "\
+add {\"numbers\": [2, 3]}\n\
+\n\
+!!\n\
+format json\n\
+factorial {\"n\": -1}\n\
+history\n\
+!7\n\
+quit\n\
+add {\"numbers\": [1]}\n";
+    let mut output = Vec::new();
+    let mut history = History::new();
+    cli::repl(
+        &config,
+        input.as_bytes(),
+        &mut output,
+        &mut history,
+        OutputFormat::Text,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.matches("result: 5.0").count(), 2);
+    assert!(output.contains("Printing results as json"));
+    assert!(output.contains(r#"{"error":{"code":"#));
+    assert!(output.contains("No history entry '!7'"));
+    // Input stops being read at `quit`
+    assert!(!output.contains("result: 1.0"));
+    assert_eq!(
+        history.entries(),
+        [
+            "add {\"numbers\": [2, 3]}",
+            "format json",
+            "factorial {\"n\": -1}",
+            "history",
+            "quit"
+        ]
+    );
+}
+
+#[test]
+fn test_repl_history_file() {
+    let path = std::env::temp_dir().join(format!("{}-repl-history", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let config = Arc::new(Config::new());
+
+    let mut history = History::load(&path).unwrap();
+    cli::repl(
+        &config,
+        "sqrt {\"number\": 9}\n".as_bytes(),
+        std::io::sink(),
+        &mut history,
+        OutputFormat::Text,
+    )
+    .unwrap();
+
+    // A later session can repeat the line
+    let mut history = History::load(&path).unwrap();
+    let mut output = Vec::new();
+    cli::repl(
+        &config,
+        "!1\n".as_bytes(),
+        &mut output,
+        &mut history,
+        OutputFormat::Text,
+    )
+    .unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert!(String::from_utf8(output).unwrap().contains("result: 3.0"));
+}
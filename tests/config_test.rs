@@ -0,0 +1,502 @@
+// Tests for layered configuration: TOML file, environment and command line
+
+use rust_math_mcp::audit::AuditArguments;
+use rust_math_mcp::config::{Config, TransportKind};
+use rust_math_mcp::tools::DefaultToolRegistry;
+use rust_math_mcp::MathService;
+use serde_json::json;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+const FULL_CONFIG: &str = r#"
+[server]
+name = "math-test"
+transport = "tcp"
+tcp_bind = "127.0.0.1:9999"
+
+[limits]
+max_array_size = 250
+tool_timeout_ms = 5000
+
+[limits.tool_timeouts_ms]
+batch_operations = 60000
+
+[rate_limit]
+enabled = true
+requests_per_second = 20
+
+[rate_limit.tools]
+factorial = 2
+
+[rate_limit.costs]
+factorial = 4
+
+[categories]
+finance = false
+
+[output]
+pretty = true
+decimal_places = 4
+"#;
+
+fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_toml_sections() {
+    let config = Config::from_toml(FULL_CONFIG).unwrap();
+
+    assert_eq!(config.server_name, "math-test");
+    assert_eq!(config.transport, TransportKind::Tcp);
+    assert_eq!(config.tcp_bind.port(), 9999);
+    assert_eq!(config.max_array_size, 250);
+    assert_eq!(config.tool_timeout("add"), Duration::from_secs(5));
+    assert_eq!(
+        config.tool_timeout("batch_operations"),
+        Duration::from_secs(60)
+    );
+    assert_eq!(config.max_requests_per_second, 20);
+    assert_eq!(config.tool_rate_limits["factorial"], 2);
+    assert_eq!(config.tool_cost("factorial"), 4.0);
+    assert!(!config.category_enabled("finance"));
+    assert!(!config.tool_enabled("compound_interest"));
+    assert!(config.tool_enabled("add"));
+    assert!(config.pretty_output);
+    assert_eq!(config.output_decimal_places, Some(4));
+}
+
+#[test]
+fn test_invalid_toml_is_rejected() {
+    let cases = [
+        ("[limits]\nmax_array_sise = 5", "max_array_sise"),
+        (
+            "[server]\ntransport = \"carrier-pigeon\"",
+            "server.transport",
+        ),
+        ("[server]\nhttp_bind = \"localhost\"", "http_bind"),
+        ("[limits]\nmax_array_size = 0", "max_array_size"),
+        (
+            "[rate_limit]\nrequests_per_second = 0",
+            "requests_per_second",
+        ),
+        ("[rate_limit.tools]\nfactorail = 3", "factorail"),
+        ("[rate_limit.costs]\nadd = -1.0", "rate_limit.costs.add"),
+        ("[categories]\nalchemy = false", "alchemy"),
+        ("[output]\ndecimal_places = 40", "output.decimal_places"),
+        ("[audit]\narguments = \"everything\"", "audit.arguments"),
+        (
+            "[audit]\npath = \"audit.jsonl\"\nmax_bytes = 0",
+            "audit.max_bytes",
+        ),
+    ];
+
+    for (toml, expected) in cases {
+        let err = Config::from_toml(toml).unwrap_err();
+        assert!(err.to_string().contains(expected), "{}: {}", toml, err);
+    }
+}
+
+#[test]
+fn test_load_layers_environment_over_file() {
+    let path = temp_file(
+        "layers.toml",
+        "[limits]\nmax_array_size = 250\nmax_decimal_places = 6\n",
+    );
+
+    // The only test in this file that touches the environment
+    std::env::set_var("MCP_MAX_ARRAY_SIZE", "300");
+    let config = Config::load(Some(&path));
+    std::env::set_var("MCP_MAX_ARRAY_SIZE", "lots");
+    let invalid = Config::load(Some(&path));
+    std::env::remove_var("MCP_MAX_ARRAY_SIZE");
+
+    let config = config.unwrap();
+    assert_eq!(config.max_array_size, 300);
+    assert_eq!(config.max_decimal_places, 6);
+    assert!(invalid
+        .unwrap_err()
+        .to_string()
+        .contains("MCP_MAX_ARRAY_SIZE"));
+
+    let missing = Config::load(Some(&path.with_extension("missing"))).unwrap_err();
+    assert!(missing.to_string().contains("cannot read"));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_disabled_category_hidden_and_refused() {
+    let config = Config::from_toml("[categories]\nfinance = false").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+
+    let tools = service._list_tools();
+    assert!(tools.iter().all(|tool| tool.name != "compound_interest"));
+    assert!(tools.iter().any(|tool| tool.name == "add"));
+
+    let args = json!({ "principal": 1000, "rate": 0.05, "time": 2 });
+    let result = service
+        ._call_tool("compound_interest", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+}
+
+#[tokio::test]
+async fn test_output_formatting() {
+    let config = Config::from_toml("[output]\npretty = true\ndecimal_places = 3").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+
+    let result = service
+        ._call_tool("divide", json!({ "a": 2, "b": 3 }).as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content, Some(json!({ "result": 0.667 })));
+    let text = result.content[0].as_text().unwrap();
+    assert_eq!(text.text, "{\n  \"result\": 0.667\n}");
+}
+
+#[tokio::test]
+async fn test_lenient_numbers() {
+    let strict = MathService::new();
+    let args = json!({ "numbers": ["1/4", "50%", 1] });
+    let result = strict
+        ._call_tool("add", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let config = Config::from_toml("[input]\nlenient_numbers = true").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+    let result = service
+        ._call_tool("add", args.as_object().cloned())
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["result"], 1.75);
+    assert_eq!(
+        structured["interpretations"],
+        json!([
+            { "argument": "numbers[0]", "input": "1/4", "value": 0.25, "format": "fraction" },
+            { "argument": "numbers[1]", "input": "50%", "value": 0.5, "format": "percentage" }
+        ])
+    );
+
+    // An argument already in percent keeps the number before the sign
+    let args = json!({ "whole": 200, "percent": "5%" });
+    let result = service
+        ._call_tool("percentage", args.as_object().cloned())
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["part"], 10.0);
+    assert_eq!(structured["interpretations"][0]["value"], 5.0);
+
+    // Rates as fractions still read "5%" as 0.05
+    let args = json!({ "principal": 100, "rate": "5%", "time": 1 });
+    let result = service
+        ._call_tool("simple_interest", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["result"], 5.0);
+
+    // Operations inside a batch are read the same way
+    let args = json!({ "operations": [
+        { "id": "s", "tool": "sin", "arguments": { "angle": "pi/2" } }
+    ] });
+    let result = service
+        ._call_tool("batch_operations", args.as_object().cloned())
+        .await
+        .unwrap();
+    let operation = &result.structured_content.unwrap()["results"][0];
+    assert_eq!(operation["result"]["result"], 1.0);
+    assert_eq!(
+        operation["result"]["interpretations"][0]["format"],
+        "constant"
+    );
+}
+
+#[tokio::test]
+async fn test_result_cache() {
+    let registry = DefaultToolRegistry;
+    let config = Config::from_toml("[cache]\nenabled = true\nexclude = [\"median\"]").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+    // Arguments no other test uses, written two ways
+    let args = json!({ "numbers": [101, 202, 303.5] });
+    let same_args = json!({ "numbers": [101.0, 202.0, 303.5] });
+
+    let before = registry.cache_stats();
+    let first = service
+        ._call_tool("mean", args.as_object().cloned())
+        .await
+        .unwrap();
+    let second = service
+        ._call_tool("mean", same_args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(first.structured_content, second.structured_content);
+    let after = registry.cache_stats();
+    assert_eq!(after.misses - before.misses, 1);
+    assert_eq!(after.hits - before.hits, 1);
+
+    // Excluded tools always run
+    for _ in 0..2 {
+        service
+            ._call_tool("median", args.as_object().cloned())
+            .await
+            .unwrap();
+    }
+    // Not cached without the setting
+    MathService::new()
+        ._call_tool("mean", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(registry.cache_stats().hits, after.hits);
+
+    assert!(Config::from_toml("[cache]\nexclude = [\"meen\"]").is_err());
+}
+
+#[tokio::test]
+async fn test_audit_log() {
+    let path = temp_file("audit.jsonl", "");
+    let config = Config::from_toml(&format!(
+        "[audit]\npath = {:?}\nmax_array_len = 3\n",
+        path.display().to_string()
+    ))
+    .unwrap();
+    assert_eq!(config.audit_arguments, AuditArguments::Redacted);
+    let service = MathService::with_config(Arc::new(config));
+
+    let args = json!({ "numbers": [1, 2, 3, 4, 5] });
+    service
+        ._call_tool("mean", args.as_object().cloned())
+        .await
+        .unwrap();
+    let args = json!({ "a": 1, "b": 0 });
+    service
+        ._call_tool("divide", args.as_object().cloned())
+        .await
+        .unwrap();
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let entries: Vec<serde_json::Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 2);
+
+    let mean = &entries[0];
+    assert_eq!(mean["session"], service.session().id());
+    assert_eq!(mean["tool"], "mean");
+    assert_eq!(mean["success"], true);
+    assert_eq!(mean["result"], 3.0);
+    assert_eq!(mean["arguments"]["numbers"]["length"], 5);
+    assert!(mean["arguments"]["numbers"].get("hash").is_some());
+    assert_eq!(mean["arguments_hash"].as_str().unwrap().len(), 16);
+    assert!(mean["timestamp"].as_str().unwrap().ends_with('Z'));
+
+    let divide = &entries[1];
+    assert_eq!(divide["success"], false);
+    assert_eq!(divide["error"]["code"], -32001);
+    assert_eq!(divide["arguments"], json!({ "a": 1, "b": 0 }));
+    assert!(divide["duration_ms"].as_f64().unwrap() >= 0.0);
+}
+
+#[test]
+fn test_binary_rejects_invalid_config_at_startup() {
+    let path = temp_file("invalid.toml", "[rate_limit.tools]\nfactorail = 3\n");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-math-mcp"))
+        .arg("--config")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown tool 'factorail'"), "{}", stderr);
+}
+
+fn listed_tools(service: &MathService) -> Vec<String> {
+    service
+        ._list_tools()
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect()
+}
+
+#[test]
+fn test_only_selected_categories_and_tools() {
+    let config =
+        Config::from_toml("[tools]\ncategories = [\"finance\"]\nallow = [\"add\"]").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+
+    let mut tools = listed_tools(&service);
+    tools.sort();
+    assert_eq!(
+        tools,
+        ["add", "compound_interest", "percentage", "simple_interest"]
+    );
+}
+
+#[tokio::test]
+async fn test_allowlist_applies_to_composite_tools() {
+    let composite = r#"
+[tools.composite.double]
+description = "Twice a number"
+expression = "2 * x"
+parameters = { x = "The number" }
+"#;
+    let config = Config::from_toml(&format!("{}\n[tools]\nallow = [\"mean\"]", composite)).unwrap();
+    assert!(!config.tool_enabled("double"));
+    let service = MathService::with_config(Arc::new(config));
+    assert_eq!(listed_tools(&service), ["mean"]);
+    let result = service
+        ._call_tool("double", json!({ "x": 2 }).as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let config = Config::from_toml(&format!(
+        "{}\n[tools]\nallow = [\"mean\", \"double\"]",
+        composite
+    ))
+    .unwrap();
+    let service = MathService::with_config(Arc::new(config));
+    assert_eq!(listed_tools(&service), ["mean", "double"]);
+}
+
+#[tokio::test]
+async fn test_denied_tool_refused_inside_batch() {
+    let config = Config::from_toml("[tools]\ndeny = [\"factorial\"]").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+    assert!(!listed_tools(&service).contains(&"factorial".to_string()));
+    assert!(listed_tools(&service).contains(&"batch_operations".to_string()));
+
+    let batch = json!({ "operations": [
+        { "id": "f", "tool": "factorial", "arguments": { "n": 5 } },
+        { "id": "a", "tool": "add", "arguments": { "numbers": [1, 2] } }
+    ] });
+    let result = service
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap();
+    let results = &result.structured_content.unwrap()["results"];
+    assert_eq!(results[0]["success"], false);
+    assert!(results[0]["error"].as_str().unwrap().contains("disabled"));
+    assert_eq!(results[1]["result"]["result"], 3.0);
+
+    let direct = service
+        ._call_tool("factorial", json!({ "n": 5 }).as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(direct.is_error, Some(true));
+}
+
+#[test]
+fn test_everything_but_batch() {
+    let config = Config::from_toml("[tools]\ndeny = [\"batch_operations\"]").unwrap();
+    let service = MathService::with_config(Arc::new(config));
+
+    let tools = listed_tools(&service);
+    assert!(!tools.contains(&"batch_operations".to_string()));
+    assert_eq!(
+        tools.len() + 1,
+        MathService::with_config(Arc::new(Config::default()))
+            ._list_tools()
+            .len()
+    );
+}
+
+#[test]
+fn test_invalid_tool_selection_is_rejected() {
+    let cases = [
+        (
+            "[tools]\ndeny = [\"teleport\"]",
+            "tools.deny: unknown tool 'teleport'",
+        ),
+        (
+            "[tools]\ncategories = [\"magic\"]",
+            "unknown category 'magic'",
+        ),
+        (
+            "[tools]\ncategories = [\"finance\"]\n[categories]\nfinance = false",
+            "no tools enabled",
+        ),
+    ];
+
+    for (toml, expected) in cases {
+        let err = Config::from_toml(toml).unwrap_err();
+        assert!(err.to_string().contains(expected), "{}: {}", toml, err);
+    }
+}
+
+#[tokio::test]
+async fn test_composite_tools_listed_and_called() {
+    let config = Config::from_toml(
+        r#"
+[tools.composite.weighted_grade]
+description = "Course grade from exam and homework averages"
+expression = "0.6 * exam + 0.4 * homework"
+parameters = { exam = "Exam average", homework = "Homework average" }
+
+[tools.composite.bmi]
+description = "Body-mass index"
+expression = "weight / height ^ 2"
+parameters = { weight = "Weight in kg", height = "Height in m" }
+
+[tools]
+deny = ["bmi"]
+"#,
+    )
+    .unwrap();
+    let service = MathService::with_config(Arc::new(config));
+
+    let tools = service._list_tools();
+    let grade = tools
+        .iter()
+        .find(|tool| tool.name == "weighted_grade")
+        .unwrap();
+    assert_eq!(grade.input_schema["required"], json!(["exam", "homework"]));
+    assert!(tools.iter().all(|tool| tool.name != "bmi"));
+
+    let args = json!({ "exam": 80, "homework": 95 });
+    let result = service
+        ._call_tool("weighted_grade", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["result"], 86.0);
+
+    let args = json!({ "weight": 81, "height": 1.8 });
+    let result = service
+        ._call_tool("bmi", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+}
+
+#[test]
+fn test_invalid_composite_tools_are_rejected() {
+    let cases = [
+        (
+            "[tools.composite.add]\ndescription = 'x'\nexpression = '1'",
+            "tools.composite.add: conflicts",
+        ),
+        (
+            "[tools.composite.f]\ndescription = 'x'\nexpression = '2 *'",
+            "tools.composite.f: expression",
+        ),
+        (
+            "[tools.composite.f]\ndescription = 'x'\nexpression = '1'\nshape = 'round'",
+            "shape",
+        ),
+    ];
+
+    for (toml, expected) in cases {
+        let err = Config::from_toml(toml).unwrap_err();
+        assert!(err.to_string().contains(expected), "{}: {}", toml, err);
+    }
+}
@@ -0,0 +1,179 @@
+// Integration tests for the streamable HTTP transport
+// Starts the server on an ephemeral localhost port and speaks plain HTTP/1.1 to it
+
+use rust_math_mcp::config::Config;
+use rust_math_mcp::transport::http;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+async fn start_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(http::serve(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+    addr
+}
+
+/// POST a JSON-RPC message and return (session id header, JSON messages from the body)
+async fn post(
+    addr: SocketAddr,
+    session: Option<&str>,
+    body: &Value,
+) -> (Option<String>, Vec<Value>) {
+    let body = body.to_string();
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+         Accept: application/json, text/event-stream\r\nConnection: close\r\n\
+         Content-Length: {}\r\n",
+        http::MCP_PATH,
+        addr,
+        body.len()
+    );
+    if let Some(session) = session {
+        request.push_str(&format!("Mcp-Session-Id: {}\r\n", session));
+    }
+    request.push_str("\r\n");
+    request.push_str(&body);
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(
+        head.starts_with("HTTP/1.1 2"),
+        "unexpected response: {}",
+        head
+    );
+    let session = head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("mcp-session-id")
+            .then(|| value.trim().to_string())
+    });
+    // Responses arrive as SSE events; pull the JSON out of each `data:` line
+    let messages = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str(data.trim()).ok())
+        .collect();
+    (session, messages)
+}
+
+async fn initialize(addr: SocketAddr) -> String {
+    let (session, messages) = post(
+        addr,
+        None,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": { "name": "http-test", "version": "1.0" }
+            }
+        }),
+    )
+    .await;
+    assert_eq!(messages[0]["result"]["serverInfo"]["name"], "rust-math-mcp");
+    let session = session.expect("server should assign a session id");
+
+    post(
+        addr,
+        Some(&session),
+        &json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    )
+    .await;
+    session
+}
+
+#[tokio::test]
+async fn test_http_call_tool() {
+    let addr = start_server().await;
+    let session = initialize(addr).await;
+
+    let (_, messages) = post(
+        addr,
+        Some(&session),
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "multiply", "arguments": { "numbers": [6, 7] } }
+        }),
+    )
+    .await;
+
+    let result = &messages[0]["result"];
+    assert_eq!(result["isError"], false);
+    let text: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(text["result"], 42.0);
+}
+
+#[tokio::test]
+async fn test_http_sessions_are_independent() {
+    let addr = start_server().await;
+    let first = initialize(addr).await;
+    let second = initialize(addr).await;
+    assert_ne!(first, second);
+
+    for session in [&first, &second] {
+        let (_, messages) = post(
+            addr,
+            Some(session),
+            &json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        )
+        .await;
+        assert!(!messages[0]["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_http_metrics_page() {
+    let addr = start_server().await;
+    let session = initialize(addr).await;
+    post(
+        addr,
+        Some(&session),
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": { "name": "divide", "arguments": { "a": 1, "b": 0 } }
+        }),
+    )
+    .await;
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        http::METRICS_PATH,
+        addr
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(
+        head.starts_with("HTTP/1.1 200"),
+        "unexpected response: {}",
+        head
+    );
+    assert!(head
+        .to_ascii_lowercase()
+        .contains("content-type: text/plain; version=0.0.4"));
+    assert!(body.contains("# TYPE mcp_tool_duration_seconds histogram"));
+    assert!(body.contains("mcp_tool_errors_total{tool=\"divide\",code=\"-32001\"}"));
+    assert!(body.contains("mcp_tool_duration_seconds_bucket{tool=\"divide\",le=\"+Inf\"}"));
+}
@@ -1,5 +1,20 @@
-use rust_math_mcp::tools::{DefaultToolRegistry, ToolRegistry};
+use rmcp::model::RawContent;
+use rust_math_mcp::config::Config;
+use rust_math_mcp::session::Session;
+use rust_math_mcp::tools::{DefaultToolRegistry, ToolContext, ToolRegistry};
+use rust_math_mcp::MathService;
 use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+fn error_text(result: &rmcp::model::CallToolResult) -> String {
+    assert_eq!(result.is_error, Some(true));
+    match &result.content[0].raw {
+        RawContent::Text(text) => text.text.clone(),
+        other => panic!("expected text content, got {:?}", other),
+    }
+}
 
 #[test]
 fn test_tool_registry_list_tools() {
@@ -47,3 +62,551 @@ fn test_tool_execution_divide_by_zero() {
     let result = registry.execute_tool("divide", &args);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_tool_arguments_validated_against_schema() {
+    let registry = DefaultToolRegistry;
+
+    let err = registry
+        .execute_tool("area_circle", &json!({ "radius": 2.0, "radus": 3.0 }))
+        .unwrap_err();
+    assert_eq!(err.code, -32602);
+    assert_eq!(
+        err.message,
+        "Invalid arguments for area_circle: unknown argument 'radus'"
+    );
+
+    let err = registry
+        .execute_tool("subtract", &json!({ "a": 1.0 }))
+        .unwrap_err();
+    assert_eq!(
+        err.message,
+        "Invalid arguments for subtract: missing required argument 'b'"
+    );
+    assert_eq!(err.data.unwrap()["argument"], "b");
+
+    let err = registry
+        .execute_tool("std_dev", &json!({ "numbers": [1.0], "sample": "yes" }))
+        .unwrap_err();
+    assert_eq!(
+        err.message,
+        "Invalid arguments for std_dev: sample must be a boolean, got \"yes\""
+    );
+
+    // An optional argument may be null
+    let result = registry
+        .execute_tool("round", &json!({ "number": 1.26, "decimals": null }))
+        .unwrap();
+    assert_eq!(result["result"], 1.0);
+}
+
+#[test]
+fn test_batch_operation_arguments_validated_against_schema() {
+    let registry = DefaultToolRegistry;
+    let args = json!({ "operations": [
+        { "id": "a", "tool": "add", "arguments": { "numbers": [1.0], "extra": 1 } }
+    ] });
+    let result = registry.execute_tool("batch_operations", &args).unwrap();
+    let failed = &result["results"][0];
+    assert_eq!(
+        failed["error"],
+        "Invalid arguments for add: unknown argument 'extra'"
+    );
+    assert_eq!(
+        failed["error_data"]["argument"],
+        "operations[0].arguments.extra"
+    );
+
+    let args = json!({ "mode": "eventually", "operations": [] });
+    let err = registry
+        .execute_tool("batch_operations", &args)
+        .unwrap_err();
+    assert_eq!(err.data.unwrap()["argument"], "mode");
+}
+
+#[test]
+fn test_service_lists_registry_tools() {
+    let registry = DefaultToolRegistry;
+    let service = MathService::new();
+    let tools = service._list_tools();
+
+    assert_eq!(
+        tools.len(),
+        registry.get_all_tools().as_array().unwrap().len()
+    );
+    let add = tools.iter().find(|t| t.name == "add").unwrap();
+    assert_eq!(add.input_schema["required"], json!(["numbers"]));
+}
+
+#[tokio::test]
+async fn test_service_call_tool() {
+    let service = MathService::new();
+    let args = json!({ "numbers": [1.0, 2.0, 3.0] });
+
+    let result = service
+        ._call_tool("add", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let text = result.content[0].as_text().unwrap().text.clone();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["result"], 6.0);
+    assert_eq!(result.structured_content, Some(value));
+}
+
+#[tokio::test]
+async fn test_service_call_tool_error_is_result() {
+    let service = MathService::new();
+    let args = json!({ "a": 10.0, "b": 0.0 });
+
+    let result = service
+        ._call_tool("divide", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let text = &result.content[0].as_text().unwrap().text;
+    assert!(text.contains("Division by zero"));
+}
+
+#[tokio::test]
+async fn test_service_error_result_carries_argument_data() {
+    let service = MathService::new();
+    let args = json!({ "numbers": [1.0, "2", 3.0] });
+
+    let result = service
+        ._call_tool("mean", args.as_object().cloned())
+        .await
+        .unwrap();
+    assert!(error_text(&result).starts_with("Error: Invalid arguments for mean"));
+    let error = &result.structured_content.unwrap()["error"];
+    assert_eq!(error["code"], -32602);
+    assert_eq!(error["data"]["argument"], "numbers[1]");
+    assert_eq!(error["data"]["expected"], "a number");
+    assert_eq!(error["data"]["received"], "2");
+    assert_eq!(
+        error["data"]["suggestion"],
+        "Pass the number without quotes"
+    );
+}
+
+#[tokio::test]
+async fn test_service_batch_error_data_has_operation_paths() {
+    let service = MathService::new();
+    let args = json!({ "operations": [
+        { "id": "ok", "tool": "add", "arguments": { "numbers": [1.0] } },
+        { "id": "bad", "tool": "factorial", "arguments": { "n": 2.5 } }
+    ] });
+
+    let result = service
+        ._call_tool("batch_operations", args.as_object().cloned())
+        .await
+        .unwrap();
+    let failed = &result.structured_content.unwrap()["results"][1];
+    assert_eq!(
+        failed["error_data"]["argument"],
+        "operations[1].arguments.n"
+    );
+    assert_eq!(failed["error_data"]["expected"], "an integer");
+
+    let args = json!({ "mode": "all_or_nothing", "operations": [
+        { "id": "sum", "tool": "add", "arguments": { "numbers": [1.0, true] } }
+    ] });
+    let result = service
+        ._call_tool("batch_operations", args.as_object().cloned())
+        .await
+        .unwrap();
+    let error = &result.structured_content.unwrap()["error"];
+    assert_eq!(error["data"]["operation"], "sum");
+    assert_eq!(
+        error["data"]["argument"],
+        "operations[0].arguments.numbers[1]"
+    );
+}
+
+#[test]
+fn test_all_tools_have_object_input_schema() {
+    let registry = DefaultToolRegistry;
+    let tools = registry.get_all_tools();
+
+    for tool in tools.as_array().unwrap() {
+        let schema = &tool["inputSchema"];
+        assert_eq!(schema["type"], "object", "tool {}", tool["name"]);
+        assert!(schema["properties"].is_object(), "tool {}", tool["name"]);
+    }
+}
+
+#[tokio::test]
+async fn test_service_sessions_are_independent() {
+    let first = MathService::new();
+    let second = MathService::new();
+    assert_ne!(first.session().id(), second.session().id());
+
+    first._call_tool("add", None).await.unwrap();
+    let shared = first.clone();
+    shared
+        ._call_tool(
+            "add",
+            Some(json!({ "numbers": [1] }).as_object().unwrap().clone()),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(first.session().tool_calls(), 2);
+    assert_eq!(second.session().tool_calls(), 0);
+}
+
+#[test]
+fn test_all_tools_have_object_output_schema() {
+    let service = MathService::new();
+
+    for tool in service._list_tools() {
+        let schema = tool.output_schema.expect("every tool has an outputSchema");
+        assert_eq!(schema["type"], "object", "tool {}", tool.name);
+    }
+}
+
+#[tokio::test]
+async fn test_service_structured_content_matches_output_schema() {
+    let service = MathService::new();
+    let args = json!({ "a": 1.0, "b": -3.0, "c": 2.0 });
+
+    let result = service
+        ._call_tool("quadratic_formula", args.as_object().cloned())
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["roots"], json!([2.0, 1.0]));
+    assert_eq!(structured["type"], "distinct");
+
+    let tools = service._list_tools();
+    let tool = tools
+        .iter()
+        .find(|t| t.name == "quadratic_formula")
+        .unwrap();
+    let schema = tool.output_schema.as_ref().unwrap();
+    for key in structured.as_object().unwrap().keys() {
+        assert!(
+            schema["properties"].get(key).is_some(),
+            "{} not in schema",
+            key
+        );
+    }
+}
+
+#[test]
+fn test_protocol_tools_call_structured_content() {
+    let registry = DefaultToolRegistry;
+    let response = rust_math_mcp::protocol::handle_method(
+        "tools/call",
+        Some(json!({ "name": "midpoint", "arguments": { "x1": 0, "y1": 0, "x2": 4, "y2": 6 } })),
+        Some(json!(1)),
+        &registry,
+    )
+    .unwrap();
+
+    let result = response.result.unwrap();
+    assert_eq!(result["structuredContent"], json!({ "x": 2.0, "y": 3.0 }));
+    let text: serde_json::Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(text, result["structuredContent"]);
+}
+
+#[test]
+fn test_protocol_unknown_method_is_json_rpc_error() {
+    let response = rust_math_mcp::protocol::handle_method(
+        "unknown/method",
+        Some(json!({})),
+        Some(json!(6)),
+        &DefaultToolRegistry,
+    )
+    .unwrap();
+
+    assert_eq!(response.id, Some(json!(6)));
+    assert!(response.result.is_none());
+    let error = response.error.unwrap();
+    assert_eq!(error.code, -32601);
+    assert_eq!(error.message, "Method not found: unknown/method");
+}
+
+#[tokio::test]
+async fn test_service_cancelled_call_is_error_result() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let result = MathService::new()
+        ._call_tool_with_context(
+            "add",
+            json!({ "numbers": [1, 2] }).as_object().cloned(),
+            ToolContext::new().with_cancellation(token),
+        )
+        .await
+        .unwrap();
+    assert!(error_text(&result).contains("cancelled"));
+}
+
+#[tokio::test]
+async fn test_service_per_tool_timeout() {
+    let mut config = Config::default();
+    config
+        .tool_timeouts
+        .insert("factorial".to_string(), Duration::from_nanos(1));
+    let service = MathService::with_config(Arc::new(config));
+
+    let result = service
+        ._call_tool("factorial", json!({ "n": 10 }).as_object().cloned())
+        .await
+        .unwrap();
+    assert!(error_text(&result).contains("timeout"));
+
+    // Other tools keep the default budget
+    let result = service
+        ._call_tool("add", json!({ "numbers": [1, 2] }).as_object().cloned())
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+}
+
+#[test]
+fn test_batch_stops_when_cancelled() {
+    let ctx = ToolContext::new();
+    ctx.cancel();
+
+    let err = DefaultToolRegistry
+        .execute_tool_with_context(
+            "batch_operations",
+            &json!({ "operations": [{ "id": "a", "tool": "add", "arguments": { "numbers": [1] } }] }),
+            &ctx,
+        )
+        .unwrap_err();
+    assert_eq!(err.code, -32002);
+}
+
+#[tokio::test]
+async fn test_large_array_statistics_report_progress() {
+    let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let ctx = ToolContext::new().with_progress({
+        let updates = updates.clone();
+        move |progress| updates.lock().unwrap().push(progress)
+    });
+    let numbers: Vec<f64> = (0..2500).map(f64::from).collect();
+
+    let result = MathService::new()
+        ._call_tool_with_context(
+            "variance",
+            json!({ "numbers": numbers }).as_object().cloned(),
+            ctx,
+        )
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+
+    // Two passes (mean, then squared deviations) of three chunks each
+    let updates = updates.lock().unwrap();
+    let steps: Vec<f64> = updates.iter().map(|p| p.progress).collect();
+    assert_eq!(steps, vec![1000.0, 2000.0, 2500.0, 3500.0, 4500.0, 5000.0]);
+    assert!(updates.iter().all(|p| p.total == Some(5000.0)));
+}
+
+fn numbers_args(numbers: &[f64]) -> Option<serde_json::Map<String, serde_json::Value>> {
+    json!({ "numbers": numbers }).as_object().cloned()
+}
+
+#[tokio::test]
+async fn test_service_rate_limit_charges_batch_per_operation() {
+    let config = Config {
+        enable_rate_limit: true,
+        max_requests_per_second: 5,
+        ..Config::default()
+    };
+    let service = MathService::with_config(Arc::new(config));
+    let batch = json!({ "operations": [
+        { "id": "a", "tool": "add", "arguments": { "numbers": [1] } },
+        { "id": "b", "tool": "add", "arguments": { "numbers": [2] } },
+        { "id": "c", "tool": "add", "arguments": { "numbers": [3] } },
+        { "id": "d", "tool": "add", "arguments": { "numbers": [4] } }
+    ] });
+
+    service
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap();
+    service
+        ._call_tool("add", numbers_args(&[1.0]))
+        .await
+        .unwrap();
+
+    let err = service
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code.0, -32002);
+    assert!(err.message.contains("this session"));
+    let retry_after = err.data.unwrap()["retry_after_ms"].as_u64().unwrap();
+    assert!(retry_after > 0 && retry_after <= 1000, "{}", retry_after);
+    assert_eq!(service.session().tool_calls(), 2);
+
+    // Other sessions have their own bucket
+    let other = MathService::with_config(Arc::new(Config {
+        max_requests_per_second: 5,
+        ..Config::default()
+    }));
+    other
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_service_per_tool_rate_limit() {
+    let mut config = Config {
+        enable_rate_limit: true,
+        ..Config::default()
+    };
+    config.tool_rate_limits.insert("factorial".to_string(), 1);
+    let service = MathService::with_config(Arc::new(config));
+    let args = json!({ "n": 5 });
+
+    service
+        ._call_tool("factorial", args.as_object().cloned())
+        .await
+        .unwrap();
+    let err = service
+        ._call_tool("factorial", args.as_object().cloned())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code.0, -32002);
+    assert!(err.message.contains("tool 'factorial'"));
+    assert!(err.data.unwrap()["retry_after_ms"].is_u64());
+
+    // Other tools only count against the session bucket
+    let result = service
+        ._call_tool("add", numbers_args(&[1.0]))
+        .await
+        .unwrap();
+    assert_ne!(result.is_error, Some(true));
+}
+
+#[tokio::test]
+async fn test_service_per_tool_rate_limit_applies_inside_batches() {
+    let mut config = Config {
+        enable_rate_limit: true,
+        ..Config::default()
+    };
+    config.tool_rate_limits.insert("factorial".to_string(), 1);
+    let service = MathService::with_config(Arc::new(config));
+    let batch = json!({ "operations": [
+        { "id": "a", "tool": "factorial", "arguments": { "n": 5 } },
+        { "id": "b", "tool": "factorial", "arguments": { "n": 6 } }
+    ] });
+
+    let result = service
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap();
+    // The operations run in parallel, so either may be the one refused
+    let result = result.structured_content.unwrap();
+    assert_eq!(result["summary"]["successful"], 1);
+    let refused = result["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|operation| operation["success"] == false)
+        .unwrap();
+    assert!(refused["error"]
+        .as_str()
+        .unwrap()
+        .contains("tool 'factorial'"));
+}
+
+#[test]
+fn test_session_rate_limit_takes_both_buckets_or_neither() {
+    let mut config = Config {
+        enable_rate_limit: true,
+        max_requests_per_second: 1,
+        ..Config::default()
+    };
+    config.tool_rate_limits.insert("factorial".to_string(), 1);
+    let session = Session::new(&config);
+
+    session.check_rate_limit("add", 1.0).unwrap();
+    let err = session.check_rate_limit("factorial", 1.0).unwrap_err();
+    assert!(err.message.contains("this session"));
+
+    // The refused call left the tool's token in place
+    session.check_tool_rate_limit("factorial", 1.0).unwrap();
+    assert!(session.check_tool_rate_limit("factorial", 1.0).is_err());
+}
+
+#[tokio::test]
+async fn test_service_rate_limit_disabled() {
+    let service = MathService::with_config(Arc::new(Config {
+        enable_rate_limit: false,
+        max_requests_per_second: 1,
+        ..Config::default()
+    }));
+
+    for _ in 0..3 {
+        service
+            ._call_tool("add", numbers_args(&[1.0]))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_service_enforces_configured_limits() {
+    let service = MathService::with_config(Arc::new(Config {
+        max_array_size: 3,
+        max_decimal_places: 4,
+        ..Config::default()
+    }));
+
+    let result = service
+        ._call_tool("mean", numbers_args(&[1.0, 2.0, 3.0, 4.0]))
+        .await
+        .unwrap();
+    assert!(error_text(&result).contains("maximum allowed size of 3"));
+
+    // Operations inside a batch run under the same limits
+    let batch = json!({ "operations": [
+        { "id": "ok", "tool": "sum", "arguments": { "numbers": [1, 2, 3] } },
+        { "id": "big", "tool": "sum", "arguments": { "numbers": [1, 2, 3, 4] } }
+    ] });
+    let result = service
+        ._call_tool("batch_operations", batch.as_object().cloned())
+        .await
+        .unwrap();
+    let summary = &result.structured_content.unwrap()["summary"];
+    assert_eq!(summary["successful"], 1);
+    assert_eq!(summary["failed"], 1);
+
+    let result = service
+        ._call_tool(
+            "round",
+            json!({ "number": 1.5, "decimals": 9 }).as_object().cloned(),
+        )
+        .await
+        .unwrap();
+    assert!(error_text(&result).contains("exceeds maximum of 4"));
+}
+
+#[test]
+fn test_context_limits_come_from_config() {
+    let config = Arc::new(Config {
+        max_array_size: 2,
+        ..Config::default()
+    });
+    let ctx = ToolContext::new().with_config(config);
+    assert_eq!(ctx.limits().max_array_size(), 2);
+    assert!(ctx.session().is_none());
+
+    let err = DefaultToolRegistry
+        .execute_tool_with_context("add", &json!({ "numbers": [1, 2, 3] }), &ctx)
+        .unwrap_err();
+    assert_eq!(err.code, -32002);
+
+    // The default context uses the built-in limits
+    assert!(DefaultToolRegistry
+        .execute_tool("add", &json!({ "numbers": [1, 2, 3] }))
+        .is_ok());
+}
@@ -0,0 +1,156 @@
+// Tests for the typed `math` API and its agreement with the JSON tools
+
+use rust_math_mcp::math::equations::{self, Point, QuadraticRoots};
+use rust_math_mcp::math::statistics::{self, Modes, Sample};
+use rust_math_mcp::math::{advanced, algebra, basic_math, combinatorics, finance, trigonometry};
+use rust_math_mcp::{DefaultToolRegistry, McpError, ToolRegistry};
+use serde_json::{json, Value};
+
+fn call(tool: &str, arguments: Value) -> Result<Value, McpError> {
+    DefaultToolRegistry.execute_tool(tool, &arguments)
+}
+
+#[test]
+fn test_statistics() {
+    let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+    assert_eq!(statistics::mean(&data).unwrap(), 5.0);
+    assert_eq!(statistics::median(&data).unwrap(), 4.5);
+    assert_eq!(
+        statistics::variance(&data, Sample::Population).unwrap(),
+        4.0
+    );
+    assert_eq!(statistics::std_dev(&data, Sample::Population).unwrap(), 2.0);
+    assert_eq!(
+        statistics::variance(&data, Sample::Sample).unwrap(),
+        32.0 / 7.0
+    );
+    assert_eq!(
+        statistics::mode(&data).unwrap(),
+        Some(Modes {
+            values: vec![4.0],
+            frequency: 3
+        })
+    );
+    assert_eq!(statistics::mode(&[1.0, 2.0]).unwrap(), None);
+    assert_eq!(statistics::sum(&data), 40.0);
+    assert_eq!(statistics::min(&data).unwrap(), 2.0);
+
+    // The input is left untouched
+    let unsorted = [3.0, 1.0, 2.0];
+    assert_eq!(statistics::median(&unsorted).unwrap(), 2.0);
+    assert_eq!(unsorted, [3.0, 1.0, 2.0]);
+
+    // NaN is ordered above every number instead of panicking
+    let with_nan = [f64::NAN, 1.0, 3.0];
+    assert_eq!(statistics::median(&with_nan).unwrap(), 3.0);
+    assert_eq!(statistics::min(&with_nan).unwrap(), 1.0);
+    assert!(statistics::max(&with_nan).unwrap().is_nan());
+
+    let err = statistics::variance(&[], Sample::Sample).unwrap_err();
+    assert_eq!(err.code, -32001);
+    assert_eq!(err.message, "Cannot calculate variance of empty array");
+}
+
+#[test]
+fn test_integers() {
+    assert_eq!(algebra::factorial(20).unwrap(), 2_432_902_008_176_640_000);
+    assert!(algebra::factorial(algebra::MAX_FACTORIAL).is_ok());
+    assert!(algebra::factorial(algebra::MAX_FACTORIAL + 1).is_err());
+    assert_eq!(algebra::gcd(-48, 18), 6);
+    assert_eq!(algebra::lcm(4, -6).unwrap(), 12);
+    assert_eq!(algebra::lcm(0, 6).unwrap(), 0);
+    assert_eq!(algebra::gcd(i64::MIN, 0), 1 << 63);
+
+    assert_eq!(combinatorics::permutation(5, 2).unwrap(), 20);
+    assert_eq!(combinatorics::combination(52, 5).unwrap(), 2_598_960);
+    assert_eq!(combinatorics::combination(5, 0).unwrap(), 1);
+    assert_eq!(combinatorics::permutation(1000, 2).unwrap(), 999_000);
+    assert_eq!(combinatorics::combination(1000, 999).unwrap(), 1000);
+    assert_eq!(combinatorics::permutation(u64::MAX, 0).unwrap(), 1);
+    assert!(combinatorics::permutation(100, 50).is_err());
+    assert_eq!(
+        combinatorics::combination(2, 3).unwrap_err().message,
+        "Combination: r must be <= n"
+    );
+}
+
+#[test]
+fn test_geometry_and_equations() {
+    let (origin, p) = (Point::new(0.0, 0.0), Point::new(3.0, 4.0));
+    assert_eq!(equations::distance(origin, p), 5.0);
+    assert_eq!(equations::midpoint(origin, p), Point::new(1.5, 2.0));
+    assert!(equations::slope(p, Point::new(3.0, 9.0)).is_err());
+    assert_eq!(equations::hypotenuse(3.0, 4.0), 5.0);
+    assert_eq!(equations::leg(5.0, 4.0), 3.0);
+
+    assert_eq!(
+        equations::quadratic_formula(1.0, -3.0, 2.0).unwrap(),
+        QuadraticRoots::Distinct(2.0, 1.0)
+    );
+    assert_eq!(
+        equations::quadratic_formula(1.0, 2.0, 1.0).unwrap(),
+        QuadraticRoots::Repeated(-1.0)
+    );
+    assert_eq!(
+        equations::quadratic_formula(1.0, 0.0, 1.0).unwrap(),
+        QuadraticRoots::Complex
+    );
+    assert!(equations::quadratic_formula(0.0, 1.0, 1.0).is_err());
+}
+
+#[test]
+fn test_other_categories() {
+    assert_eq!(basic_math::round(12.3456, 2), 12.35);
+    assert_eq!(basic_math::round(1234.0, -2), 1200.0);
+    assert_eq!(
+        basic_math::divide(1.0, 0.0).unwrap_err().message,
+        "Division by zero"
+    );
+    assert_eq!(finance::simple_interest(1000.0, 0.05, 3.0), 150.0);
+    assert_eq!(finance::percentage(45.0, 60.0), 75.0);
+    assert_eq!(finance::percent_of(75.0, 60.0), 45.0);
+    assert_eq!(advanced::log10(1000.0).unwrap(), 3.0);
+    assert!(advanced::logarithm(8.0, 1.0).is_err());
+    assert!(trigonometry::asin(2.0).is_err());
+    assert_eq!(
+        trigonometry::radians_to_degrees(std::f64::consts::PI),
+        180.0
+    );
+}
+
+#[test]
+fn test_tools_wrap_the_typed_api() {
+    let numbers = [1.5, 2.5, 2.5, 10.0, -3.0];
+
+    let result = call("variance", json!({ "numbers": numbers, "sample": true })).unwrap();
+    assert_eq!(
+        result["result"],
+        statistics::variance(&numbers, Sample::Sample).unwrap()
+    );
+    let result = call("median", json!({ "numbers": numbers })).unwrap();
+    assert_eq!(result["result"], statistics::median(&numbers).unwrap());
+    let result = call(
+        "compound_interest",
+        json!({ "principal": 1000, "rate": 0.05, "time": 10, "compounds_per_year": 12 }),
+    )
+    .unwrap();
+    assert_eq!(
+        result["result"],
+        finance::compound_interest(1000.0, 0.05, 10.0, 12.0)
+    );
+    let result = call("factorial", json!({ "n": 10 })).unwrap();
+    assert_eq!(result["result"], algebra::factorial(10).unwrap() as f64);
+
+    // Errors are the same too
+    let tool_err = call("sqrt", json!({ "number": -1 })).unwrap_err();
+    let typed_err = basic_math::sqrt(-1.0).unwrap_err();
+    assert_eq!(tool_err.code, typed_err.code);
+    assert_eq!(tool_err.message, typed_err.message);
+
+    // Argument checks that only make sense for JSON stay in the tools
+    let err = call("factorial", json!({ "n": -1 })).unwrap_err();
+    assert_eq!(err.message, "Factorial is not defined for negative numbers");
+    let err = call("permutation", json!({ "n": 5, "r": -1 })).unwrap_err();
+    assert_eq!(err.message, "Permutation: n and r must be non-negative");
+}
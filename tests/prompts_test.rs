@@ -0,0 +1,115 @@
+// Tests for the MCP prompt templates
+
+use rmcp::model::{PromptMessageContent, PromptMessageRole};
+use rust_math_mcp::prompts::{self, Role};
+use rust_math_mcp::MathService;
+use serde_json::{json, Map, Value};
+
+fn args(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn test_service_lists_prompts() {
+    let prompts = MathService::new()._list_prompts();
+    let names: Vec<&str> = prompts.iter().map(|p| p.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        ["solve_word_problem", "analyze_dataset", "loan_comparison"]
+    );
+    let loan = &prompts[2];
+    let arguments = loan.arguments.as_ref().unwrap();
+    assert!(arguments
+        .iter()
+        .any(|a| a.name == "principal" && a.required == Some(true)));
+    assert!(arguments
+        .iter()
+        .any(|a| a.name == "compounds_per_year" && a.required == Some(false)));
+}
+
+#[test]
+fn test_solve_word_problem() {
+    let problem = "A train travels 120 km in 1.5 hours. What is its average speed?";
+    let messages =
+        prompts::get("solve_word_problem", &args(json!({ "problem": problem }))).unwrap();
+
+    assert_eq!(messages[0].role, Role::User);
+    assert!(messages[0].text.contains(problem));
+    assert!(messages[0].text.contains("batch_operations"));
+    assert_eq!(messages.last().unwrap().role, Role::Assistant);
+}
+
+#[test]
+fn test_analyze_dataset() {
+    let messages = prompts::get(
+        "analyze_dataset",
+        &args(json!({ "data": "[2, 4, 4, 4, 5, 5, 7, 9]", "sample": "false", "context": "test scores" })),
+    )
+    .unwrap();
+
+    let text = &messages[0].text;
+    assert!(text.contains("8 values"));
+    assert!(text.contains("test scores"));
+    assert!(text.contains("\"tool\":\"std_dev\""));
+    assert!(text.contains("\"sample\":false"));
+}
+
+#[test]
+fn test_loan_comparison() {
+    let messages = prompts::get(
+        "loan_comparison",
+        &args(json!({ "principal": "20000", "rates": "4.5%, 0.0525", "years": 5 })),
+    )
+    .unwrap();
+
+    let text = &messages[0].text;
+    assert!(text.contains("option_1: 4.5% per year"));
+    assert!(text.contains("option_2: 5.25% per year"));
+    assert!(text.contains("\"tool\":\"compound_interest\""));
+    assert!(text.contains("\"compounds_per_year\":12.0"));
+}
+
+#[test]
+fn test_prompt_argument_errors() {
+    let missing =
+        prompts::get("loan_comparison", &args(json!({ "principal": "1000" }))).unwrap_err();
+    assert_eq!(missing.code, -32602);
+    assert!(missing.message.contains("rates"));
+
+    let bad_data =
+        prompts::get("analyze_dataset", &args(json!({ "data": "1, two, 3" }))).unwrap_err();
+    assert!(bad_data.message.contains("data"));
+
+    let bad_rates = prompts::get(
+        "loan_comparison",
+        &args(json!({ "principal": 1000, "rates": "cheap", "years": 3 })),
+    )
+    .unwrap_err();
+    assert!(bad_rates.message.contains("rates"));
+
+    let unknown = prompts::get("write_poem", &Map::new()).unwrap_err();
+    assert!(unknown.message.contains("Unknown prompt"));
+}
+
+#[test]
+fn test_service_get_prompt() {
+    let result = MathService::new()
+        ._get_prompt(
+            "solve_word_problem",
+            Some(args(json!({ "problem": "What is 15% of 80?" }))),
+        )
+        .unwrap();
+
+    assert!(result.description.is_some());
+    assert_eq!(result.messages[0].role, PromptMessageRole::User);
+    let PromptMessageContent::Text { text } = &result.messages[0].content else {
+        panic!("expected text content");
+    };
+    assert!(text.contains("What is 15% of 80?"));
+
+    let err = MathService::new()
+        ._get_prompt("solve_word_problem", None)
+        .unwrap_err();
+    assert_eq!(err.code.0, -32602);
+}
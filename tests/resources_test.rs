@@ -0,0 +1,252 @@
+// Tests for the math:// resources
+
+use rmcp::model::ResourceContents;
+use rust_math_mcp::config::Config;
+use rust_math_mcp::metrics;
+use rust_math_mcp::resources;
+use rust_math_mcp::tools::{get_all_tools, DefaultToolRegistry, ToolRegistry, CATEGORIES};
+use rust_math_mcp::MathService;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+/// Held by tests that look at the process-wide metrics
+static METRICS: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_constants_have_units() {
+    let constants = resources::read("math://constants", &Config::default()).unwrap();
+
+    let pi = constants["mathematical"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "pi")
+        .unwrap();
+    assert_eq!(pi["value"], std::f64::consts::PI);
+    assert!(pi.get("unit").is_none());
+
+    let c = constants["physical"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "speed_of_light")
+        .unwrap();
+    assert_eq!(c["value"], 299_792_458.0);
+    assert_eq!(c["unit"], "m/s");
+}
+
+#[test]
+fn test_formulas_per_category() {
+    let finance = resources::read("math://formulas/finance", &Config::default()).unwrap();
+
+    assert_eq!(finance["category"], "finance");
+    let compound = finance["formulas"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["tool"] == "compound_interest")
+        .unwrap();
+    assert_eq!(compound["formula"], "A = P(1 + r/n)^(nt)");
+
+    for category in CATEGORIES {
+        let uri = format!("math://formulas/{}", category.name);
+        assert!(resources::read(&uri, &Config::default()).is_ok(), "{}", uri);
+    }
+}
+
+#[test]
+fn test_every_tool_documented_with_working_example() {
+    for tool in get_all_tools().as_array().unwrap() {
+        let name = tool["name"].as_str().unwrap();
+        let doc = resources::read(&format!("math://tools/{}", name), &Config::default()).unwrap();
+
+        assert_eq!(doc["name"], name);
+        assert_eq!(doc["inputSchema"], tool["inputSchema"]);
+        assert!(doc["category"].is_string(), "{}", name);
+        let result = &doc["example"]["result"];
+        assert!(
+            result.is_object() && result.get("error").is_none(),
+            "example for {} failed: {}",
+            name,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_tool_doc_example_result() {
+    let doc = resources::read("math://tools/quadratic_formula", &Config::default()).unwrap();
+
+    assert_eq!(doc["category"], "equations");
+    assert_eq!(
+        doc["example"]["result"]["roots"],
+        serde_json::json!([2.0, 1.0])
+    );
+}
+
+#[test]
+fn test_unknown_resources() {
+    for uri in [
+        "math://nothing",
+        "math://formulas/alchemy",
+        "math://tools/transmute",
+        "file:///etc/passwd",
+    ] {
+        let err = resources::read(uri, &Config::default()).unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains(uri));
+    }
+}
+
+#[test]
+fn test_service_lists_and_reads_resources() {
+    let service = MathService::new();
+
+    let listed = service._list_resources();
+    let tool_count = get_all_tools().as_array().unwrap().len();
+    assert_eq!(listed.len(), 2 + CATEGORIES.len() + tool_count);
+    assert!(listed.iter().any(|r| r.uri == "math://tools/add"));
+
+    let templates = service._list_resource_templates();
+    assert_eq!(templates.len(), 2);
+
+    let read = service._read_resource("math://tools/add").unwrap();
+    let ResourceContents::TextResourceContents {
+        text, mime_type, ..
+    } = &read.contents[0]
+    else {
+        panic!("expected text contents");
+    };
+    assert_eq!(mime_type.as_deref(), Some("application/json"));
+    let doc: Value = serde_json::from_str(text).unwrap();
+    assert_eq!(doc["example"]["result"]["result"], 10.0);
+}
+
+#[test]
+fn test_disabled_tools_have_no_resources() {
+    let mut config = Config {
+        enabled_categories: Some(["finance".to_string()].into()),
+        ..Config::default()
+    };
+    config.disabled_tools.insert("percentage".to_string());
+
+    let uris: Vec<String> = resources::list(&config)
+        .into_iter()
+        .map(|resource| resource.uri)
+        .collect();
+    assert!(uris.contains(&"math://tools/compound_interest".to_string()));
+    assert!(!uris.contains(&"math://tools/percentage".to_string()));
+    assert!(!uris.contains(&"math://tools/add".to_string()));
+    assert!(!uris.contains(&"math://formulas/basic_math".to_string()));
+
+    for uri in [
+        "math://tools/percentage",
+        "math://tools/add",
+        "math://formulas/basic_math",
+    ] {
+        assert_eq!(
+            resources::read(uri, &config).unwrap_err().code,
+            -32602,
+            "{}",
+            uri
+        );
+    }
+    let finance = resources::read("math://formulas/finance", &config).unwrap();
+    let formulas = finance["formulas"].as_array().unwrap();
+    assert!(formulas
+        .iter()
+        .all(|formula| formula["tool"] != "percentage"));
+    assert!(formulas
+        .iter()
+        .any(|formula| formula["tool"] == "simple_interest"));
+}
+
+#[test]
+fn test_metrics_resource() {
+    let _guard = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let registry = DefaultToolRegistry;
+    registry
+        .execute_tool("lcm", &json!({ "a": 4, "b": 6 }))
+        .unwrap();
+    registry
+        .execute_tool("lcm", &json!({ "a": "four", "b": 6 }))
+        .unwrap_err();
+    // Unknown tools are not recorded
+    registry.execute_tool("transmute", &json!({})).unwrap_err();
+    registry
+        .execute_tool(
+            "batch_operations",
+            &json!({
+                "operations": [
+                    { "id": "bad", "tool": "sqrt", "arguments": { "number": -1 } },
+                    { "id": "next", "tool": "lcm", "arguments": { "a": "${bad.result}", "b": 2 } }
+                ]
+            }),
+        )
+        .unwrap();
+
+    let metrics = resources::read("math://metrics", &Config::default()).unwrap();
+    let lcm = &metrics["tools"]["lcm"];
+    assert!(lcm["calls"].as_u64().unwrap() >= 2);
+    assert!(lcm["errors"]["-32602"].as_u64().unwrap() >= 1);
+    assert!(lcm["skipped"].as_u64().unwrap() >= 1);
+    assert!(lcm["latency_ms"]["buckets"].as_array().unwrap().len() > 1);
+    assert!(metrics["tools"]["sqrt"]["calls"].as_u64().unwrap() >= 1);
+    assert!(
+        metrics["tools"]["batch_operations"]["calls"]
+            .as_u64()
+            .unwrap()
+            >= 1
+    );
+    assert!(metrics["tools"].get("transmute").is_none());
+    assert!(metrics["cache"]["hits"].is_u64());
+}
+
+#[test]
+fn test_batches_record_no_unknown_tools() {
+    let _guard = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let registry = DefaultToolRegistry;
+    registry
+        .execute_tool(
+            "batch_operations",
+            &json!({
+                "operations": [
+                    { "id": "bad", "tool": "sqrt", "arguments": { "number": -1 } },
+                    { "id": "after_failure", "tool": "conjure", "arguments": { "x": "${bad.result}" } },
+                    { "id": "ok", "tool": "add", "arguments": { "numbers": [1] } },
+                    { "id": "unresolved", "tool": "summon", "arguments": { "x": "${ok.missing}" } }
+                ]
+            }),
+        )
+        .unwrap();
+    registry
+        .execute_tool(
+            "batch_operations",
+            &json!({
+                "operations": [
+                    { "id": "bad", "tool": "sqrt", "arguments": { "number": -1 } },
+                    { "id": "ok", "tool": "add", "arguments": { "numbers": [1] } },
+                    { "id": "skipped", "tool": "banish", "arguments": { "x": "${ok.result}" } }
+                ],
+                "mode": "fail_fast"
+            }),
+        )
+        .unwrap();
+
+    let metrics = metrics::global().snapshot();
+    for tool in ["conjure", "summon", "banish"] {
+        assert!(metrics["tools"].get(tool).is_none(), "{}", tool);
+    }
+}
+
+#[test]
+fn test_reading_resources_records_no_metrics() {
+    let _guard = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let before = metrics::global().snapshot();
+
+    for resource in resources::list(&Config::default()) {
+        resources::read(&resource.uri, &Config::default()).unwrap();
+    }
+
+    assert_eq!(metrics::global().snapshot(), before);
+}
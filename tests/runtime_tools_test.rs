@@ -0,0 +1,244 @@
+// Integration tests for tools registered at runtime by an embedding application
+// The runtime registry is process-wide, so every test uses its own tool names
+
+use rust_math_mcp::config::Config;
+use rust_math_mcp::error::McpResult;
+use rust_math_mcp::tools::basic_math::Add;
+use rust_math_mcp::tools::{DefaultToolRegistry, MathTool, ToolContext, ToolRegistry};
+use rust_math_mcp::transport::socket;
+use rust_math_mcp::MathService;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+fn definition(name: &str) -> Value {
+    json!({
+        "name": name,
+        "description": "Body-mass index from weight (kg) and height (m)",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "weight": { "type": "number" },
+                "height": { "type": "number" }
+            },
+            "required": ["weight", "height"]
+        }
+    })
+}
+
+fn bmi(args: &Value, _ctx: &ToolContext) -> McpResult<Value> {
+    let weight = args["weight"].as_f64().unwrap_or_default();
+    let height = args["height"].as_f64().unwrap_or_default();
+    Ok(json!({ "result": weight / (height * height) }))
+}
+
+fn tool_names(tools: &Value) -> Vec<&str> {
+    tools
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect()
+}
+
+#[test]
+fn test_register_list_execute_unregister() {
+    let registry = DefaultToolRegistry;
+    registry.register(definition("bmi_basic"), bmi).unwrap();
+
+    let tools = registry.get_all_tools();
+    let names = tool_names(&tools);
+    assert!(names.contains(&"add"));
+    assert!(names.contains(&"bmi_basic"));
+
+    let result = registry
+        .execute_tool("bmi_basic", &json!({ "weight": 72.0, "height": 1.8 }))
+        .unwrap();
+    assert!((result["result"].as_f64().unwrap() - 22.222).abs() < 0.001);
+
+    assert!(registry.unregister("bmi_basic"));
+    assert!(!registry.unregister("bmi_basic"));
+    let err = registry
+        .execute_tool("bmi_basic", &json!({ "weight": 72.0, "height": 1.8 }))
+        .unwrap_err();
+    assert!(err.message.contains("Unknown tool"));
+}
+
+#[test]
+fn test_register_rejects_bad_definitions() {
+    let registry = DefaultToolRegistry;
+
+    let err = registry.register(definition("add"), bmi).unwrap_err();
+    assert_eq!(err.code, -32602);
+    assert!(err.message.contains("already registered"));
+
+    let err = registry
+        .register(json!({ "inputSchema": {} }), bmi)
+        .unwrap_err();
+    assert!(err.message.contains("name"));
+
+    let err = registry
+        .register(json!({ "name": "bmi_no_schema" }), bmi)
+        .unwrap_err();
+    assert!(err.message.contains("inputSchema"));
+
+    registry.register(definition("bmi_twice"), bmi).unwrap();
+    assert!(registry.register(definition("bmi_twice"), bmi).is_err());
+    assert!(registry.unregister("bmi_twice"));
+
+    // Built-in tools stay put
+    assert!(!registry.unregister("add"));
+    assert!(registry
+        .execute_tool("add", &json!({ "numbers": [1, 2] }))
+        .is_ok());
+}
+
+#[test]
+fn test_registered_tools_obey_deny_list() {
+    DefaultToolRegistry
+        .register(definition("bmi_denied"), bmi)
+        .unwrap();
+    let config = Config {
+        disabled_tools: BTreeSet::from(["bmi_denied".to_string()]),
+        ..Config::default()
+    };
+    let service = MathService::with_config(Arc::new(config));
+
+    let names: Vec<String> = service
+        ._list_tools()
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+    assert!(!names.contains(&"bmi_denied".to_string()));
+    assert!(DefaultToolRegistry.unregister("bmi_denied"));
+}
+
+#[test]
+fn test_registered_tool_cannot_nest_batches() {
+    // A tool that runs a batch of its own
+    let definition = json!({
+        "name": "double_batch",
+        "description": "Add a number to itself in a batch",
+        "inputSchema": { "type": "object", "properties": { "x": { "type": "number" } } }
+    });
+    DefaultToolRegistry
+        .register(definition, |args, ctx| {
+            let batch = json!({
+                "operations": [{ "id": "sum", "tool": "add", "arguments": { "numbers": [args["x"], args["x"]] } }]
+            });
+            DefaultToolRegistry.execute_tool_with_context("batch_operations", &batch, ctx)
+        })
+        .unwrap();
+
+    let result = DefaultToolRegistry
+        .execute_tool("double_batch", &json!({ "x": 2 }))
+        .unwrap();
+    assert_eq!(result["results"][0]["result"]["result"], 4.0);
+
+    let outer = json!({
+        "operations": [{ "id": "outer", "tool": "double_batch", "arguments": { "x": 2 } }]
+    });
+    let result = DefaultToolRegistry
+        .execute_tool("batch_operations", &outer)
+        .unwrap();
+    assert_eq!(result["results"][0]["success"], false);
+    assert_eq!(
+        result["results"][0]["error"],
+        "batch_operations cannot be nested"
+    );
+    assert!(DefaultToolRegistry.unregister("double_batch"));
+}
+
+#[tokio::test]
+async fn test_registered_math_tool_through_service() {
+    // A `MathTool` is registered with its generated definition
+    struct Sum;
+    impl MathTool for Sum {
+        const NAME: &'static str = "sum_registered";
+        const DESCRIPTION: &'static str = "Add numbers";
+        type Input = <Add as MathTool>::Input;
+        type Output = <Add as MathTool>::Output;
+
+        fn example() -> Value {
+            json!({ "numbers": [1, 2] })
+        }
+
+        fn run(input: Self::Input) -> McpResult<Self::Output> {
+            Add::run(input)
+        }
+    }
+    DefaultToolRegistry.register_tool::<Sum>().unwrap();
+
+    let service = MathService::new();
+    let tool = service
+        ._list_tools()
+        .into_iter()
+        .find(|tool| tool.name == "sum_registered")
+        .unwrap();
+    assert!(tool.output_schema.is_some());
+
+    let mut arguments = serde_json::Map::new();
+    arguments.insert("numbers".to_string(), json!([4, 5]));
+    let result = service
+        ._call_tool("sum_registered", Some(arguments))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["result"], 9.0);
+    assert!(DefaultToolRegistry.unregister("sum_registered"));
+}
+
+#[tokio::test]
+async fn test_clients_notified_of_tool_list_changes() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(socket::serve_tcp(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+
+    let mut stream = BufReader::new(TcpStream::connect(addr).await.unwrap());
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-06-18",
+            "capabilities": {},
+            "clientInfo": { "name": "runtime-tools-test", "version": "1.0" }
+        }
+    });
+    let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    for message in [initialize, initialized] {
+        let line = format!("{}\n", message);
+        stream.get_mut().write_all(line.as_bytes()).await.unwrap();
+    }
+
+    let mut line = String::new();
+    stream.read_line(&mut line).await.unwrap();
+    let response: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(
+        response["result"]["capabilities"]["tools"]["listChanged"],
+        true
+    );
+
+    // Give the session a moment to process `initialized` and subscribe
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    DefaultToolRegistry
+        .register(definition("bmi_notified"), bmi)
+        .unwrap();
+
+    let mut line = String::new();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        stream.read_line(&mut line),
+    )
+    .await
+    .expect("no notification received")
+    .unwrap();
+    let notification: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(notification["method"], "notifications/tools/list_changed");
+    assert!(DefaultToolRegistry.unregister("bmi_notified"));
+}
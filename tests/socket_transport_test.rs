@@ -0,0 +1,225 @@
+// Integration tests for the TCP and Unix socket transports
+// Each connection is an independent newline-delimited JSON-RPC session
+
+use rust_math_mcp::config::Config;
+use rust_math_mcp::transport::socket;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Minimal line-oriented MCP client over any stream
+struct Client<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
+    async fn connect(stream: S) -> Self {
+        let mut client = Self {
+            stream: BufReader::new(stream),
+        };
+        let response = client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "clientInfo": { "name": "socket-test", "version": "1.0" }
+                }),
+            )
+            .await;
+        assert_eq!(response["result"]["serverInfo"]["name"], "rust-math-mcp");
+        client
+            .send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await;
+        client
+    }
+
+    async fn send(&mut self, message: &Value) {
+        let line = format!("{}\n", message);
+        self.stream
+            .get_mut()
+            .write_all(line.as_bytes())
+            .await
+            .unwrap();
+        self.stream.get_mut().flush().await.unwrap();
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Value {
+        self.send(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .await;
+        self.receive().await
+    }
+
+    async fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.stream.read_line(&mut line).await.unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    async fn call(&mut self, tool: &str, arguments: Value) -> Value {
+        let response = self
+            .request(
+                "tools/call",
+                json!({ "name": tool, "arguments": arguments }),
+            )
+            .await;
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        serde_json::from_str(text).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn test_tcp_concurrent_sessions() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(socket::serve_tcp(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+
+    // Both connections stay open while the other is used
+    let mut first = Client::connect(TcpStream::connect(addr).await.unwrap()).await;
+    let mut second = Client::connect(TcpStream::connect(addr).await.unwrap()).await;
+
+    let result = first.call("add", json!({ "numbers": [1, 2] })).await;
+    assert_eq!(result["result"], 3.0);
+    let result = second.call("subtract", json!({ "a": 10, "b": 4 })).await;
+    assert_eq!(result["result"], 6.0);
+    let result = first.call("sqrt", json!({ "number": 81 })).await;
+    assert_eq!(result["result"], 9.0);
+}
+
+#[tokio::test]
+async fn test_batch_progress_notifications() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(socket::serve_tcp(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+    let mut client = Client::connect(TcpStream::connect(addr).await.unwrap()).await;
+
+    client
+        .send(&json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": {
+                "name": "batch_operations",
+                "arguments": { "operations": [
+                    { "id": "a", "tool": "add", "arguments": { "numbers": [1, 2] } },
+                    { "id": "b", "tool": "sqrt", "arguments": { "number": 16 } },
+                    { "id": "c", "tool": "abs", "arguments": { "number": -3 } }
+                ] },
+                "_meta": { "progressToken": "batch-1" }
+            }
+        }))
+        .await;
+
+    let mut progress = Vec::new();
+    let response = loop {
+        let message = client.receive().await;
+        if message["method"] == "notifications/progress" {
+            assert_eq!(message["params"]["progressToken"], "batch-1");
+            assert_eq!(message["params"]["total"], 3.0);
+            progress.push(message["params"]["progress"].as_f64().unwrap());
+        } else {
+            break message;
+        }
+    };
+
+    assert_eq!(response["id"], 7);
+    assert_eq!(progress, vec![1.0, 2.0, 3.0]);
+
+    // No token, no notifications: the next message is the response
+    let result = client.call("add", json!({ "numbers": [1, 2] })).await;
+    assert_eq!(result["result"], 3.0);
+}
+
+#[tokio::test]
+async fn test_undecodable_lines_get_json_rpc_errors() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(socket::serve_tcp(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+    let mut client = Client::connect(TcpStream::connect(addr).await.unwrap()).await;
+
+    let response = client.request("tools/teleport", json!({})).await;
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["error"]["code"], -32601);
+    assert!(response.get("result").is_none());
+
+    let response = client.request("tools/call", json!({ "name": 42 })).await;
+    assert_eq!(response["error"]["code"], -32602);
+
+    client
+        .stream
+        .get_mut()
+        .write_all(b"{not json\n")
+        .await
+        .unwrap();
+    let response = client.receive().await;
+    assert_eq!(response["error"]["code"], -32700);
+    assert!(response["id"].is_null());
+
+    // The session carries on
+    let result = client.call("add", json!({ "numbers": [1, 2] })).await;
+    assert_eq!(result["result"], 3.0);
+}
+
+#[tokio::test]
+async fn test_initialized_notification_is_optional() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(socket::serve_tcp(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+    let mut client = Client {
+        stream: BufReader::new(TcpStream::connect(addr).await.unwrap()),
+    };
+
+    let response = client
+        .request(
+            "initialize",
+            json!({
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "socket-test", "version": "1.0" }
+            }),
+        )
+        .await;
+    // Answered in the client's protocol version
+    assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+
+    let result = client.call("add", json!({ "numbers": [1, 2] })).await;
+    assert_eq!(result["result"], 3.0);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket_session() {
+    use tokio::net::{UnixListener, UnixStream};
+
+    let path = std::env::temp_dir().join(format!("rust-math-mcp-test-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+    tokio::spawn(socket::serve_unix(
+        listener,
+        Arc::new(Config::new()),
+        std::future::pending(),
+    ));
+
+    let mut client = Client::connect(UnixStream::connect(&path).await.unwrap()).await;
+    let result = client.call("multiply", json!({ "numbers": [3, 4] })).await;
+    assert_eq!(result["result"], 12.0);
+
+    let _ = std::fs::remove_file(&path);
+}